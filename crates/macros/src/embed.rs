@@ -1,7 +1,7 @@
 use darling::{ast::NestedMeta, FromMeta};
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
-use syn::ItemStruct;
+use syn::{Fields, Index, ItemStruct};
 
 type SynResult<T> = Result<T, syn::Error>;
 
@@ -26,15 +26,29 @@ pub fn embeddable(attr: TokenStream2, item: TokenStream2) -> SynResult<TokenStre
 
     let struct_name = item_struct.ident;
 
-    let field_embedding = item_struct.fields.iter().map(|field| {
-        let field_name = &field
-            .ident
-            .clone()
-            .expect("Only named fields are supported");
-        quote! {
-            #field_name: self.#field_name.embed(&mut synthesizer, stringify!(#field_name))?
+    let fields_embedding = match &item_struct.fields {
+        Fields::Named(fields) => {
+            let field_embedding = fields.named.iter().map(|field| {
+                let field_name = &field.ident;
+                quote! {
+                    #field_name: self.#field_name.embed(&mut synthesizer, stringify!(#field_name))?
+                }
+            });
+            quote! { { #(#field_embedding),* } }
         }
-    });
+        // Tuple structs: fields are accessed by position and annotated with their index.
+        Fields::Unnamed(fields) => {
+            let field_embedding = (0..fields.unnamed.len()).map(|i| {
+                let index = Index::from(i);
+                let annotation = i.to_string();
+                quote! {
+                    self.#index.embed(&mut synthesizer, #annotation)?
+                }
+            });
+            quote! { ( #(#field_embedding),* ) }
+        }
+        Fields::Unit => quote! { {} },
+    };
 
     Ok(quote! {
         #backed_up_struct
@@ -48,9 +62,7 @@ pub fn embeddable(attr: TokenStream2, item: TokenStream2) -> SynResult<TokenStre
                 annotation: impl Into<alloc::string::String>,
             ) -> Result<Self::Embedded, halo2_proofs::plonk::Error> {
                 let mut synthesizer = synthesizer.namespaced(annotation);
-                Ok(#struct_name {
-                    #(#field_embedding),*
-                })
+                Ok(#struct_name #fields_embedding)
             }
         }
     })
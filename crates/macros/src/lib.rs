@@ -7,7 +7,8 @@ mod embed;
 /// # Requirements
 ///
 /// 1. All the fields of the struct already implement `Embed`.
-/// 2. Only structs with named fields are supported
+/// 2. Only structs with named fields or tuple structs are supported. Fields of a tuple struct are
+///    annotated with their index (`"0"`, `"1"`, ...).
 /// 3. `halo2_proofs` must be in scope.
 /// 4. Can be used only in the `shielder_circuits` crate.
 ///
@@ -6,7 +6,9 @@ use rand_core::{OsRng, SeedableRng};
 use shielder_circuits::{
     circuits::{
         deposit::DepositProverKnowledge, merkle::MerkleProverKnowledge,
-        new_account::NewAccountProverKnowledge, withdraw::WithdrawProverKnowledge, Params,
+        new_account::NewAccountProverKnowledge,
+        new_account_with_deposit::NewAccountWithDepositProverKnowledge,
+        withdraw::WithdrawProverKnowledge, Params,
     },
     consts::merkle_constants::NOTE_TREE_HEIGHT,
     generate_keys_with_min_k, generate_proof, generate_setup_params, Fr, ProverKnowledge, G1,
@@ -53,6 +55,7 @@ fn measure_circuit<PK: ProverKnowledge>(circuit_name: &str) {
 
 fn main() {
     measure_circuit::<NewAccountProverKnowledge<Fr>>("New account");
+    measure_circuit::<NewAccountWithDepositProverKnowledge<Fr>>("New account with deposit");
     measure_circuit::<DepositProverKnowledge<Fr>>("Deposit");
     measure_circuit::<WithdrawProverKnowledge<Fr>>("Withdraw");
     measure_circuit::<MerkleProverKnowledge<NOTE_TREE_HEIGHT, Fr>>("Merkle");
@@ -0,0 +1,275 @@
+use alloc::vec::Vec;
+
+use halo2_proofs::{
+    circuit,
+    plonk::{Advice, ConstraintSystem, Error},
+};
+
+use crate::{
+    chips::{range_check::RangeCheckChip, shortlist_hash::Shortlist, sum::SumChip},
+    column_pool::{ColumnPool, ConfigPhase},
+    consts::RANGE_PROOF_NUM_WORDS,
+    gates::{
+        balance_update::{BalanceUpdateGate, BalanceUpdateGateInput},
+        sum::SumGate,
+        Gate,
+    },
+    synthesizer::Synthesizer,
+    AssignedCell, Field, Fr,
+};
+
+/// Chip that proves that a multi-token balance update applies `update_value` to exactly one token.
+#[derive(Clone, Debug)]
+pub struct BalanceUpdateChip {
+    gate: BalanceUpdateGate,
+    sum: SumChip,
+    range_check: RangeCheckChip,
+}
+
+impl BalanceUpdateChip {
+    /// Configures the chip together with the range check and sum chips it depends on. The circuit
+    /// must enable constants in some fixed column.
+    pub fn new(
+        system: &mut ConstraintSystem<Fr>,
+        advice_pool: &mut ColumnPool<Advice, ConfigPhase>,
+    ) -> Self {
+        let sum = SumChip::new(SumGate::create_gate(system, advice_pool));
+        Self {
+            gate: BalanceUpdateGate::create_gate(system, advice_pool),
+            range_check: RangeCheckChip::new(system, advice_pool, sum.clone()),
+            sum,
+        }
+    }
+
+    /// Constrains `balances_new[i] = balances_old[i] + selected[i] · update_value`, where
+    /// `selected` is a (witnessed) one-hot vector: every `selected[i]` is binary and they sum up to
+    /// one. `update_value` is signed, i.e., a decrease is passed as the negated amount.
+    ///
+    /// All balances are range-checked to `MAX_TOKEN_ACCUMULATION_BIT_LENGTH` bits, so neither side
+    /// of the update can wrap around the field modulus. Together, this means that exactly one token
+    /// changes by exactly `update_value`, and hence the total is preserved up to `update_value`.
+    pub fn constrain_total_balance_preserved<const NUM_TOKENS: usize>(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        balances_old: &Shortlist<AssignedCell, NUM_TOKENS>,
+        balances_new: &Shortlist<AssignedCell, NUM_TOKENS>,
+        update_value: AssignedCell,
+    ) -> Result<(), Error> {
+        for balance in balances_old.items.iter().chain(&balances_new.items) {
+            self.range_check
+                .constrain_value::<RANGE_PROOF_NUM_WORDS>(synthesizer, balance.clone())?;
+        }
+
+        // The updated token is the first one with a changed balance (or the first one if no balance
+        // changes, i.e., if `update_value` is zero).
+        let updated_token =
+            (0..NUM_TOKENS)
+                .rev()
+                .fold(circuit::Value::known(0), |updated_token, token| {
+                    let changed = balances_old.items[token]
+                        .value()
+                        .zip(balances_new.items[token].value())
+                        .map(|(old, new)| old != new);
+                    changed
+                        .zip(updated_token)
+                        .map(|(changed, updated_token)| if changed { token } else { updated_token })
+                });
+
+        let mut selected = Vec::with_capacity(NUM_TOKENS);
+        for (token, (balance_old, balance_new)) in balances_old
+            .items
+            .iter()
+            .zip(&balances_new.items)
+            .enumerate()
+        {
+            let selected_token = synthesizer.assign_value(
+                "selected",
+                updated_token.map(|updated_token| Fr::from((updated_token == token) as u64)),
+            )?;
+            self.gate.apply_in_new_region(
+                synthesizer,
+                BalanceUpdateGateInput {
+                    balance_old: balance_old.clone(),
+                    balance_new: balance_new.clone(),
+                    selected: selected_token.clone(),
+                    update_value: update_value.clone(),
+                },
+            )?;
+            selected.push(selected_token);
+        }
+
+        let one = synthesizer.assign_constant("one", Fr::ONE)?;
+        self.sum.constrain_sum_many(synthesizer, &selected, one)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+
+    use halo2_proofs::{
+        circuit::{floor_planner::V1, Layouter},
+        dev::MockProver,
+        plonk::{Advice, Circuit, ConstraintSystem, Error},
+    };
+
+    use crate::{
+        chips::{
+            balance_update::BalanceUpdateChip, range_check::test_utils::with_forged_witnesses,
+            shortlist_hash::Shortlist,
+        },
+        column_pool::{ColumnPool, PreSynthesisPhase},
+        consts::MAX_ACCOUNT_BALANCE_PASSING_RANGE_CHECK,
+        embed::Embed,
+        synthesizer::create_synthesizer,
+        Field, Fr, PrimeField,
+    };
+
+    const NUM_TOKENS: usize = 8;
+
+    #[derive(Clone, Debug, Default)]
+    struct BalanceUpdateCircuit {
+        balances_old: [Fr; NUM_TOKENS],
+        balances_new: [Fr; NUM_TOKENS],
+        update_value: Fr,
+    }
+
+    impl Circuit<Fr> for BalanceUpdateCircuit {
+        type Config = (ColumnPool<Advice, PreSynthesisPhase>, BalanceUpdateChip);
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            // The range check and the one-hot check need a constant column.
+            let fixed = meta.fixed_column();
+            meta.enable_constant(fixed);
+
+            let mut advice_pool = ColumnPool::<Advice, _>::new();
+            let chip = BalanceUpdateChip::new(meta, &mut advice_pool);
+
+            (advice_pool.conclude_configuration(), chip)
+        }
+
+        fn synthesize(
+            &self,
+            (pool, chip): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let pool = pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+
+            let balances_old =
+                Shortlist::new(self.balances_old).embed(&mut synthesizer, "balances_old")?;
+            let balances_new =
+                Shortlist::new(self.balances_new).embed(&mut synthesizer, "balances_new")?;
+            let update_value = self.update_value.embed(&mut synthesizer, "update_value")?;
+
+            chip.constrain_total_balance_preserved(
+                &mut synthesizer,
+                &balances_old,
+                &balances_new,
+                update_value,
+            )
+        }
+    }
+
+    const BALANCES: [u64; NUM_TOKENS] = [10, 20, 30, 40, 50, 60, 70, 80];
+
+    fn verify(
+        balances_old: [Fr; NUM_TOKENS],
+        balances_new: [Fr; NUM_TOKENS],
+        update_value: Fr,
+    ) -> Result<(), Vec<String>> {
+        let circuit = BalanceUpdateCircuit {
+            balances_old,
+            balances_new,
+            update_value,
+        };
+        MockProver::run(11, &circuit, vec![])
+            .expect("Mock prover should run successfully")
+            .verify()
+            .map_err(|errors| {
+                errors
+                    .into_iter()
+                    .map(|failure| failure.to_string())
+                    .collect()
+            })
+    }
+
+    fn verify_update(balances_new: [u64; NUM_TOKENS], update_value: Fr) -> Result<(), Vec<String>> {
+        verify(
+            BALANCES.map(Fr::from),
+            balances_new.map(Fr::from),
+            update_value,
+        )
+    }
+
+    #[test]
+    fn conserved_balance_update_passes() {
+        let mut increased = BALANCES;
+        increased[2] += 5;
+        let mut decreased = BALANCES;
+        decreased[7] -= 5;
+
+        assert!(verify_update(increased, Fr::from(5)).is_ok());
+        assert!(verify_update(decreased, -Fr::from(5)).is_ok());
+        assert!(verify_update(BALANCES, Fr::ZERO).is_ok());
+    }
+
+    #[test]
+    fn balance_update_inflating_another_token_fails() {
+        let mut balances_new = BALANCES;
+        balances_new[2] += 5;
+        balances_new[3] += 1; // Fabricated.
+
+        assert!(verify_update(balances_new, Fr::from(5)).is_err());
+    }
+
+    #[test]
+    fn moving_value_across_tokens_fails() {
+        // The total is unchanged, but the value of one token is moved to another one.
+        let mut balances_new = BALANCES;
+        balances_new[2] += 5;
+        balances_new[3] -= 5;
+
+        assert!(verify_update(balances_new, Fr::ZERO).is_err());
+    }
+
+    #[test]
+    fn splitting_the_update_across_tokens_fails() {
+        let mut balances_new = BALANCES;
+        balances_new[2] += 2;
+        balances_new[3] += 3;
+
+        assert!(verify_update(balances_new, Fr::from(5)).is_err());
+    }
+
+    #[test]
+    fn wrapped_balance_fails() {
+        // `0 - 5` wraps around the field modulus, so the updated balance is out of range.
+        let mut balances_old = BALANCES.map(Fr::from);
+        balances_old[0] = Fr::ZERO;
+        let mut balances_new = balances_old;
+        balances_new[0] = -Fr::from(5);
+
+        assert!(
+            with_forged_witnesses(|| verify(balances_old, balances_new, -Fr::from(5))).is_err()
+        );
+    }
+
+    #[test]
+    fn max_balance_passes() {
+        let mut balances_new = BALANCES.map(Fr::from);
+        balances_new[1] = Fr::from_u128(MAX_ACCOUNT_BALANCE_PASSING_RANGE_CHECK);
+        let update_value = balances_new[1] - Fr::from(BALANCES[1]);
+
+        assert!(verify(BALANCES.map(Fr::from), balances_new, update_value).is_ok());
+    }
+}
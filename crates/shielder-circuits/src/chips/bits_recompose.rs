@@ -0,0 +1,255 @@
+use halo2_proofs::plonk::{Advice, ConstraintSystem, Error};
+
+use crate::{
+    column_pool::{ColumnPool, ConfigPhase},
+    consts::FIELD_BITS,
+    gates::{
+        canonical_bit::{CanonicalBitGate, CanonicalBitGateInput},
+        linear_equation::{LinearEquationGate, LinearEquationGateInput, LINEAR_EQUATION_TERMS},
+        Gate,
+    },
+    synthesizer::Synthesizer,
+    AssignedCell, Field, Fr, PrimeField, Value,
+};
+
+/// Chip that ties a scalar to its claimed little-endian bit decomposition, for circuits that
+/// receive both separately.
+#[derive(Clone, Debug)]
+pub struct BitsRecomposeChip {
+    linear_equation: LinearEquationGate,
+    canonical_bit: CanonicalBitGate,
+}
+
+impl BitsRecomposeChip {
+    /// Configures the chip together with its gates. The circuit must enable constants in some
+    /// fixed column.
+    pub fn new(
+        system: &mut ConstraintSystem<Fr>,
+        advice_pool: &mut ColumnPool<Advice, ConfigPhase>,
+    ) -> Self {
+        Self {
+            linear_equation: LinearEquationGate::create_gate(system, advice_pool),
+            canonical_bit: CanonicalBitGate::create_gate(system, advice_pool),
+        }
+    }
+
+    /// Constrains `bits` to be the canonical little-endian binary representation of `value`: every
+    /// bit is binary, `Σ 2^i · bit_i = value` and, read as an integer, the bits are below the field
+    /// modulus. The latter rules out the decompositions of `value + p` (since `2^FIELD_BITS`
+    /// exceeds the modulus, both would otherwise recompose to `value`).
+    pub fn constrain_bits_recompose(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        bits: &[AssignedCell; FIELD_BITS],
+        value: &AssignedCell,
+    ) -> Result<(), Error> {
+        self.constrain_canonical(synthesizer, bits)?;
+        self.constrain_recompose(synthesizer, bits, value)
+    }
+
+    /// Constrains the bits to be binary and not to exceed `p - 1`, comparing them with its bits
+    /// from the most significant one.
+    fn constrain_canonical(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        bits: &[AssignedCell; FIELD_BITS],
+    ) -> Result<(), Error> {
+        let max_value = (-Fr::ONE).to_repr();
+        let zero = synthesizer.zero_cell()?;
+        let one = synthesizer.assign_constant("one", Fr::ONE)?;
+
+        let mut prefix_equal = one.clone();
+        for (i, bit) in bits.iter().enumerate().rev() {
+            let modulus_bit = (max_value.as_ref()[i / 8] >> (i % 8)) & 1 == 1;
+
+            let prefix_equal_next = synthesizer.assign_value(
+                "prefix_equal",
+                if modulus_bit {
+                    prefix_equal.value().cloned() * bit.value().cloned()
+                } else {
+                    prefix_equal.value().cloned()
+                },
+            )?;
+            self.canonical_bit.apply_in_new_region(
+                synthesizer,
+                CanonicalBitGateInput {
+                    bit: bit.clone(),
+                    modulus_bit: if modulus_bit {
+                        one.clone()
+                    } else {
+                        zero.clone()
+                    },
+                    prefix_equal,
+                    prefix_equal_next: prefix_equal_next.clone(),
+                },
+            )?;
+            prefix_equal = prefix_equal_next;
+        }
+
+        Ok(())
+    }
+
+    /// Constrains `Σ 2^i · bit_i = value` (modulo the field order). The bits are recomposed
+    /// `LINEAR_EQUATION_TERMS` at a time, starting from the most significant chunk (padded with
+    /// zeros).
+    fn constrain_recompose(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        bits: &[AssignedCell; FIELD_BITS],
+        value: &AssignedCell,
+    ) -> Result<(), Error> {
+        let zero = synthesizer.zero_cell()?;
+        let chunks = FIELD_BITS.div_ceil(LINEAR_EQUATION_TERMS);
+        let mut accumulator = zero.clone();
+
+        for chunk in (0..chunks).rev() {
+            let terms: [AssignedCell; LINEAR_EQUATION_TERMS] = core::array::from_fn(|i| {
+                bits.get(chunk * LINEAR_EQUATION_TERMS + i)
+                    .unwrap_or(&zero)
+                    .clone()
+            });
+
+            let result = if chunk == 0 {
+                value.clone()
+            } else {
+                let result_value = terms.iter().enumerate().fold(
+                    accumulator.value().cloned()
+                        * Value::known(Fr::from(1u64 << LINEAR_EQUATION_TERMS)),
+                    |sum, (i, term)| {
+                        sum + term.value().cloned() * Value::known(Fr::from(1u64 << i))
+                    },
+                );
+                synthesizer.assign_value("bits_recompose_accumulator", result_value)?
+            };
+
+            self.linear_equation.apply_in_new_region(
+                synthesizer,
+                LinearEquationGateInput {
+                    accumulator,
+                    terms,
+                    result: result.clone(),
+                },
+            )?;
+            accumulator = result;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec;
+
+    use halo2_proofs::{
+        circuit::{floor_planner::V1, Layouter},
+        dev::MockProver,
+        plonk::{Advice, Circuit, ConstraintSystem, Error},
+    };
+
+    use crate::{
+        chips::bits_recompose::BitsRecomposeChip,
+        column_pool::{ColumnPool, PreSynthesisPhase},
+        consts::FIELD_BITS,
+        curve_arithmetic::field_element_to_le_bits,
+        embed::Embed,
+        rng,
+        synthesizer::create_synthesizer,
+        Field, Fr, PrimeField,
+    };
+
+    /// Embeds `self.0` as LE bits and `self.1` as the value they should recompose to.
+    #[derive(Clone, Debug)]
+    struct BitsRecomposeCircuit([Fr; FIELD_BITS], Fr);
+
+    impl Circuit<Fr> for BitsRecomposeCircuit {
+        type Config = (ColumnPool<Advice, PreSynthesisPhase>, BitsRecomposeChip);
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self([Fr::ZERO; FIELD_BITS], Fr::ZERO)
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            // The chip needs a constant column for the bits of the modulus.
+            let fixed = meta.fixed_column();
+            meta.enable_constant(fixed);
+
+            let mut advice_pool = ColumnPool::<Advice, _>::new();
+            let chip = BitsRecomposeChip::new(meta, &mut advice_pool);
+            (advice_pool.conclude_configuration(), chip)
+        }
+
+        fn synthesize(
+            &self,
+            (pool, chip): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let pool = pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+
+            let bits = self.0.embed(&mut synthesizer, "bits")?;
+            let value = self.1.embed(&mut synthesizer, "value")?;
+
+            chip.constrain_bits_recompose(&mut synthesizer, &bits, &value)
+        }
+    }
+
+    fn verify_recompose(bits: [Fr; FIELD_BITS], value: Fr) -> bool {
+        MockProver::run(11, &BitsRecomposeCircuit(bits, value), vec![])
+            .expect("Mock prover should run successfully")
+            .verify()
+            .is_ok()
+    }
+
+    /// LE bits of the integer `repr` (LE bytes), which may exceed the modulus.
+    fn bits_of_repr(repr: [u8; 32]) -> [Fr; FIELD_BITS] {
+        core::array::from_fn(|i| Fr::from(((repr[i / 8] >> (i % 8)) & 1) as u64))
+    }
+
+    #[test]
+    fn consistent_bits_recompose_to_value() {
+        let value = Fr::random(rng());
+        assert!(verify_recompose(field_element_to_le_bits(value), value));
+    }
+
+    #[test]
+    fn extreme_values_recompose() {
+        assert!(verify_recompose([Fr::ZERO; FIELD_BITS], Fr::ZERO));
+        assert!(verify_recompose(
+            field_element_to_le_bits(-Fr::ONE),
+            -Fr::ONE
+        ));
+    }
+
+    #[test]
+    fn inconsistent_bits_do_not_recompose_to_value() {
+        let value = Fr::random(rng());
+        let mut bits = field_element_to_le_bits(value);
+        bits[FIELD_BITS - 1] = Fr::ONE - bits[FIELD_BITS - 1];
+
+        assert!(!verify_recompose(bits, value));
+        assert!(!verify_recompose(
+            field_element_to_le_bits(value),
+            value + Fr::ONE
+        ));
+    }
+
+    #[test]
+    fn non_binary_bits_fail() {
+        // `[2, 0, …]` recomposes to `2`, just like the bits of `2`.
+        let mut bits = [Fr::ZERO; FIELD_BITS];
+        bits[0] = Fr::from(2);
+
+        assert!(!verify_recompose(bits, Fr::from(2)));
+    }
+
+    #[test]
+    fn non_canonical_bits_fail() {
+        // The bits of `p` itself recompose to zero modulo `p`.
+        let mut modulus = (-Fr::ONE).to_repr();
+        modulus[0] += 1;
+
+        assert!(!verify_recompose(bits_of_repr(modulus), Fr::ZERO));
+    }
+}
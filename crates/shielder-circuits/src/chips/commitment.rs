@@ -0,0 +1,175 @@
+use halo2_proofs::plonk::Error;
+
+use crate::{
+    poseidon::circuit::{hash, PoseidonChip},
+    synthesizer::Synthesizer,
+    version::COMMITMENT_VERSION,
+    AssignedCell, Fr,
+};
+
+pub mod off_circuit {
+    use crate::{poseidon::off_circuit::hash, version::COMMITMENT_VERSION, Fr};
+
+    /// The commitment to the `(recipient, amount, nonce)` tuple of a transaction.
+    pub fn commitment(recipient: Fr, amount: Fr, nonce: Fr) -> Fr {
+        hash(&[
+            Fr::from(COMMITMENT_VERSION as u64),
+            recipient,
+            amount,
+            nonce,
+        ])
+    }
+}
+
+/// Computes the commitment to the `(recipient, amount, nonce)` tuple of a transaction.
+///
+/// Previously, the `Commitment` public input was an opaque value: the circuits only exposed it, and
+/// its relation to the transaction was up to the caller (e.g., the contract hashing its calldata).
+/// Deriving it in-circuit changes its meaning, hence it is domain-separated with
+/// [`COMMITMENT_VERSION`], which callers must check before relying on its structure.
+#[derive(Clone, Debug)]
+pub struct CommitmentChip {
+    poseidon: PoseidonChip,
+}
+
+impl CommitmentChip {
+    pub fn new(poseidon: PoseidonChip) -> Self {
+        Self { poseidon }
+    }
+
+    pub fn compute(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        recipient: AssignedCell,
+        amount: AssignedCell,
+        nonce: AssignedCell,
+    ) -> Result<AssignedCell, Error> {
+        let version = synthesizer
+            .assign_constant("commitment_version", Fr::from(COMMITMENT_VERSION as u64))?;
+        hash(
+            synthesizer,
+            self.poseidon.clone(),
+            [version, recipient, amount, nonce],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+
+    use halo2_proofs::{
+        circuit::{floor_planner::V1, Layouter},
+        dev::MockProver,
+        plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+    };
+
+    use crate::{
+        chips::commitment::{off_circuit, CommitmentChip},
+        column_pool::{ColumnPool, PreSynthesisPhase},
+        config_builder::ConfigsBuilder,
+        embed::Embed,
+        poseidon::off_circuit::hash,
+        synthesizer::create_synthesizer,
+        Fr,
+    };
+
+    #[derive(Clone, Debug, Default)]
+    struct CommitmentCircuit {
+        recipient: Fr,
+        amount: Fr,
+        nonce: Fr,
+    }
+
+    impl Circuit<Fr> for CommitmentCircuit {
+        type Config = (
+            ColumnPool<Advice, PreSynthesisPhase>,
+            CommitmentChip,
+            Column<Instance>,
+        );
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+            let configs_builder = ConfigsBuilder::new(meta).with_poseidon();
+            let chip = CommitmentChip::new(configs_builder.poseidon_chip());
+
+            (configs_builder.finish(), chip, instance)
+        }
+
+        fn synthesize(
+            &self,
+            (pool, chip, instance): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let pool = pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+
+            let recipient = self.recipient.embed(&mut synthesizer, "recipient")?;
+            let amount = self.amount.embed(&mut synthesizer, "amount")?;
+            let nonce = self.nonce.embed(&mut synthesizer, "nonce")?;
+
+            let commitment = chip.compute(&mut synthesizer, recipient, amount, nonce)?;
+            synthesizer.constrain_instance(commitment.cell(), instance, 0)
+        }
+    }
+
+    fn verify(circuit: CommitmentCircuit, commitment: Fr) -> Result<(), Vec<String>> {
+        MockProver::run(7, &circuit, vec![vec![commitment]])
+            .expect("Mock prover should run successfully")
+            .verify()
+            .map_err(|errors| {
+                errors
+                    .into_iter()
+                    .map(|failure| failure.to_string())
+                    .collect()
+            })
+    }
+
+    fn example() -> CommitmentCircuit {
+        CommitmentCircuit {
+            recipient: Fr::from(1),
+            amount: Fr::from(2),
+            nonce: Fr::from(3),
+        }
+    }
+
+    #[test]
+    fn commitment_to_the_transaction_passes() {
+        let commitment = off_circuit::commitment(Fr::from(1), Fr::from(2), Fr::from(3));
+        assert!(verify(example(), commitment).is_ok());
+    }
+
+    #[test]
+    fn commitment_is_separated_by_version() {
+        let (recipient, amount, nonce) = (Fr::from(1), Fr::from(2), Fr::from(3));
+
+        assert_ne!(
+            off_circuit::commitment(recipient, amount, nonce),
+            hash(&[recipient, amount, nonce])
+        );
+    }
+
+    #[test]
+    fn changed_recipient_fails() {
+        let commitment = off_circuit::commitment(Fr::from(1), Fr::from(2), Fr::from(3));
+        let circuit = CommitmentCircuit {
+            recipient: Fr::from(4),
+            ..example()
+        };
+
+        let errors = verify(circuit, commitment).expect_err("Verification should fail");
+        assert!(errors
+            .iter()
+            .any(|error| error.contains("Equality constraint not satisfied")));
+    }
+}
@@ -3,8 +3,16 @@ use macros::embeddable;
 
 use super::{points_add::PointsAddChip, scalar_multiply::ScalarMultiplyChip, sum::SumChip};
 use crate::{
-    chips::scalar_multiply::ScalarMultiplyChipInput, consts::FIELD_BITS,
-    curve_arithmetic::GrumpkinPoint, embed::Embed, synthesizer::Synthesizer, AssignedCell,
+    chips::{bits_recompose::BitsRecomposeChip, scalar_multiply::ScalarMultiplyChipInput},
+    consts::FIELD_BITS,
+    curve_arithmetic::GrumpkinPoint,
+    embed::{embed_constants, Embed},
+    gates::{
+        non_zero::{NonZeroGate, NonZeroGateInput},
+        Gate,
+    },
+    synthesizer::Synthesizer,
+    AssignedCell, Field, Value,
 };
 
 #[derive(Clone, Debug)]
@@ -40,6 +48,9 @@ pub struct ElGamalEncryptionChip {
     pub multiply_chip: ScalarMultiplyChip,
     pub add_chip: PointsAddChip,
     pub sum_chip: SumChip,
+    /// Chip recomposing the salt and gate proving it non-zero. When present, [`Self::encrypt`]
+    /// rejects a zero salt (see [`Self::constrain_salt_non_zero`]).
+    pub salt_check: Option<(BitsRecomposeChip, NonZeroGate)>,
 }
 
 impl ElGamalEncryptionChip {
@@ -52,9 +63,45 @@ impl ElGamalEncryptionChip {
             multiply_chip,
             add_chip,
             sum_chip,
+            salt_check: None,
         }
     }
 
+    /// Constrains the salt to be non-zero. With a zero salt, `ciphertext1` is the point at infinity
+    /// and `ciphertext2` is the message itself.
+    ///
+    /// `salt_le_bits` are constrained to be the canonical representation of the salt, i.e., an
+    /// integer in `[1, p)`, where `p` is the order of `Fr`. Since `p` is below the Grumpkin group
+    /// order, such a salt is a non-zero scalar of the group as well. The (negligibly few) salts
+    /// between `p` and the group order are rejected.
+    fn constrain_salt_non_zero(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        (bits_recompose, non_zero): &(BitsRecomposeChip, NonZeroGate),
+        salt_le_bits: &[AssignedCell; FIELD_BITS],
+    ) -> Result<(), Error> {
+        let salt_value = salt_le_bits
+            .iter()
+            .rev()
+            .fold(Value::known(Fr::ZERO), |salt, bit| {
+                salt * Value::known(Fr::from(2u64)) + bit.value().cloned()
+            });
+        let salt = synthesizer.assign_value("salt", salt_value)?;
+        bits_recompose.constrain_bits_recompose(synthesizer, salt_le_bits, &salt)?;
+
+        let inverse = synthesizer.assign_value(
+            "salt_inverse",
+            salt.value().map(|salt| salt.invert().unwrap_or(Fr::ZERO)),
+        )?;
+        non_zero.apply_in_new_region(
+            synthesizer,
+            NonZeroGateInput {
+                value: salt,
+                inverse,
+            },
+        )
+    }
+
     fn constrain_generator(
         &self,
         synthesizer: &mut impl Synthesizer,
@@ -62,9 +109,7 @@ impl ElGamalEncryptionChip {
     ) -> Result<(), Error> {
         let g = GrumpkinPoint::generator();
 
-        let gx = synthesizer.assign_constant("g.x", g.x)?;
-        let gy = synthesizer.assign_constant("g.y", g.y)?;
-        let gz = synthesizer.assign_constant("g.z", g.z)?;
+        let [gx, gy, gz] = embed_constants(synthesizer, [g.x, g.y, g.z], "g")?;
 
         self.sum_chip
             .constrain_equal(synthesizer, generator.x, gx)?;
@@ -85,6 +130,10 @@ impl ElGamalEncryptionChip {
             salt_le_bits,
         }: &ElGamalEncryptionInput<AssignedCell>,
     ) -> Result<ElGamalEncryptionChipOutput<AssignedCell>, Error> {
+        if let Some(salt_check) = &self.salt_check {
+            self.constrain_salt_non_zero(synthesizer, salt_check, salt_le_bits)?;
+        }
+
         let generator_value = GrumpkinPoint::generator();
         let generator = generator_value.embed(synthesizer, "G1 generator")?;
 
@@ -128,7 +177,7 @@ pub mod off_circuit {
     use rand::RngCore;
 
     use crate::{
-        curve_arithmetic::{self, GrumpkinPoint},
+        curve_arithmetic::{self, GrumpkinPoint, GrumpkinPointAffine},
         field_element_to_le_bits,
     };
 
@@ -152,13 +201,20 @@ pub mod off_circuit {
     ) -> (GrumpkinPoint<Fr>, GrumpkinPoint<Fr>) {
         let generator = GrumpkinPoint::generator();
         let salt_bits = field_element_to_le_bits(encryption_salt);
-        let shared_secret = curve_arithmetic::scalar_multiply(public_key, salt_bits);
         let ciphertext1 = curve_arithmetic::scalar_multiply(generator, salt_bits);
-        let ciphertext2 = curve_arithmetic::points_add(message, shared_secret);
+        let ciphertext2 =
+            curve_arithmetic::points_add(message, shared_secret(public_key, encryption_salt));
 
         (ciphertext1, ciphertext2)
     }
 
+    /// The secret `public_key · salt` masking the message in the 2nd ciphertext, i.e.,
+    /// `ciphertext2 = message + shared_secret`. Lets anyone knowing the salt check the ciphertext
+    /// without the private key.
+    pub fn shared_secret(public_key: GrumpkinPoint<Fr>, salt: grumpkin::Fr) -> GrumpkinPoint<Fr> {
+        curve_arithmetic::scalar_multiply(public_key, field_element_to_le_bits(salt))
+    }
+
     pub fn decrypt(
         ciphertext1: GrumpkinPoint<Fr>,
         ciphertext2: GrumpkinPoint<Fr>,
@@ -168,6 +224,18 @@ pub mod off_circuit {
         let shared_secret = curve_arithmetic::scalar_multiply(ciphertext1, private_key_bits);
         ciphertext2 - shared_secret
     }
+
+    /// Recovers the viewing key from the ciphertexts published by `NewAccountCircuit`. The viewing
+    /// key is encrypted as a point whose x-coordinate is the key itself, so this is just decryption
+    /// followed by taking `x` of the (normalized) message.
+    pub fn recover_viewing_key(
+        ciphertext1: GrumpkinPointAffine<Fr>,
+        ciphertext2: GrumpkinPointAffine<Fr>,
+        private_key: grumpkin::Fr,
+    ) -> Fr {
+        let message = decrypt(ciphertext1.into(), ciphertext2.into(), private_key);
+        curve_arithmetic::normalize_point(message).x
+    }
 }
 
 #[cfg(test)]
@@ -182,7 +250,7 @@ mod tests {
         arithmetic::Field,
         circuit::{floor_planner::V1, Layouter},
         dev::MockProver,
-        halo2curves::{bn256::Fr, grumpkin},
+        halo2curves::{bn256::Fr, ff::PrimeField, grumpkin},
         plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
     };
 
@@ -190,18 +258,22 @@ mod tests {
         off_circuit, ElGamalEncryptionChip, ElGamalEncryptionChipOutput, ElGamalEncryptionInput,
     };
     use crate::{
+        chips::viewing_key::off_circuit::derive_viewing_key,
         column_pool::{ColumnPool, PreSynthesisPhase},
         config_builder::ConfigsBuilder,
         curve_arithmetic::{field_element_to_le_bits, normalize_point, GrumpkinPoint},
         embed::Embed,
-        generate_keys, rng,
+        generate_keys,
+        new_account::off_circuit::encode_viewing_key_as_point,
+        rng,
         synthesizer::create_synthesizer,
     };
 
+    /// With `CHECK_SALT`, the chip is configured to reject a zero salt.
     #[derive(Clone, Debug, Default)]
-    struct ElGamalEncryptionCircuit(ElGamalEncryptionInput<Fr>);
+    struct ElGamalEncryptionCircuit<const CHECK_SALT: bool>(ElGamalEncryptionInput<Fr>);
 
-    impl Circuit<Fr> for ElGamalEncryptionCircuit {
+    impl<const CHECK_SALT: bool> Circuit<Fr> for ElGamalEncryptionCircuit<CHECK_SALT> {
         type Config = (
             ColumnPool<Advice, PreSynthesisPhase>,
             ElGamalEncryptionChip,
@@ -221,15 +293,15 @@ mod tests {
             let fixed = meta.fixed_column();
             meta.enable_constant(fixed);
 
-            let configs_builder = ConfigsBuilder::new(meta)
-                .with_scalar_multiply_chip()
-                .with_points_add_chip()
-                .with_sum();
-
-            let chip = ElGamalEncryptionChip {
-                multiply_chip: configs_builder.scalar_multiply_chip(),
-                add_chip: configs_builder.points_add_chip(),
-                sum_chip: configs_builder.sum_chip(),
+            let configs_builder = ConfigsBuilder::new(meta);
+            let (configs_builder, chip) = if CHECK_SALT {
+                let configs_builder = configs_builder.with_el_gamal_encryption_chip_checked();
+                let chip = configs_builder.el_gamal_encryption_chip_checked();
+                (configs_builder, chip)
+            } else {
+                let configs_builder = configs_builder.with_el_gamal_encryption_chip();
+                let chip = configs_builder.el_gamal_encryption_chip();
+                (configs_builder, chip)
             };
 
             (configs_builder.finish(), chip, instance)
@@ -277,10 +349,17 @@ mod tests {
     fn verify(
         input: ElGamalEncryptionInput<Fr>,
         expected: ElGamalEncryptionChipOutput<Fr>,
+    ) -> Result<(), Vec<String>> {
+        verify_with::<false>(input, expected)
+    }
+
+    fn verify_with<const CHECK_SALT: bool>(
+        input: ElGamalEncryptionInput<Fr>,
+        expected: ElGamalEncryptionChipOutput<Fr>,
     ) -> Result<(), Vec<String>> {
         MockProver::run(
             12,
-            &ElGamalEncryptionCircuit(input),
+            &ElGamalEncryptionCircuit::<CHECK_SALT>(input),
             vec![vec![
                 expected.ciphertext1.x,
                 expected.ciphertext1.y,
@@ -314,6 +393,40 @@ mod tests {
         assert_eq!(message, normalize_point(recovered_message));
     }
 
+    #[test]
+    fn second_ciphertext_is_message_masked_with_shared_secret() {
+        let mut rng = rng();
+
+        let (_, public_key) = generate_keys(&mut rng);
+        let message = GrumpkinPoint::random(&mut rng);
+        let salt = grumpkin::Fr::random(rng);
+
+        let (_, ciphertext2) = off_circuit::encrypt(message, public_key, salt);
+        let shared_secret = off_circuit::shared_secret(public_key, salt);
+
+        assert_eq!(message, normalize_point(ciphertext2 - shared_secret));
+    }
+
+    #[test]
+    fn viewing_key_is_recovered_from_ciphertexts() {
+        let mut rng = rng();
+
+        let (private_key, public_key) = generate_keys(&mut rng);
+        // About half of the viewing keys are x-coordinates of some point (and can be encrypted).
+        let (viewing_key, encoded_viewing_key) = (0..)
+            .map(|_| derive_viewing_key(Fr::random(&mut rng)))
+            .find_map(|key| encode_viewing_key_as_point(key).map(|point| (key, point)))
+            .expect("some viewing key is encodable");
+        let salt = grumpkin::Fr::random(&mut rng);
+
+        let (ciphertext1, ciphertext2) =
+            off_circuit::encrypt(encoded_viewing_key.into(), public_key, salt);
+        let recovered =
+            off_circuit::recover_viewing_key(ciphertext1.into(), ciphertext2.into(), private_key);
+
+        assert_eq!(recovered, viewing_key);
+    }
+
     #[test]
     fn encrypt_random_message() {
         let mut rng = rng();
@@ -332,4 +445,53 @@ mod tests {
 
         assert!(verify(input, output).is_ok());
     }
+
+    fn encryption_with_salt(
+        salt: grumpkin::Fr,
+    ) -> (ElGamalEncryptionInput<Fr>, ElGamalEncryptionChipOutput<Fr>) {
+        let mut rng = rng();
+
+        let (_, public_key) = generate_keys(&mut rng);
+        let message = GrumpkinPoint::random(&mut rng);
+        let (ciphertext1, ciphertext2) = off_circuit::encrypt(message, public_key, salt);
+
+        (
+            input(message, public_key, salt),
+            ElGamalEncryptionChipOutput {
+                ciphertext1,
+                ciphertext2,
+            },
+        )
+    }
+
+    #[test]
+    fn checked_encryption_with_random_salt_passes() {
+        let (input, output) = encryption_with_salt(grumpkin::Fr::random(rng()));
+        assert!(verify_with::<true>(input, output).is_ok());
+    }
+
+    #[test]
+    fn checked_encryption_with_zero_salt_fails() {
+        let (input, output) = encryption_with_salt(grumpkin::Fr::ZERO);
+
+        // Without the check, the degenerate encryption is accepted.
+        assert!(verify(input.clone(), output).is_ok());
+
+        let errors = verify_with::<true>(input, output).expect_err("Verification should fail");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("Non-zero gate"));
+    }
+
+    #[test]
+    fn checked_encryption_with_salt_aliasing_zero_fails() {
+        // `p` (the order of `Fr`) is a valid Grumpkin scalar, but its bits recompose to zero in `Fr`.
+        let mut modulus = (-Fr::ONE).to_repr();
+        modulus[0] += 1;
+        let salt = Option::<grumpkin::Fr>::from(grumpkin::Fr::from_repr(modulus))
+            .expect("p is below the Grumpkin group order");
+        let (input, output) = encryption_with_salt(salt);
+
+        assert!(verify(input.clone(), output).is_ok());
+        assert!(verify_with::<true>(input, output).is_err());
+    }
 }
@@ -0,0 +1,133 @@
+use alloc::vec::Vec;
+
+use halo2_proofs::plonk::{Advice, ConstraintSystem, Error};
+
+use crate::{
+    column_pool::{ColumnPool, ConfigPhase},
+    gates::{
+        membership_lookup::{MembershipLookupGate, MembershipLookupGateInput},
+        Gate,
+    },
+    synthesizer::Synthesizer,
+    AssignedCell, Fr,
+};
+
+/// Chip that proves membership of a needle in a haystack of arbitrary (witness-dependent) length
+/// with a single lookup. Meant for large sets (e.g., allow-lists), where `MembershipGate` would be
+/// too costly.
+///
+/// Every haystack is identified by a `tag`, fixed at synthesis time. A needle is checked only
+/// against the haystacks passed with the same tag, so independent sets must use distinct tags.
+#[derive(Clone, Debug)]
+pub struct MembershipLookupChip(MembershipLookupGate);
+
+impl MembershipLookupChip {
+    pub fn new(
+        system: &mut ConstraintSystem<Fr>,
+        advice_pool: &mut ColumnPool<Advice, ConfigPhase>,
+    ) -> Self {
+        Self(MembershipLookupGate::create_gate(system, advice_pool))
+    }
+
+    /// Constrains `needle` to be equal to one of the `haystack` cells (or of the cells of another
+    /// haystack passed with the same `tag`).
+    pub fn constrain_membership(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        tag: u64,
+        needle: AssignedCell,
+        haystack: &[AssignedCell],
+    ) -> Result<(), Error> {
+        let gate_input = MembershipLookupGateInput {
+            tag: synthesizer.assign_constant("membership_lookup_tag", Fr::from(tag))?,
+            needle,
+            haystack: Vec::from(haystack),
+        };
+        self.0.apply_in_new_region(synthesizer, gate_input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{vec, vec::Vec};
+
+    use halo2_proofs::{
+        circuit::{floor_planner::V1, Layouter},
+        dev::{MockProver, VerifyFailure},
+        plonk::{Advice, Circuit, ConstraintSystem, Error},
+    };
+
+    use super::MembershipLookupChip;
+    use crate::{
+        column_pool::{ColumnPool, PreSynthesisPhase},
+        embed::Embed,
+        synthesizer::create_synthesizer,
+        Fr,
+    };
+
+    /// Checks `needles[i]` against `haystacks[i]`, using `i` as the tag.
+    #[derive(Clone, Debug, Default)]
+    struct MembershipLookupCircuit {
+        needles: Vec<Fr>,
+        haystacks: Vec<Vec<Fr>>,
+    }
+
+    impl Circuit<Fr> for MembershipLookupCircuit {
+        type Config = (ColumnPool<Advice, PreSynthesisPhase>, MembershipLookupChip);
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            // The tags are constants.
+            let fixed = meta.fixed_column();
+            meta.enable_constant(fixed);
+
+            let mut advice_pool = ColumnPool::<Advice, _>::new();
+            let chip = MembershipLookupChip::new(meta, &mut advice_pool);
+            (advice_pool.conclude_configuration(), chip)
+        }
+
+        fn synthesize(
+            &self,
+            (pool, chip): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let pool = pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+
+            for (tag, (needle, haystack)) in self.needles.iter().zip(&self.haystacks).enumerate() {
+                let needle = needle.embed(&mut synthesizer, "needle")?;
+                let haystack = haystack.embed(&mut synthesizer, "haystack")?;
+                chip.constrain_membership(&mut synthesizer, tag as u64, needle, &haystack)?;
+            }
+            Ok(())
+        }
+    }
+
+    fn verify(needles: &[u64], haystacks: &[&[u64]]) -> Result<(), Vec<VerifyFailure>> {
+        let circuit = MembershipLookupCircuit {
+            needles: needles.iter().copied().map(Fr::from).collect(),
+            haystacks: haystacks
+                .iter()
+                .map(|haystack| haystack.iter().copied().map(Fr::from).collect())
+                .collect(),
+        };
+        MockProver::run(8, &circuit, vec![])
+            .expect("Mock prover should run successfully")
+            .verify()
+    }
+
+    #[test]
+    fn needles_in_their_haystacks_pass() {
+        assert!(verify(&[2, 30], &[&[1, 2, 3], &[10, 20, 30]]).is_ok());
+    }
+
+    #[test]
+    fn needle_in_another_haystack_fails() {
+        // `10` belongs to the second haystack only.
+        assert!(verify(&[10, 30], &[&[1, 2, 3], &[10, 20, 30]]).is_err());
+    }
+}
@@ -1,10 +1,27 @@
+#[cfg(feature = "chip-balances")]
+pub mod balance_update;
+pub mod bits_recompose;
+pub mod commitment;
+#[cfg(feature = "chip-elgamal")]
 pub mod el_gamal;
 pub mod mac;
+pub mod membership_lookup;
 pub mod note;
+pub mod nullifier;
+#[cfg(feature = "chip-elgamal")]
 pub mod points_add;
+#[cfg(feature = "chip-elgamal")]
+pub mod points_sub;
 pub mod range_check;
+#[cfg(feature = "chip-elgamal")]
 pub mod scalar_multiply;
+#[cfg(feature = "chip-balances")]
+pub mod shortlist_hash;
 pub mod sum;
+#[cfg(feature = "chip-balances")]
+pub mod threshold;
+#[cfg(feature = "chip-elgamal")]
 pub mod to_affine;
+#[cfg(feature = "chip-elgamal")]
 pub mod to_projective;
 pub mod viewing_key;
@@ -1,17 +1,22 @@
-use core::array;
+use core::{array, fmt::Debug, marker::PhantomData};
 
-use halo2_proofs::{arithmetic::Field, plonk::Error};
+use halo2_proofs::plonk::Error;
+use strum::IntoEnumIterator;
 use strum_macros::{EnumCount, EnumIter};
 
 use crate::{
-    chips::sum::SumChip,
-    consts::POSEIDON_RATE,
+    chips::{nullifier::NullifierChip, range_check::RangeCheckChip, sum::SumChip},
+    consts::{POSEIDON_RATE, TOKEN_ADDRESS_RANGE_PROOF_NUM_WORDS},
     embed::Embed,
+    gates::{
+        non_zero::{NonZeroGate, NonZeroGateInput},
+        Gate,
+    },
     instance_wrapper::InstanceWrapper,
     poseidon::circuit::{hash, PoseidonChip},
     synthesizer::Synthesizer,
-    version::NoteVersion,
-    AssignedCell, Fr, Value,
+    version::{NoteLayout, NoteVersion, NOTE_VERSION},
+    AssignedCell, Field, Fr, Value,
 };
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, EnumIter, EnumCount)]
@@ -50,12 +55,147 @@ impl Embed for Note<Value> {
     }
 }
 
+impl<T> Note<T> {
+    /// Returns a builder that has to be given every field (but the version, which defaults to
+    /// `NOTE_VERSION`) exactly once before [`NoteBuilder::build`] becomes available. Unlike a struct
+    /// literal, every value is passed to a setter named after its field.
+    pub fn builder() -> NoteBuilder<T, Unset, Unset, Unset, Unset> {
+        NoteBuilder {
+            version: NOTE_VERSION,
+            id: Unset,
+            nullifier: Unset,
+            account_balance: Unset,
+            token_address: Unset,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// Placeholder for a [`NoteBuilder`] field that has not been set yet.
+#[derive(Copy, Clone, Debug)]
+pub struct Unset;
+
+/// Typestate builder of [`Note<T>`]. Each of the type parameters after `T` is either [`Unset`]
+/// or `T`, depending on whether the respective field has been set.
+#[derive(Copy, Clone, Debug)]
+pub struct NoteBuilder<T, Id, Nullifier, AccountBalance, TokenAddress> {
+    version: NoteVersion,
+    id: Id,
+    nullifier: Nullifier,
+    account_balance: AccountBalance,
+    token_address: TokenAddress,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, Id, Nullifier, AccountBalance, TokenAddress>
+    NoteBuilder<T, Id, Nullifier, AccountBalance, TokenAddress>
+{
+    pub fn version(self, version: NoteVersion) -> Self {
+        Self { version, ..self }
+    }
+}
+
+impl<T, Nullifier, AccountBalance, TokenAddress>
+    NoteBuilder<T, Unset, Nullifier, AccountBalance, TokenAddress>
+{
+    pub fn id(self, id: T) -> NoteBuilder<T, T, Nullifier, AccountBalance, TokenAddress> {
+        NoteBuilder {
+            version: self.version,
+            id,
+            nullifier: self.nullifier,
+            account_balance: self.account_balance,
+            token_address: self.token_address,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, Id, AccountBalance, TokenAddress> NoteBuilder<T, Id, Unset, AccountBalance, TokenAddress> {
+    pub fn nullifier(self, nullifier: T) -> NoteBuilder<T, Id, T, AccountBalance, TokenAddress> {
+        NoteBuilder {
+            version: self.version,
+            id: self.id,
+            nullifier,
+            account_balance: self.account_balance,
+            token_address: self.token_address,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, Id, Nullifier, TokenAddress> NoteBuilder<T, Id, Nullifier, Unset, TokenAddress> {
+    pub fn account_balance(
+        self,
+        account_balance: T,
+    ) -> NoteBuilder<T, Id, Nullifier, T, TokenAddress> {
+        NoteBuilder {
+            version: self.version,
+            id: self.id,
+            nullifier: self.nullifier,
+            account_balance,
+            token_address: self.token_address,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, Id, Nullifier, AccountBalance> NoteBuilder<T, Id, Nullifier, AccountBalance, Unset> {
+    pub fn token_address(
+        self,
+        token_address: T,
+    ) -> NoteBuilder<T, Id, Nullifier, AccountBalance, T> {
+        NoteBuilder {
+            version: self.version,
+            id: self.id,
+            nullifier: self.nullifier,
+            account_balance: self.account_balance,
+            token_address,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> NoteBuilder<T, T, T, T, T> {
+    pub fn build(self) -> Note<T> {
+        Note {
+            version: self.version,
+            id: self.id,
+            nullifier: self.nullifier,
+            account_balance: self.account_balance,
+            token_address: self.token_address,
+        }
+    }
+}
+
 pub mod off_circuit {
-    use halo2_proofs::arithmetic::Field;
+    use halo2_proofs::{arithmetic::Field, halo2curves::ff::PrimeField};
 
-    use crate::{chips::note::Note, consts::POSEIDON_RATE, poseidon::off_circuit::hash, Fr};
+    #[cfg(feature = "chip-balances")]
+    use crate::chips::shortlist_hash::{off_circuit::shortlist_hash, Shortlist};
+    use crate::{
+        chips::note::Note,
+        consts::POSEIDON_RATE,
+        poseidon::off_circuit::hash,
+        version::{NoteLayout, NOTE_VERSION},
+        Fr,
+    };
 
+    /// Hash of `note`, in the layout given by its version (see
+    /// [`crate::NoteVersion::hash_layout`]).
     pub fn note_hash(note: &Note<Fr>) -> Fr {
+        match note.version.hash_layout() {
+            NoteLayout::Nested => nested_note_hash(note),
+            NoteLayout::Flat => hash(&[
+                note.version.as_field(),
+                note.id,
+                note.nullifier,
+                note.account_balance,
+                note.token_address,
+            ]),
+        }
+    }
+
+    fn nested_note_hash(note: &Note<Fr>) -> Fr {
         let balance_hash = hash::<POSEIDON_RATE>(&[
             note.account_balance,
             note.token_address,
@@ -75,6 +215,38 @@ pub mod off_circuit {
 
         hash(&input)
     }
+
+    /// Hash of the note that replaces a note holding `old_balance` after a deposit (positive
+    /// `delta`) or a withdrawal (negative `delta`), i.e., the `HashedNewNote` public input.
+    pub fn new_note_hash(
+        id: Fr,
+        new_nullifier: Fr,
+        old_balance: Fr,
+        delta: i128,
+        token_address: Fr,
+    ) -> Fr {
+        let magnitude = Fr::from_u128(delta.unsigned_abs());
+        let account_balance = if delta < 0 {
+            old_balance - magnitude
+        } else {
+            old_balance + magnitude
+        };
+
+        note_hash(&Note {
+            version: NOTE_VERSION,
+            id,
+            nullifier: new_nullifier,
+            account_balance,
+            token_address,
+        })
+    }
+
+    /// Generalization of the balance hash to many tokens. Meant for a future multi-token note
+    /// version.
+    #[cfg(feature = "chip-balances")]
+    pub fn multi_balance_hash<const NUM_TOKENS: usize>(balances: &Shortlist<Fr, NUM_TOKENS>) -> Fr {
+        shortlist_hash(balances)
+    }
 }
 
 /// Chip that is able to calculate note hash
@@ -84,6 +256,9 @@ pub struct NoteChip {
 
     pub sum: SumChip,
     pub poseidon: PoseidonChip,
+    pub range_check: RangeCheckChip,
+    pub non_zero: NonZeroGate,
+    pub nullifier: NullifierChip,
 }
 
 impl NoteChip {
@@ -96,7 +271,8 @@ impl NoteChip {
         synthesizer.assign_constant("note_version", note_version)
     }
 
-    /// Calculates the note_hash as follows:
+    /// Calculates the note_hash in the layout given by `note.version` (see
+    /// [`NoteVersion::hash_layout`]). For version 0:
     ///
     ///   `note_hash = poseidon2(NOTE_VERSION, note.id, note.nullifier,
     ///                          poseidon2(note.balance, note.token_address, 0, 0, 0, 0, 0))`
@@ -104,7 +280,8 @@ impl NoteChip {
     /// The reason for the double nesting and for the padding is historical: we keep this hash shape
     /// for backward compatibility with notes created by the 1st version of Shielder.
     ///
-    /// Constrains `note.token_address` to match the respective public input.
+    /// Constrains `note.token_address` to match the respective public input and to be a valid
+    /// token address (see [`Self::constrain_token_address_range`]).
     pub fn note_hash(
         &self,
         synthesizer: &mut impl Synthesizer,
@@ -112,21 +289,46 @@ impl NoteChip {
     ) -> Result<AssignedCell, Error> {
         let note_version = self.assign_note_version(note, synthesizer)?;
 
-        let h_balance = self.balance_hash(synthesizer, note)?;
-
         self.public_inputs.constrain_cells(
             synthesizer,
             [(note.token_address.clone(), NoteInstance::TokenAddress)],
         )?;
+        self.constrain_token_address_range(synthesizer, note.token_address.clone())?;
+
+        match note.version.hash_layout() {
+            NoteLayout::Nested => {
+                let h_balance = self.balance_hash(synthesizer, note)?;
+                let input = [
+                    note_version,
+                    note.id.clone(),
+                    note.nullifier.clone(),
+                    h_balance,
+                ];
+                hash(synthesizer, self.poseidon.clone(), input)
+            }
+            NoteLayout::Flat => {
+                let input = [
+                    note_version,
+                    note.id.clone(),
+                    note.nullifier.clone(),
+                    note.account_balance.clone(),
+                    note.token_address.clone(),
+                ];
+                hash(synthesizer, self.poseidon.clone(), input)
+            }
+        }
+    }
 
-        let input = [
-            note_version,
-            note.id.clone(),
-            note.nullifier.clone(),
-            h_balance,
-        ];
-
-        hash(synthesizer, self.poseidon.clone(), input)
+    /// Constrains `token_address` to be less than `2^TOKEN_ADDRESS_BIT_LENGTH`, i.e., to fit in the
+    /// Ethereum address space. Otherwise, the prover could use a field element that only matches the
+    /// actual token address after the truncation done off-chain.
+    pub fn constrain_token_address_range(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        token_address: AssignedCell,
+    ) -> Result<(), Error> {
+        self.range_check
+            .constrain_value::<TOKEN_ADDRESS_RANGE_PROOF_NUM_WORDS>(synthesizer, token_address)
     }
 
     fn balance_hash(
@@ -134,7 +336,7 @@ impl NoteChip {
         synthesizer: &mut impl Synthesizer,
         note: &Note<AssignedCell>,
     ) -> Result<AssignedCell, Error> {
-        let zero_cell = synthesizer.assign_constant("Zero", Fr::ZERO)?;
+        let zero_cell = synthesizer.zero_cell()?;
 
         let mut input: [_; POSEIDON_RATE] = array::from_fn(|_| zero_cell.clone());
         input[0] = note.account_balance.clone();
@@ -143,6 +345,71 @@ impl NoteChip {
         hash(synthesizer, self.poseidon.clone(), input)
     }
 
+    /// Constrains `note.account_balance` to match the public input `instance` of `public_inputs`.
+    /// Allows disclosing the balance of an otherwise hidden note.
+    pub fn constrain_balance_equals_public<Id: IntoEnumIterator + Ord + Debug>(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        note: &Note<AssignedCell>,
+        public_inputs: &InstanceWrapper<Id>,
+        instance: Id,
+    ) -> Result<(), Error> {
+        public_inputs.constrain_cells(synthesizer, [(note.account_balance.clone(), instance)])
+    }
+
+    /// Calculates the note hash (see [`Self::note_hash`]) and the hash of `note.nullifier`. The
+    /// nullifier hash is constrained to the public input `nullifier_instance`, while the note hash
+    /// is returned (it is usually proven to be a member of the Merkle tree).
+    pub fn hash_note_and_expose_nullifier<Id: IntoEnumIterator + Ord + Debug>(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        note: &Note<AssignedCell>,
+        public_inputs: &InstanceWrapper<Id>,
+        nullifier_instance: Id,
+    ) -> Result<AssignedCell, Error> {
+        let note_hash = self.note_hash(synthesizer, note)?;
+        let nullifier_hash = self
+            .nullifier
+            .hash_nullifier(synthesizer, note.nullifier.clone())?;
+
+        public_inputs.constrain_cells(synthesizer, [(nullifier_hash, nullifier_instance)])?;
+        Ok(note_hash)
+    }
+
+    /// Constrains `old_nullifier != new_nullifier`, i.e., that a note update does not reuse the
+    /// nullifier of the old note. This is done by exhibiting the inverse of their difference.
+    pub fn constrain_nullifiers_distinct(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        old_nullifier: AssignedCell,
+        new_nullifier: AssignedCell,
+    ) -> Result<(), Error> {
+        let difference = synthesizer.assign_value(
+            "nullifier_difference",
+            new_nullifier.value() - old_nullifier.value(),
+        )?;
+        self.sum.constrain_sum(
+            synthesizer,
+            old_nullifier,
+            difference.clone(),
+            new_nullifier,
+        )?;
+
+        let inverse = synthesizer.assign_value(
+            "nullifier_difference_inverse",
+            difference
+                .value()
+                .map(|difference| difference.invert().unwrap_or(Fr::ZERO)),
+        )?;
+        self.non_zero.apply_in_new_region(
+            synthesizer,
+            NonZeroGateInput {
+                value: difference,
+                inverse,
+            },
+        )
+    }
+
     pub fn increase_balance(
         &self,
         synthesizer: &mut impl Synthesizer,
@@ -194,7 +461,8 @@ mod tests {
 
     use super::{Note, NoteChip, NoteInstance};
     use crate::{
-        circuits::test_utils::expect_prover_success_and_run_verification,
+        chips::range_check::test_utils::with_forged_witnesses,
+        circuits::test_utils::{expect_gate_failure, expect_prover_success_and_run_verification},
         column_pool::{ColumnPool, PreSynthesisPhase},
         config_builder::ConfigsBuilder,
         embed::Embed,
@@ -202,7 +470,7 @@ mod tests {
         poseidon::off_circuit::hash,
         synthesizer::create_synthesizer,
         test_utils::expect_instance_permutation_failures,
-        Fr, NoteVersion, Value,
+        Fr, NoteVersion, UnsupportedNoteVersion, Value,
     };
 
     // Tests `NoteChip`. Constrains the last public input to the output of the function under test.
@@ -211,6 +479,9 @@ mod tests {
         TestNoteHash(Note<Value>),
         TestBalanceIncrease((Value, Value)),
         TestBalanceDecrease((Value, Value)),
+        TestBalanceEqualsPublic(Note<Value>),
+        TestNoteAndNullifierHash(Note<Value>),
+        TestNullifiersDistinct((Value, Value)),
     }
 
     impl TestCircuit {
@@ -224,6 +495,26 @@ mod tests {
             })
         }
 
+        pub fn balance_equals_public_test(note: Note<impl Into<Fr>>) -> Self {
+            TestCircuit::TestBalanceEqualsPublic(Note {
+                version: note.version,
+                id: Value::known(note.id.into()),
+                nullifier: Value::known(note.nullifier.into()),
+                account_balance: Value::known(note.account_balance.into()),
+                token_address: Value::known(note.token_address.into()),
+            })
+        }
+
+        pub fn note_and_nullifier_hash_test(note: Note<impl Into<Fr>>) -> Self {
+            TestCircuit::TestNoteAndNullifierHash(Note {
+                version: note.version,
+                id: Value::known(note.id.into()),
+                nullifier: Value::known(note.nullifier.into()),
+                account_balance: Value::known(note.account_balance.into()),
+                token_address: Value::known(note.token_address.into()),
+            })
+        }
+
         pub fn balance_increase_test(
             balance_old: impl Into<Fr>,
             increase_value: impl Into<Fr>,
@@ -249,6 +540,7 @@ mod tests {
     pub enum TestInstance {
         TokenAddress,
         ChipOutput,
+        NullifierHash,
     }
 
     impl TryFrom<TestInstance> for NoteInstance {
@@ -262,6 +554,16 @@ mod tests {
         }
     }
 
+    fn unknown_note() -> Note<Value> {
+        Note {
+            version: NoteVersion::V0,
+            id: Value::unknown(),
+            nullifier: Value::unknown(),
+            account_balance: Value::unknown(),
+            token_address: Value::unknown(),
+        }
+    }
+
     impl Circuit<Fr> for TestCircuit {
         type Config = (
             NoteChip,
@@ -272,19 +574,26 @@ mod tests {
 
         fn without_witnesses(&self) -> Self {
             match self {
-                TestCircuit::TestNoteHash(_) => TestCircuit::TestNoteHash(Note {
-                    version: NoteVersion::new(0),
-                    id: Value::unknown(),
-                    nullifier: Value::unknown(),
-                    account_balance: Value::unknown(),
-                    token_address: Value::unknown(),
+                // The version determines the shape of the circuit, so it is not a witness.
+                TestCircuit::TestNoteHash(note) => TestCircuit::TestNoteHash(Note {
+                    version: note.version,
+                    ..unknown_note()
                 }),
+                TestCircuit::TestBalanceEqualsPublic(_) => {
+                    TestCircuit::TestBalanceEqualsPublic(unknown_note())
+                }
+                TestCircuit::TestNoteAndNullifierHash(_) => {
+                    TestCircuit::TestNoteAndNullifierHash(unknown_note())
+                }
                 TestCircuit::TestBalanceIncrease(_) => {
                     TestCircuit::TestBalanceIncrease((Value::unknown(), Value::unknown()))
                 }
                 TestCircuit::TestBalanceDecrease(_) => {
                     TestCircuit::TestBalanceDecrease((Value::unknown(), Value::unknown()))
                 }
+                TestCircuit::TestNullifiersDistinct(_) => {
+                    TestCircuit::TestNullifiersDistinct((Value::unknown(), Value::unknown()))
+                }
             }
         }
 
@@ -327,6 +636,39 @@ mod tests {
 
                     chip.decrease_balance(&mut synthesizer, balance_old, decrease_value)?
                 }
+
+                TestCircuit::TestBalanceEqualsPublic(note) => {
+                    let note = note.embed(&mut synthesizer, "note")?;
+
+                    return chip.constrain_balance_equals_public(
+                        &mut synthesizer,
+                        &note,
+                        &public_inputs,
+                        TestInstance::ChipOutput,
+                    );
+                }
+
+                TestCircuit::TestNullifiersDistinct((old_nullifier, new_nullifier)) => {
+                    let old_nullifier = old_nullifier.embed(&mut synthesizer, "old_nullifier")?;
+                    let new_nullifier = new_nullifier.embed(&mut synthesizer, "new_nullifier")?;
+
+                    return chip.constrain_nullifiers_distinct(
+                        &mut synthesizer,
+                        old_nullifier,
+                        new_nullifier,
+                    );
+                }
+
+                TestCircuit::TestNoteAndNullifierHash(note) => {
+                    let note = note.embed(&mut synthesizer, "note")?;
+
+                    chip.hash_note_and_expose_nullifier(
+                        &mut synthesizer,
+                        &note,
+                        &public_inputs,
+                        TestInstance::NullifierHash,
+                    )?
+                }
             };
 
             public_inputs
@@ -341,7 +683,7 @@ mod tests {
     })]
     fn note_hash_is_calculated_correctly(token_address: Fr) {
         let circuit = TestCircuit::note_hash_test(Note {
-            version: NoteVersion::new(0),
+            version: NoteVersion::V0,
             id: Fr::from(1),
             nullifier: Fr::from(2),
             account_balance: Fr::from(3),
@@ -366,10 +708,86 @@ mod tests {
         assert!(expect_prover_success_and_run_verification(circuit, &pub_input).is_ok());
     }
 
+    #[test]
+    fn v0_note_hash_is_unchanged() {
+        let note = example_note();
+        let expected = hash(&[
+            Fr::from(0),
+            Fr::from(1),
+            Fr::from(2),
+            hash(&[
+                Fr::from(3),
+                Fr::from(4),
+                Fr::ZERO,
+                Fr::ZERO,
+                Fr::ZERO,
+                Fr::ZERO,
+                Fr::ZERO,
+            ]),
+        ]);
+
+        assert_eq!(super::off_circuit::note_hash(&note), expected);
+    }
+
+    #[test]
+    fn v1_note_hash_differs_from_v0_one() {
+        let v0_note = example_note();
+        let v1_note = Note {
+            version: NoteVersion::V1,
+            ..v0_note
+        };
+        let v1_hash = super::off_circuit::note_hash(&v1_note);
+
+        assert_eq!(
+            v1_hash,
+            hash(&[
+                Fr::from(1),
+                Fr::from(1),
+                Fr::from(2),
+                Fr::from(3),
+                Fr::from(4)
+            ])
+        );
+        assert_ne!(v1_hash, super::off_circuit::note_hash(&v0_note));
+
+        let circuit = TestCircuit::note_hash_test(v1_note);
+        let pub_input = [Fr::from(4), v1_hash];
+        assert!(expect_prover_success_and_run_verification(circuit, &pub_input).is_ok());
+    }
+
+    #[test]
+    fn only_versions_with_a_layout_are_accepted() {
+        assert_eq!(NoteVersion::new(0), Ok(NoteVersion::V0));
+        assert_eq!(NoteVersion::new(1), Ok(NoteVersion::V1));
+        assert_eq!(NoteVersion::new(2), Err(UnsupportedNoteVersion(2)));
+
+        assert_eq!(NoteVersion::V0.as_field(), Fr::from(0));
+        assert_eq!(NoteVersion::V1.as_field(), Fr::from(1));
+    }
+
+    #[test]
+    fn note_hash_fails_for_token_address_out_of_range() {
+        let token_address = Fr::from(2).pow([160]); // Smallest invalid token address.
+        let note = Note {
+            version: NoteVersion::V0,
+            id: Fr::from(1),
+            nullifier: Fr::from(2),
+            account_balance: Fr::from(3),
+            token_address,
+        };
+        let circuit = TestCircuit::note_hash_test(note);
+        let pub_input = [token_address, super::off_circuit::note_hash(&note)];
+
+        assert!(with_forged_witnesses(|| {
+            expect_prover_success_and_run_verification(circuit, &pub_input)
+        })
+        .is_err());
+    }
+
     #[test]
     fn note_hash_output_is_constrained() {
         let circuit = TestCircuit::note_hash_test(Note {
-            version: NoteVersion::new(0),
+            version: NoteVersion::V0,
             id: Fr::from(1),
             nullifier: Fr::from(2),
             account_balance: Fr::from(3),
@@ -390,7 +808,7 @@ mod tests {
     #[test]
     fn note_hash_input_is_constrained() {
         let note = Note {
-            version: NoteVersion::new(0),
+            version: NoteVersion::V0,
             id: Fr::from(1),
             nullifier: Fr::from(2),
             account_balance: Fr::from(3),
@@ -438,4 +856,169 @@ mod tests {
 
         expect_instance_permutation_failures(&failures, "balance_new", 1);
     }
+
+    fn example_note() -> Note<Fr> {
+        Note {
+            version: NoteVersion::V0,
+            id: Fr::from(1),
+            nullifier: Fr::from(2),
+            account_balance: Fr::from(3),
+            token_address: Fr::from(4),
+        }
+    }
+
+    #[test]
+    fn builder_gives_same_note_hash_as_struct_literal() {
+        let note = Note::builder()
+            .id(Fr::from(1))
+            .nullifier(Fr::from(2))
+            .account_balance(Fr::from(3))
+            .token_address(Fr::from(4))
+            .build();
+
+        assert_eq!(
+            super::off_circuit::note_hash(&note),
+            super::off_circuit::note_hash(&example_note())
+        );
+    }
+
+    #[test]
+    fn builder_accepts_fields_in_any_order() {
+        let note = Note::builder()
+            .token_address(Fr::from(4))
+            .account_balance(Fr::from(3))
+            .version(NoteVersion::V0)
+            .nullifier(Fr::from(2))
+            .id(Fr::from(1))
+            .build();
+
+        assert_eq!(
+            super::off_circuit::note_hash(&note),
+            super::off_circuit::note_hash(&example_note())
+        );
+    }
+
+    #[test]
+    fn new_note_hash_after_deposit() {
+        let note = example_note();
+        let new_note = Note {
+            nullifier: Fr::from(5),
+            account_balance: Fr::from(10),
+            ..note
+        };
+
+        assert_eq!(
+            super::off_circuit::new_note_hash(
+                note.id,
+                new_note.nullifier,
+                note.account_balance,
+                7,
+                note.token_address
+            ),
+            super::off_circuit::note_hash(&new_note)
+        );
+    }
+
+    #[test]
+    fn new_note_hash_after_withdrawal() {
+        let note = example_note();
+        let new_note = Note {
+            nullifier: Fr::from(5),
+            account_balance: Fr::from(1),
+            ..note
+        };
+
+        assert_eq!(
+            super::off_circuit::new_note_hash(
+                note.id,
+                new_note.nullifier,
+                note.account_balance,
+                -2,
+                note.token_address
+            ),
+            super::off_circuit::note_hash(&new_note)
+        );
+    }
+
+    #[test]
+    fn balance_equals_public_passes() {
+        let circuit = TestCircuit::balance_equals_public_test(example_note());
+        let token_address = 999; // Irrelevant.
+        let pub_input = [token_address, 3];
+
+        assert!(
+            expect_prover_success_and_run_verification(circuit, &pub_input.map(Fr::from)).is_ok()
+        );
+    }
+
+    #[test]
+    fn balance_equals_public_is_constrained() {
+        let circuit = TestCircuit::balance_equals_public_test(example_note());
+        let token_address = 999; // Irrelevant.
+        let pub_input = [token_address, 4];
+
+        let failures =
+            expect_prover_success_and_run_verification(circuit, &pub_input.map(Fr::from))
+                .expect_err("Verification must fail");
+
+        expect_instance_permutation_failures(&failures, "note", 1);
+    }
+
+    #[test]
+    fn note_and_nullifier_hash_passes() {
+        let note = example_note();
+        let circuit = TestCircuit::note_and_nullifier_hash_test(note);
+        let pub_input = [
+            note.token_address,
+            super::off_circuit::note_hash(&note),
+            hash(&[note.nullifier]),
+        ];
+
+        assert!(expect_prover_success_and_run_verification(circuit, &pub_input).is_ok());
+    }
+
+    #[test]
+    fn exposed_nullifier_hash_is_constrained() {
+        let note = example_note();
+        let circuit = TestCircuit::note_and_nullifier_hash_test(note);
+        let pub_input = [
+            note.token_address,
+            super::off_circuit::note_hash(&note),
+            hash(&[note.nullifier]) + Fr::ONE,
+        ];
+
+        let failures = expect_prover_success_and_run_verification(circuit, &pub_input)
+            .expect_err("Verification must fail");
+
+        expect_instance_permutation_failures(
+            &failures,
+            "permute state", // Region defined in `poseidon-gadget`.
+            2,
+        );
+    }
+
+    fn nullifiers_distinct_test(old_nullifier: u64, new_nullifier: u64) -> TestCircuit {
+        TestCircuit::TestNullifiersDistinct((
+            Value::known(Fr::from(old_nullifier)),
+            Value::known(Fr::from(new_nullifier)),
+        ))
+    }
+
+    #[test]
+    fn distinct_nullifiers_pass() {
+        let circuit = nullifiers_distinct_test(1, 2);
+
+        assert!(expect_prover_success_and_run_verification(circuit, &[]).is_ok());
+    }
+
+    #[test]
+    fn equal_nullifiers_fail() {
+        let circuit = nullifiers_distinct_test(2, 2);
+
+        let failures = expect_prover_success_and_run_verification(circuit, &[])
+            .expect_err("Verification must fail");
+
+        assert_eq!(failures.len(), 1);
+        expect_gate_failure(&failures[0], "Non-zero gate");
+    }
 }
@@ -0,0 +1,133 @@
+use halo2_proofs::plonk::Error;
+
+use crate::{
+    poseidon::circuit::{hash, PoseidonChip},
+    synthesizer::Synthesizer,
+    AssignedCell,
+};
+
+pub mod off_circuit {
+    use crate::{poseidon::off_circuit::hash, Fr};
+
+    /// The public hash of a nullifier (see [`super::NullifierChip::hash_nullifier`]).
+    pub fn nullifier_hash(nullifier: Fr) -> Fr {
+        hash(&[nullifier])
+    }
+}
+
+/// Computes the hashes under which nullifiers (and the prenullifier `id` of a new account) are
+/// published. All the circuits go through this chip, so the shape of the hash is defined in one
+/// place.
+#[derive(Clone, Debug)]
+pub struct NullifierChip {
+    poseidon: PoseidonChip,
+}
+
+impl NullifierChip {
+    pub fn new(poseidon: PoseidonChip) -> Self {
+        Self { poseidon }
+    }
+
+    pub fn hash_nullifier(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        nullifier: AssignedCell,
+    ) -> Result<AssignedCell, Error> {
+        hash(synthesizer, self.poseidon.clone(), [nullifier])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+
+    use halo2_proofs::{
+        circuit::{floor_planner::V1, Layouter},
+        dev::MockProver,
+        plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+    };
+    use rand_core::OsRng;
+
+    use crate::{
+        chips::nullifier::{off_circuit, NullifierChip},
+        column_pool::{ColumnPool, PreSynthesisPhase},
+        config_builder::ConfigsBuilder,
+        embed::Embed,
+        synthesizer::create_synthesizer,
+        Field, Fr,
+    };
+
+    #[derive(Clone, Debug, Default)]
+    struct NullifierCircuit {
+        nullifier: Fr,
+    }
+
+    impl Circuit<Fr> for NullifierCircuit {
+        type Config = (
+            ColumnPool<Advice, PreSynthesisPhase>,
+            NullifierChip,
+            Column<Instance>,
+        );
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+            let configs_builder = ConfigsBuilder::new(meta).with_nullifier();
+            let chip = configs_builder.nullifier_chip();
+
+            (configs_builder.finish(), chip, instance)
+        }
+
+        fn synthesize(
+            &self,
+            (pool, chip, instance): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let pool = pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+
+            let nullifier = self.nullifier.embed(&mut synthesizer, "nullifier")?;
+            let hashed = chip.hash_nullifier(&mut synthesizer, nullifier)?;
+            synthesizer.constrain_instance(hashed.cell(), instance, 0)
+        }
+    }
+
+    fn verify(nullifier: Fr, hashed: Fr) -> Result<(), Vec<String>> {
+        MockProver::run(6, &NullifierCircuit { nullifier }, vec![vec![hashed]])
+            .expect("Mock prover should run successfully")
+            .verify()
+            .map_err(|errors| {
+                errors
+                    .into_iter()
+                    .map(|failure| failure.to_string())
+                    .collect()
+            })
+    }
+
+    #[test]
+    fn circuit_matches_off_circuit_hash() {
+        for _ in 0..4 {
+            let nullifier = Fr::random(OsRng);
+            assert!(verify(nullifier, off_circuit::nullifier_hash(nullifier)).is_ok());
+        }
+    }
+
+    #[test]
+    fn hash_of_another_nullifier_fails() {
+        let errors = verify(Fr::from(41), off_circuit::nullifier_hash(Fr::from(42)))
+            .expect_err("Verification should fail");
+
+        assert!(errors
+            .iter()
+            .any(|error| error.contains("Equality constraint not satisfied")));
+    }
+}
@@ -0,0 +1,162 @@
+use halo2_proofs::plonk::{Advice, ConstraintSystem, Error};
+
+use crate::{
+    column_pool::{ColumnPool, ConfigPhase},
+    curve_arithmetic::{self, GrumpkinPoint},
+    embed::Embed,
+    gates::{
+        points_sub::{PointsSubGate, PointsSubGateInput},
+        Gate,
+    },
+    synthesizer::Synthesizer,
+    AssignedCell, Fr, Value,
+};
+
+/// Chip that subtracts two points on a Grumpkin curve.
+///
+/// P - Q = S
+#[derive(Clone, Debug)]
+pub struct PointsSubChip {
+    gate: PointsSubGate,
+}
+
+impl PointsSubChip {
+    pub fn new(
+        system: &mut ConstraintSystem<Fr>,
+        advice_pool: &mut ColumnPool<Advice, ConfigPhase>,
+    ) -> Self {
+        Self {
+            gate: PointsSubGate::create_gate(system, advice_pool),
+        }
+    }
+
+    pub fn points_sub(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        p: &GrumpkinPoint<AssignedCell>,
+        q: &GrumpkinPoint<AssignedCell>,
+    ) -> Result<GrumpkinPoint<AssignedCell>, Error> {
+        let q_value: GrumpkinPoint<Value> = q.clone().into();
+        let s_value = curve_arithmetic::points_add::<Value>(
+            p.clone().into(),
+            GrumpkinPoint::new(q_value.x, -q_value.y, q_value.z),
+        );
+        let s = s_value.embed(synthesizer, "S")?;
+
+        self.gate.apply_in_new_region(
+            synthesizer,
+            PointsSubGateInput {
+                p: p.clone(),
+                q: q.clone(),
+                s: s.clone(),
+            },
+        )?;
+
+        Ok(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{vec, vec::Vec};
+
+    use halo2_proofs::{
+        circuit::{floor_planner::V1, Layouter},
+        dev::{MockProver, VerifyFailure},
+        halo2curves::{bn256::Fr, group::Group, grumpkin::G1},
+        plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+    };
+
+    use super::PointsSubChip;
+    use crate::{
+        column_pool::{ColumnPool, PreSynthesisPhase},
+        embed::Embed,
+        rng,
+        synthesizer::create_synthesizer,
+        GrumpkinPoint,
+    };
+
+    #[derive(Clone, Debug, Default)]
+    struct PointsSubCircuit {
+        p: GrumpkinPoint<Fr>,
+        q: GrumpkinPoint<Fr>,
+    }
+
+    impl Circuit<Fr> for PointsSubCircuit {
+        type Config = (
+            ColumnPool<Advice, PreSynthesisPhase>,
+            PointsSubChip,
+            Column<Instance>,
+        );
+
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            // public input column
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+            // register point sub chip
+            let mut advice_pool = ColumnPool::<Advice, _>::new();
+            let chip = PointsSubChip::new(meta, &mut advice_pool);
+
+            (advice_pool.conclude_configuration(), chip, instance)
+        }
+
+        fn synthesize(
+            &self,
+            (column_pool, chip, instance): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let column_pool = column_pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &column_pool);
+
+            let p = self.p.embed(&mut synthesizer, "P")?;
+            let q = self.q.embed(&mut synthesizer, "Q")?;
+
+            let s = chip.points_sub(&mut synthesizer, &p, &q)?;
+
+            synthesizer.constrain_instance(s.x.cell(), instance, 0)?;
+            synthesizer.constrain_instance(s.y.cell(), instance, 1)?;
+            synthesizer.constrain_instance(s.z.cell(), instance, 2)?;
+
+            Ok(())
+        }
+    }
+
+    fn verify(
+        p: GrumpkinPoint<Fr>,
+        q: GrumpkinPoint<Fr>,
+        expected: GrumpkinPoint<Fr>,
+    ) -> Result<(), Vec<VerifyFailure>> {
+        let circuit = PointsSubCircuit { p, q };
+        MockProver::run(4, &circuit, vec![vec![expected.x, expected.y, expected.z]])
+            .expect("Mock prover should run")
+            .verify()
+    }
+
+    #[test]
+    fn subtracting_random_points() {
+        let rng = rng();
+
+        let p = G1::random(rng.clone());
+        let q = G1::random(rng.clone());
+        let expected = p - q;
+
+        assert!(verify(p.into(), q.into(), expected.into()).is_ok());
+    }
+
+    #[test]
+    fn incorrect_inputs() {
+        let rng = rng();
+
+        let p = G1::random(rng.clone());
+        let q = G1::random(rng.clone());
+        let s = G1::random(rng.clone());
+
+        assert!(verify(p.into(), q.into(), s.into()).is_err());
+    }
+}
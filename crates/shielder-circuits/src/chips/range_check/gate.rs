@@ -1,4 +1,4 @@
-use alloc::vec;
+use alloc::{vec, vec::Vec};
 
 use halo2_proofs::{
     plonk::{Advice, Column, ConstraintSystem, Error, Expression, Selector},
@@ -86,6 +86,7 @@ impl Gate for RangeCheckGate {
         RangeCheckGateInput { base, shifted }: Self::Input,
     ) -> Result<(), Error> {
         self.table.ensure_initialized(synthesizer)?;
+        synthesizer.record_enabled_gate(GATE_NAME);
         synthesizer.assign_region(
             || GATE_NAME,
             |mut region| {
@@ -106,6 +107,10 @@ impl Gate for RangeCheckGate {
         pool.ensure_capacity(cs, 1);
         pool.get_any_column()
     }
+
+    fn advice_columns(&self) -> Vec<Column<Advice>> {
+        vec![self.advice]
+    }
 }
 
 #[cfg(test)]
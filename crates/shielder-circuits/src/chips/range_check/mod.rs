@@ -3,21 +3,65 @@ use halo2_proofs::plonk::{Advice, ConstraintSystem, Error};
 
 use crate::{
     chips::{
-        range_check::{gate::RangeCheckGateInput, running_sum::running_sum},
+        range_check::{bits::to_chunks, gate::RangeCheckGateInput, running_sum::running_sum},
         sum::SumChip,
     },
     column_pool::{ColumnPool, ConfigPhase},
     consts::RANGE_PROOF_CHUNK_SIZE,
     embed::Embed,
-    gates::Gate,
+    gates::{sum::SumGate, Gate},
     synthesizer::Synthesizer,
-    AssignedCell, Fr,
+    AssignedCell, Field, Fr,
 };
 
 mod bits;
 mod gate;
 mod running_sum;
 
+/// Whether the prover-side sanity checks of the witness generation (in `to_chunks` and
+/// `running_sum`) are on. Outside of tests, this is always the case.
+#[cfg(not(test))]
+fn witness_checks_enabled() -> bool {
+    true
+}
+
+#[cfg(test)]
+fn witness_checks_enabled() -> bool {
+    !test_utils::FORGING.with(|forging| forging.get())
+}
+
+#[cfg(test)]
+pub mod test_utils {
+    use core::cell::Cell;
+
+    std::thread_local! {
+        pub(super) static FORGING: Cell<bool> = const { Cell::new(false) };
+    }
+
+    /// Runs `f` with the prover-side sanity checks of the range check witness generation turned
+    /// off. An out-of-range value then yields a (forged) witness built from its least significant
+    /// bits instead of a panic, so that tests can check that the constraints alone reject it.
+    pub fn with_forged_witnesses<R>(f: impl FnOnce() -> R) -> R {
+        FORGING.with(|forging| forging.set(true));
+        let result = f();
+        FORGING.with(|forging| forging.set(false));
+        result
+    }
+}
+
+/// Number of rows at the end of every column that halo2 reserves for blinding, assuming that no
+/// advice column is queried at more than 3 rotations.
+const RESERVED_ROWS: usize = 6;
+
+/// The smallest `k` of a circuit that can hold the range-check lookup table
+/// (`2^RANGE_PROOF_CHUNK_SIZE` rows) together with the rows reserved for blinding. Comparing it
+/// with the `k` of a circuit tells whether the table or the advice dominates its size.
+pub fn min_k_for_table() -> u32 {
+    let rows = (1 << RANGE_PROOF_CHUNK_SIZE) + RESERVED_ROWS;
+    // `ceil(log2(rows))`
+    (rows - 1).ilog2() + 1
+}
+
 #[derive(Clone, Debug)]
 pub struct RangeCheckChip {
     range_gate: RangeCheckGate,
@@ -36,6 +80,16 @@ impl RangeCheckChip {
         }
     }
 
+    /// Same as `new`, but creates its own `SumChip`. Meant for circuits that need range checks
+    /// without otherwise using the sum chip.
+    pub fn new_standalone(
+        system: &mut ConstraintSystem<Fr>,
+        advice_pool: &mut ColumnPool<Advice, ConfigPhase>,
+    ) -> Self {
+        let sum_chip = SumChip::new(SumGate::create_gate(system, advice_pool));
+        Self::new(system, advice_pool, sum_chip)
+    }
+
     /// Constrains the value to be less than `2^(CHUNK_SIZE * CHUNKS)`.
     pub fn constrain_value<const CHUNKS: usize>(
         &self,
@@ -71,4 +125,235 @@ impl RangeCheckChip {
 
         Ok(())
     }
+
+    /// Same bound as [`Self::constrain_value`], but instead of the running sum, `value` is split
+    /// into `CHUNKS` chunks of `CHUNK_SIZE` bits, each looked up in the range table directly.
+    /// Their recomposition is then constrained with the sum chip (Horner's scheme, multiplying by
+    /// `2^CHUNK_SIZE` through repeated doubling), which takes `CHUNK_SIZE + 1` sum gates per chunk.
+    pub fn constrain_value_lookup<const CHUNKS: usize>(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        value: AssignedCell,
+    ) -> Result<(), Error> {
+        let chunks = to_chunks(value.value().copied(), RANGE_PROOF_CHUNK_SIZE, CHUNKS)
+            .embed(synthesizer, "chunks")?;
+        let zero = synthesizer.zero_cell()?;
+
+        for chunk in &chunks {
+            // With `shifted = 0`, the gate looks up `base` itself.
+            self.range_gate.apply_in_new_region(
+                synthesizer,
+                RangeCheckGateInput {
+                    base: chunk.clone(),
+                    shifted: zero.clone(),
+                },
+            )?;
+        }
+
+        let (most_significant, rest) = chunks.split_last().expect("CHUNKS must be positive");
+        let mut recomposed = most_significant.clone();
+        for chunk in rest.iter().rev() {
+            let shifted = self.shift_by_chunk(synthesizer, recomposed)?;
+            recomposed = synthesizer.assign_value("recomposed", shifted.value() + chunk.value())?;
+            self.sum_chip
+                .constrain_sum(synthesizer, shifted, chunk.clone(), recomposed.clone())?;
+        }
+
+        self.sum_chip
+            .constrain_equal(synthesizer, value, recomposed)
+    }
+
+    /// Returns `value · 2^CHUNK_SIZE`, constrained by doubling `value` `CHUNK_SIZE` times.
+    fn shift_by_chunk(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        mut value: AssignedCell,
+    ) -> Result<AssignedCell, Error> {
+        for _ in 0..RANGE_PROOF_CHUNK_SIZE {
+            let doubled = synthesizer.assign_value("doubled", value.value() + value.value())?;
+            self.sum_chip
+                .constrain_sum(synthesizer, value.clone(), value, doubled.clone())?;
+            value = doubled;
+        }
+        Ok(value)
+    }
+
+    /// Constrains the value to lie in `[-2^k, 2^k)`, where `k = CHUNK_SIZE * CHUNKS - 1` and
+    /// negative values are represented as `p - |value|`. This is done by range-checking
+    /// `value + 2^k` with [`Self::constrain_value`].
+    pub fn constrain_signed_value<const CHUNKS: usize>(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        value: AssignedCell,
+    ) -> Result<(), Error> {
+        let offset = Fr::from(2).pow([(RANGE_PROOF_CHUNK_SIZE * CHUNKS - 1) as u64]);
+        let offset_cell = synthesizer.assign_constant("signed_range_offset", offset)?;
+
+        let shifted = synthesizer
+            .assign_value("shifted_value", value.value().map(|value| *value + offset))?;
+        self.sum_chip
+            .constrain_sum(synthesizer, value, offset_cell, shifted.clone())?;
+
+        self.constrain_value::<CHUNKS>(synthesizer, shifted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec;
+
+    use halo2_proofs::{
+        circuit::{floor_planner::V1, Layouter},
+        dev::MockProver,
+        plonk::{Advice, Circuit, ConstraintSystem, Error, Fixed},
+    };
+
+    use crate::{
+        chips::range_check::{min_k_for_table, test_utils::with_forged_witnesses, RangeCheckChip},
+        column_pool::{ColumnPool, PreSynthesisPhase},
+        consts::RANGE_PROOF_CHUNK_SIZE,
+        embed::Embed,
+        synthesizer::create_synthesizer,
+        Field, Fr,
+    };
+
+    const CHUNKS: usize = 2;
+
+    #[derive(Clone, Copy, Debug, Default)]
+    enum Mode {
+        #[default]
+        Unsigned,
+        Signed,
+        Lookup,
+    }
+
+    #[derive(Clone, Debug, Default)]
+    struct RangeCheckOnlyCircuit {
+        value: Fr,
+        mode: Mode,
+    }
+
+    impl Circuit<Fr> for RangeCheckOnlyCircuit {
+        type Config = (ColumnPool<Advice, PreSynthesisPhase>, RangeCheckChip);
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                value: Fr::ZERO,
+                mode: self.mode,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let mut advice_pool = ColumnPool::<Advice, _>::new();
+            let mut fixed_pool = ColumnPool::<Fixed, _>::new();
+            fixed_pool.ensure_capacity(meta, 1);
+
+            let range_check = RangeCheckChip::new_standalone(meta, &mut advice_pool);
+            (advice_pool.conclude_configuration(), range_check)
+        }
+
+        fn synthesize(
+            &self,
+            (pool, range_check): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let pool = pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+            let value = self.value.embed(&mut synthesizer, "value")?;
+
+            match self.mode {
+                Mode::Unsigned => range_check.constrain_value::<CHUNKS>(&mut synthesizer, value),
+                Mode::Signed => {
+                    range_check.constrain_signed_value::<CHUNKS>(&mut synthesizer, value)
+                }
+                Mode::Lookup => {
+                    range_check.constrain_value_lookup::<CHUNKS>(&mut synthesizer, value)
+                }
+            }
+        }
+    }
+
+    fn run(value: Fr, mode: Mode) -> bool {
+        MockProver::run(10, &RangeCheckOnlyCircuit { value, mode }, vec![])
+            .expect("Mock prover should run successfully")
+            .verify()
+            .is_ok()
+    }
+
+    fn verify(value: u64) -> bool {
+        run(Fr::from(value), Mode::Unsigned)
+    }
+
+    fn verify_lookup(value: u64) -> bool {
+        run(Fr::from(value), Mode::Lookup)
+    }
+
+    fn verify_signed(value: i64) -> bool {
+        let magnitude = Fr::from(value.unsigned_abs());
+        run(if value < 0 { -magnitude } else { magnitude }, Mode::Signed)
+    }
+
+    #[test]
+    fn standalone_range_check_accepts_values_in_range() {
+        assert!(verify(0));
+        assert!(verify((1 << 16) - 1));
+    }
+
+    #[test]
+    fn standalone_range_check_rejects_values_out_of_range() {
+        assert!(!with_forged_witnesses(|| verify(1 << 16)));
+    }
+
+    #[test]
+    fn signed_range_check_accepts_values_in_range() {
+        assert!(verify_signed(-(1 << 15)));
+        assert!(verify_signed(-1));
+        assert!(verify_signed(0));
+        assert!(verify_signed((1 << 15) - 1));
+    }
+
+    #[test]
+    fn signed_range_check_rejects_values_below_range() {
+        assert!(!with_forged_witnesses(|| verify_signed(-(1 << 15) - 1)));
+    }
+
+    #[test]
+    fn signed_range_check_rejects_values_above_range() {
+        assert!(!with_forged_witnesses(|| verify_signed(1 << 15)));
+    }
+
+    #[test]
+    fn range_check_circuit_fits_in_min_k_for_table() {
+        let k = min_k_for_table();
+        let circuit = RangeCheckOnlyCircuit {
+            value: Fr::from(1),
+            mode: Mode::Unsigned,
+        };
+
+        assert!(MockProver::run(k, &circuit, vec![])
+            .expect("Mock prover should run successfully")
+            .verify()
+            .is_ok());
+        assert!(MockProver::run(k - 1, &circuit, vec![]).is_err());
+    }
+
+    #[test]
+    fn lookup_range_check_accepts_values_in_range() {
+        assert!(verify_lookup(0));
+        assert!(verify_lookup(1));
+        assert!(verify_lookup(0x1234));
+    }
+
+    #[test]
+    fn lookup_range_check_accepts_max_value() {
+        assert!(verify_lookup((1 << (RANGE_PROOF_CHUNK_SIZE * CHUNKS)) - 1));
+    }
+
+    #[test]
+    fn lookup_range_check_rejects_values_out_of_range() {
+        assert!(!with_forged_witnesses(|| verify_lookup(
+            1 << (RANGE_PROOF_CHUNK_SIZE * CHUNKS)
+        )));
+    }
 }
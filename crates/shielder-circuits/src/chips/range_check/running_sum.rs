@@ -1,6 +1,9 @@
 use alloc::{vec, vec::Vec};
 
-use crate::{chips::range_check::bits::to_chunks, Field, Fr, Value};
+use crate::{
+    chips::range_check::{bits::to_chunks, witness_checks_enabled},
+    Field, Fr, Value,
+};
 
 /// Computes the running sum of a value. The sum will consist of `chunks + 1` values, satisfying:
 ///  - `z_i = 2^chunk_size * z_{i + 1} + a_i`
@@ -31,7 +34,9 @@ pub fn running_sum(value: Value, chunk_size: usize, chunks: usize) -> Vec<Value>
     }
 
     // Sanity check for the prover, that we have generated correct running sum.
-    current_sum.assert_if_known(|v| *v == Fr::ZERO);
+    if witness_checks_enabled() {
+        current_sum.assert_if_known(|v| *v == Fr::ZERO);
+    }
 
     running_sum
 }
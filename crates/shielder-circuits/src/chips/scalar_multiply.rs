@@ -1,17 +1,20 @@
-use alloc::vec::Vec;
+use alloc::{format, vec::Vec};
 
-use halo2_proofs::plonk::Error;
+use halo2_proofs::plonk::{Advice, ConstraintSystem, Error};
 
 use crate::{
+    column_pool::{ColumnPool, ConfigPhase},
     consts::FIELD_BITS,
     curve_arithmetic::{self, GrumpkinPoint},
     embed::Embed,
     gates::{
+        fixed_base_multiply::{FixedBaseMultiplyGate, FixedBaseMultiplyGateInput},
         scalar_multiply::{ScalarMultiplyGate, ScalarMultiplyGateInput},
+        scalar_multiply_w2::ScalarMultiplyGateW2,
         Gate,
     },
     synthesizer::Synthesizer,
-    AssignedCell, V,
+    AssignedCell, Field, Fr, Value, V,
 };
 
 #[derive(Clone, Debug)]
@@ -49,26 +52,117 @@ impl ScalarMultiplyChip {
         synthesizer: &mut impl Synthesizer,
         inputs: &ScalarMultiplyChipInput<AssignedCell>,
     ) -> Result<GrumpkinPoint<AssignedCell>, Error> {
-        let ScalarMultiplyChipInput { scalar_bits, input } = inputs;
-
-        let bits: Vec<V> = scalar_bits
-            .iter()
-            .map(|cell| V(cell.value().cloned()))
-            .collect();
-        let bits: [V; FIELD_BITS] = bits.try_into().expect("not a {FIELD_BITS} bit array");
-        let input: GrumpkinPoint<V> = GrumpkinPoint {
-            x: V(input.x.value().cloned()),
-            y: V(input.y.value().cloned()),
-            z: V(input.z.value().cloned()),
-        };
-
+        let (input, bits) = values(inputs);
         let final_result_value: GrumpkinPoint<V> = curve_arithmetic::scalar_multiply(input, bits);
         let final_result = final_result_value.embed(synthesizer, "S")?;
 
         self.multiply_gate.apply_in_new_region(
             synthesizer,
             ScalarMultiplyGateInput {
+                scalar_bits: inputs.scalar_bits.clone(),
+                input: inputs.input.clone(),
+                final_result: final_result.clone(),
+            },
+        )?;
+
+        Ok(final_result)
+    }
+}
+
+/// Same as [`ScalarMultiplyChip`], but for a fixed base point, backed by
+/// [`FixedBaseMultiplyGate`].
+#[derive(Clone, Debug)]
+pub struct FixedBaseMultiplyChip {
+    pub gate: FixedBaseMultiplyGate,
+}
+
+impl FixedBaseMultiplyChip {
+    /// Configures the chip together with its gate. The circuit must enable constants in some fixed
+    /// column (the table is embedded as constants).
+    pub fn new(
+        system: &mut ConstraintSystem<Fr>,
+        advice_pool: &mut ColumnPool<Advice, ConfigPhase>,
+    ) -> Self {
+        Self {
+            gate: FixedBaseMultiplyGate::create_gate(system, advice_pool),
+        }
+    }
+
+    /// Multiplies a fixed base point by a scalar, given the table of the doublings of the base
+    /// (`generator_table[i]` is `2^i` times the base, e.g., from
+    /// `curve_arithmetic::precompute_generator_table`). The table is embedded as constants, so
+    /// only the conditional additions are done in-circuit.
+    pub fn fixed_base_multiply(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        generator_table: &[GrumpkinPoint<Fr>; FIELD_BITS],
+        scalar_bits: &[AssignedCell; FIELD_BITS],
+    ) -> Result<GrumpkinPoint<AssignedCell>, Error> {
+        let mut table = Vec::with_capacity(FIELD_BITS);
+        let mut final_result_value = GrumpkinPoint::<Value>::zero();
+        for (i, (point, bit)) in generator_table.iter().zip(scalar_bits).enumerate() {
+            table.push(GrumpkinPoint::new(
+                synthesizer.assign_constant(format!("table[{i}][x]"), point.x)?,
+                synthesizer.assign_constant(format!("table[{i}][y]"), point.y)?,
+                synthesizer.assign_constant(format!("table[{i}][z]"), point.z)?,
+            ));
+
+            let mut is_one = false;
+            bit.value().map(|f| {
+                is_one = Fr::ONE == *f;
+            });
+            if is_one {
+                final_result_value =
+                    curve_arithmetic::points_add(final_result_value, (*point).into());
+            }
+        }
+        let final_result = final_result_value.embed(synthesizer, "S")?;
+
+        self.gate.apply_in_new_region(
+            synthesizer,
+            FixedBaseMultiplyGateInput {
                 scalar_bits: scalar_bits.clone(),
+                table: table.try_into().expect("table has FIELD_BITS points"),
+                final_result: final_result.clone(),
+            },
+        )?;
+
+        Ok(final_result)
+    }
+}
+
+/// Same as [`ScalarMultiplyChip`], but backed by [`ScalarMultiplyGateW2`], which consumes two bits
+/// of the scalar per row (and thus takes about half the rows).
+#[derive(Clone, Debug)]
+pub struct ScalarMultiplyChipW2 {
+    pub multiply_gate: ScalarMultiplyGateW2,
+}
+
+impl ScalarMultiplyChipW2 {
+    /// Configures the chip together with its gate.
+    pub fn new(
+        system: &mut ConstraintSystem<Fr>,
+        advice_pool: &mut ColumnPool<Advice, ConfigPhase>,
+    ) -> Self {
+        Self {
+            multiply_gate: ScalarMultiplyGateW2::create_gate(system, advice_pool),
+        }
+    }
+
+    pub fn scalar_multiply(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        inputs: &ScalarMultiplyChipInput<AssignedCell>,
+    ) -> Result<GrumpkinPoint<AssignedCell>, Error> {
+        let (input, bits) = values(inputs);
+        let final_result_value: GrumpkinPoint<V> =
+            curve_arithmetic::scalar_multiply_windowed(input, bits);
+        let final_result = final_result_value.embed(synthesizer, "S")?;
+
+        self.multiply_gate.apply_in_new_region(
+            synthesizer,
+            ScalarMultiplyGateInput {
+                scalar_bits: inputs.scalar_bits.clone(),
                 input: inputs.input.clone(),
                 final_result: final_result.clone(),
             },
@@ -78,6 +172,23 @@ impl ScalarMultiplyChip {
     }
 }
 
+/// Extracts the values of the input point and of the scalar bits.
+fn values(
+    ScalarMultiplyChipInput { scalar_bits, input }: &ScalarMultiplyChipInput<AssignedCell>,
+) -> (GrumpkinPoint<V>, [V; FIELD_BITS]) {
+    let bits: Vec<V> = scalar_bits
+        .iter()
+        .map(|cell| V(cell.value().cloned()))
+        .collect();
+    let bits: [V; FIELD_BITS] = bits.try_into().expect("not a {FIELD_BITS} bit array");
+    let input: GrumpkinPoint<V> = GrumpkinPoint {
+        x: V(input.x.value().cloned()),
+        y: V(input.y.value().cloned()),
+        z: V(input.z.value().cloned()),
+    };
+    (input, bits)
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::{
@@ -89,16 +200,25 @@ mod tests {
     use halo2_proofs::{
         circuit::{floor_planner::V1, Layouter},
         dev::MockProver,
-        halo2curves::{bn256::Fr, ff::PrimeField, group::Group, grumpkin::G1},
+        halo2curves::{
+            bn256::Fr,
+            ff::{Field, PrimeField},
+            group::Group,
+            grumpkin::G1,
+        },
         plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
     };
 
-    use super::{ScalarMultiplyChip, ScalarMultiplyChipInput};
+    use super::{
+        FixedBaseMultiplyChip, ScalarMultiplyChip, ScalarMultiplyChipInput, ScalarMultiplyChipW2,
+    };
     use crate::{
         column_pool::{ColumnPool, PreSynthesisPhase},
         config_builder::ConfigsBuilder,
         consts::FIELD_BITS,
-        curve_arithmetic::{self, field_element_to_le_bits},
+        curve_arithmetic::{
+            self, field_element_to_le_bits, normalize_point, precompute_generator_table,
+        },
         embed::Embed,
         rng,
         synthesizer::create_synthesizer,
@@ -162,6 +282,120 @@ mod tests {
         }
     }
 
+    #[derive(Clone, Debug, Default)]
+    struct WindowedScalarMultiplyCircuit(ScalarMultiplyChipInput<Fr>);
+
+    impl Circuit<Fr> for WindowedScalarMultiplyCircuit {
+        type Config = (
+            ColumnPool<Advice, PreSynthesisPhase>,
+            ScalarMultiplyChipW2,
+            Column<Instance>,
+        );
+
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+
+            let fixed = meta.fixed_column();
+            meta.enable_constant(fixed);
+
+            let mut advice_pool = ColumnPool::<Advice, _>::new();
+            let chip = ScalarMultiplyChipW2::new(meta, &mut advice_pool);
+
+            (advice_pool.conclude_configuration(), chip, instance)
+        }
+
+        fn synthesize(
+            &self,
+            (column_pool, chip, instance): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let ScalarMultiplyChipInput { input, scalar_bits } = self.0;
+
+            let column_pool = column_pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &column_pool);
+
+            let input = input.embed(&mut synthesizer, "input")?;
+            let scalar_bits = scalar_bits.embed(&mut synthesizer, "scalar_bits")?;
+
+            let result = chip.scalar_multiply(
+                &mut synthesizer,
+                &ScalarMultiplyChipInput { input, scalar_bits },
+            )?;
+
+            synthesizer.constrain_instance(result.x.cell(), instance, 0)?;
+            synthesizer.constrain_instance(result.y.cell(), instance, 1)?;
+            synthesizer.constrain_instance(result.z.cell(), instance, 2)?;
+
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    struct FixedBaseMultiplyCircuit([Fr; FIELD_BITS]);
+
+    impl Default for FixedBaseMultiplyCircuit {
+        fn default() -> Self {
+            Self([Fr::ZERO; FIELD_BITS])
+        }
+    }
+
+    impl Circuit<Fr> for FixedBaseMultiplyCircuit {
+        type Config = (
+            ColumnPool<Advice, PreSynthesisPhase>,
+            FixedBaseMultiplyChip,
+            Column<Instance>,
+        );
+
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+
+            let fixed = meta.fixed_column();
+            meta.enable_constant(fixed);
+
+            let mut advice_pool = ColumnPool::<Advice, _>::new();
+            let chip = FixedBaseMultiplyChip::new(meta, &mut advice_pool);
+
+            (advice_pool.conclude_configuration(), chip, instance)
+        }
+
+        fn synthesize(
+            &self,
+            (column_pool, chip, instance): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let column_pool = column_pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &column_pool);
+
+            let scalar_bits = self.0.embed(&mut synthesizer, "scalar_bits")?;
+
+            let result = chip.fixed_base_multiply(
+                &mut synthesizer,
+                &precompute_generator_table(),
+                &scalar_bits,
+            )?;
+
+            synthesizer.constrain_instance(result.x.cell(), instance, 0)?;
+            synthesizer.constrain_instance(result.y.cell(), instance, 1)?;
+            synthesizer.constrain_instance(result.z.cell(), instance, 2)?;
+
+            Ok(())
+        }
+    }
+
     fn input(p: G1, scalar_bits: [Fr; FIELD_BITS]) -> ScalarMultiplyChipInput<Fr> {
         ScalarMultiplyChipInput {
             input: p.into(),
@@ -173,19 +407,23 @@ mod tests {
         input: ScalarMultiplyChipInput<Fr>,
         expected: GrumpkinPoint<Fr>,
     ) -> Result<(), Vec<String>> {
-        MockProver::run(
-            10,
-            &ScalarMultiplyCircuit(input),
-            vec![vec![expected.x, expected.y, expected.z]],
-        )
-        .expect("Mock prover should run successfully")
-        .verify()
-        .map_err(|errors| {
-            errors
-                .into_iter()
-                .map(|failure| failure.to_string())
-                .collect()
-        })
+        run(10, &ScalarMultiplyCircuit(input), expected)
+    }
+
+    fn run(
+        k: u32,
+        circuit: &impl Circuit<Fr>,
+        expected: GrumpkinPoint<Fr>,
+    ) -> Result<(), Vec<String>> {
+        MockProver::run(k, circuit, vec![vec![expected.x, expected.y, expected.z]])
+            .expect("Mock prover should run successfully")
+            .verify()
+            .map_err(|errors| {
+                errors
+                    .into_iter()
+                    .map(|failure| failure.to_string())
+                    .collect()
+            })
     }
 
     #[test]
@@ -201,4 +439,78 @@ mod tests {
 
         assert!(verify(input, expected).is_ok());
     }
+
+    #[test]
+    fn windowed_chip_matches_scalar_multiply() {
+        let mut rng = rng();
+
+        for _ in 0..4 {
+            let p = G1::random(&mut rng);
+            let bits = field_element_to_le_bits(Fr::random(&mut rng));
+
+            let expected = curve_arithmetic::scalar_multiply_windowed(p.into(), bits);
+            assert_eq!(
+                normalize_point(expected),
+                normalize_point(curve_arithmetic::scalar_multiply(p.into(), bits))
+            );
+
+            let circuit = WindowedScalarMultiplyCircuit(input(p, bits));
+            assert!(run(10, &circuit, expected).is_ok());
+            assert!(run(10, &circuit, p.into()).is_err());
+        }
+    }
+
+    /// Whether `circuit` can be laid out in `2^k` rows and verifies.
+    fn fits(k: u32, circuit: &impl Circuit<Fr>, expected: GrumpkinPoint<Fr>) -> bool {
+        MockProver::run(k, circuit, vec![vec![expected.x, expected.y, expected.z]])
+            .is_ok_and(|prover| prover.verify().is_ok())
+    }
+
+    #[test]
+    fn windowed_chip_takes_fewer_rows() {
+        let mut rng = rng();
+        let p = G1::random(&mut rng);
+        let bits = field_element_to_le_bits(Fr::random(&mut rng));
+
+        // One row per bit does not fit in `2^8` rows, one row per two bits does.
+        let bitwise = ScalarMultiplyCircuit(input(p, bits));
+        let bitwise_expected = curve_arithmetic::scalar_multiply(p.into(), bits);
+        assert!(fits(10, &bitwise, bitwise_expected));
+        assert!(!fits(8, &bitwise, bitwise_expected));
+
+        let windowed = WindowedScalarMultiplyCircuit(input(p, bits));
+        let windowed_expected = curve_arithmetic::scalar_multiply_windowed(p.into(), bits);
+        assert!(fits(8, &windowed, windowed_expected));
+    }
+
+    #[test]
+    fn fixed_base_multiply_matches_scalar_multiply() {
+        let mut rng = rng();
+        let table = precompute_generator_table();
+
+        for _ in 0..4 {
+            let bits = field_element_to_le_bits(Fr::random(&mut rng));
+
+            // The projective coordinates of the result depend on the order of additions, so we
+            // recompute them the way the chip does.
+            let result = table
+                .iter()
+                .zip(bits)
+                .filter(|(_, bit)| *bit == Fr::ONE)
+                .fold(GrumpkinPoint::zero(), |acc, (point, _)| {
+                    curve_arithmetic::points_add(acc, *point)
+                });
+            assert_eq!(
+                normalize_point(result),
+                normalize_point(curve_arithmetic::scalar_multiply(
+                    GrumpkinPoint::generator(),
+                    bits
+                ))
+            );
+
+            let circuit = FixedBaseMultiplyCircuit(bits);
+            assert!(run(11, &circuit, result).is_ok());
+            assert!(run(11, &circuit, GrumpkinPoint::generator()).is_err());
+        }
+    }
 }
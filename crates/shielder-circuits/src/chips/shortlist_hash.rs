@@ -0,0 +1,244 @@
+use core::array;
+
+use halo2_proofs::plonk::Error;
+
+use crate::{
+    consts::POSEIDON_RATE,
+    embed::Embed,
+    poseidon::circuit::{hash, PoseidonChip},
+    synthesizer::Synthesizer,
+    AssignedCell,
+};
+
+/// Number of items absorbed by a single hash in the chain. The remaining slot of the Poseidon input
+/// carries the hash of the following chunks.
+pub const CHUNK_SIZE: usize = POSEIDON_RATE - 1;
+
+/// A fixed-length list of items, hashed as a chain of `CHUNK_SIZE`-long chunks:
+///
+///   `h_i = poseidon(h_{i+1}, chunk_i)`, with `h_{last+1} = 0`,
+///
+/// where the last chunk is padded with zeros. The hash of the shortlist is `h_0`.
+#[derive(Copy, Clone, Debug)]
+pub struct Shortlist<T, const N: usize> {
+    pub items: [T; N],
+}
+
+impl<T, const N: usize> Shortlist<T, N> {
+    pub fn new(items: [T; N]) -> Self {
+        Self { items }
+    }
+}
+
+impl<E: Embed, const N: usize> Embed for Shortlist<E, N> {
+    type Embedded = Shortlist<E::Embedded, N>;
+
+    fn embed(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        annotation: impl Into<alloc::string::String>,
+    ) -> Result<Self::Embedded, Error> {
+        Ok(Shortlist::new(self.items.embed(synthesizer, annotation)?))
+    }
+}
+
+pub mod off_circuit {
+    use halo2_proofs::arithmetic::Field;
+
+    use crate::{
+        chips::shortlist_hash::{Shortlist, CHUNK_SIZE},
+        consts::POSEIDON_RATE,
+        poseidon::off_circuit::hash,
+        Fr,
+    };
+
+    pub fn shortlist_hash<const N: usize>(shortlist: &Shortlist<Fr, N>) -> Fr {
+        let mut last = Fr::ZERO;
+        for chunk in shortlist.items.chunks(CHUNK_SIZE).rev() {
+            let mut input = [Fr::ZERO; POSEIDON_RATE];
+            input[0] = last;
+            input[1..=chunk.len()].copy_from_slice(chunk);
+            last = hash(&input);
+        }
+        last
+    }
+
+    /// Off-circuit model of skipping a hash: `hash` if `sum` is non-zero and `0` otherwise (e.g., to
+    /// treat a chunk of zero items as absent).
+    pub fn skip_hash(sum: Fr, hash: Fr) -> Fr {
+        if sum == Fr::ZERO {
+            Fr::ZERO
+        } else {
+            hash
+        }
+    }
+}
+
+/// Chip that is able to calculate the hash of a `Shortlist`.
+#[derive(Clone, Debug)]
+pub struct ShortlistHashChip {
+    poseidon: PoseidonChip,
+}
+
+impl ShortlistHashChip {
+    pub fn new(poseidon: PoseidonChip) -> Self {
+        Self { poseidon }
+    }
+
+    pub fn shortlist_hash<const N: usize>(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        shortlist: &Shortlist<AssignedCell, N>,
+    ) -> Result<AssignedCell, Error> {
+        let zero_cell = synthesizer.zero_cell()?;
+
+        let mut last = zero_cell.clone();
+        for chunk in shortlist.items.chunks(CHUNK_SIZE).rev() {
+            let mut input: [_; POSEIDON_RATE] = array::from_fn(|_| zero_cell.clone());
+            input[0] = last;
+            input[1..=chunk.len()].clone_from_slice(chunk);
+            last = hash(synthesizer, self.poseidon.clone(), input)?;
+        }
+        Ok(last)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+
+    use halo2_proofs::{
+        arithmetic::Field,
+        circuit::{floor_planner::V1, Layouter},
+        dev::MockProver,
+        plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+    };
+
+    use super::{
+        off_circuit::{shortlist_hash, skip_hash},
+        Shortlist, ShortlistHashChip,
+    };
+    use crate::{
+        column_pool::{ColumnPool, PreSynthesisPhase},
+        config_builder::ConfigsBuilder,
+        embed::Embed,
+        poseidon::off_circuit::hash,
+        synthesizer::create_synthesizer,
+        Fr, Value,
+    };
+
+    const N: usize = 8;
+
+    #[derive(Clone, Debug)]
+    struct ShortlistCircuit(Shortlist<Value, N>);
+
+    impl Default for ShortlistCircuit {
+        fn default() -> Self {
+            Self(Shortlist::new([Value::unknown(); N]))
+        }
+    }
+
+    impl Circuit<Fr> for ShortlistCircuit {
+        type Config = (
+            ColumnPool<Advice, PreSynthesisPhase>,
+            ShortlistHashChip,
+            Column<Instance>,
+        );
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+
+            let configs_builder = ConfigsBuilder::new(meta).with_poseidon();
+            let chip = ShortlistHashChip::new(configs_builder.poseidon_chip());
+
+            (configs_builder.finish(), chip, instance)
+        }
+
+        fn synthesize(
+            &self,
+            (pool, chip, instance): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let pool = pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+
+            let shortlist = self.0.embed(&mut synthesizer, "shortlist")?;
+            let shortlist_hash = chip.shortlist_hash(&mut synthesizer, &shortlist)?;
+
+            synthesizer.constrain_instance(shortlist_hash.cell(), instance, 0)
+        }
+    }
+
+    fn verify(items: [Fr; N], expected_hash: Fr) -> Result<(), Vec<String>> {
+        MockProver::run(
+            8,
+            &ShortlistCircuit(Shortlist::new(items.map(Value::known))),
+            vec![vec![expected_hash]],
+        )
+        .expect("Mock prover should run successfully")
+        .verify()
+        .map_err(|errors| {
+            errors
+                .into_iter()
+                .map(|failure| failure.to_string())
+                .collect()
+        })
+    }
+
+    fn example_items() -> [Fr; N] {
+        core::array::from_fn(|i| Fr::from(i as u64 + 1))
+    }
+
+    #[test]
+    fn chunks_are_chained_from_the_back() {
+        let items = example_items();
+
+        let tail = hash(&[
+            Fr::ZERO,
+            items[6],
+            items[7],
+            Fr::ZERO,
+            Fr::ZERO,
+            Fr::ZERO,
+            Fr::ZERO,
+        ]);
+        let expected = hash(&[
+            tail, items[0], items[1], items[2], items[3], items[4], items[5],
+        ]);
+
+        assert_eq!(shortlist_hash(&Shortlist::new(items)), expected);
+    }
+
+    #[test]
+    fn in_circuit_hash_matches_off_circuit() {
+        let items = example_items();
+
+        assert!(verify(items, shortlist_hash(&Shortlist::new(items))).is_ok());
+    }
+
+    #[test]
+    fn incorrect_hash_fails() {
+        let items = example_items();
+
+        assert!(verify(items, shortlist_hash(&Shortlist::new(items)) + Fr::ONE).is_err());
+    }
+
+    #[test]
+    fn hash_is_skipped_only_for_zero_sum() {
+        let hash = Fr::from(42);
+
+        assert_eq!(skip_hash(Fr::ZERO, hash), Fr::ZERO);
+        assert_eq!(skip_hash(Fr::ONE, hash), hash);
+        assert_eq!(skip_hash(-Fr::ONE, hash), hash);
+    }
+}
@@ -6,7 +6,7 @@ use crate::{
         Gate,
     },
     synthesizer::Synthesizer,
-    AssignedCell, Field, Fr,
+    AssignedCell,
 };
 
 #[derive(Clone, Debug)]
@@ -33,6 +33,32 @@ impl SumChip {
         self.0.apply_in_new_region(synthesizer, gate_input)
     }
 
+    /// Constrain cells to satisfy the equation `terms[0] + ... + terms[n - 1] = total` (`total = 0`
+    /// if there are no terms). Every addition takes a separate `SumGate` application.
+    #[cfg(feature = "chip-balances")]
+    pub fn constrain_sum_many(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        terms: &[AssignedCell],
+        total: AssignedCell,
+    ) -> Result<(), Error> {
+        let Some((last, init)) = terms.split_last() else {
+            return self.constrain_zero(synthesizer, total);
+        };
+        let Some((first, middle)) = init.split_first() else {
+            return self.constrain_equal(synthesizer, last.clone(), total);
+        };
+
+        let mut partial_sum = first.clone();
+        for term in middle {
+            let next =
+                synthesizer.assign_value("partial_sum", partial_sum.value() + term.value())?;
+            self.constrain_sum(synthesizer, partial_sum, term.clone(), next.clone())?;
+            partial_sum = next;
+        }
+        self.constrain_sum(synthesizer, partial_sum, last.clone(), total)
+    }
+
     /// Constrain cells to satisfy the equation `left_sock = right_sock`.
     pub fn constrain_equal(
         &self,
@@ -42,7 +68,7 @@ impl SumChip {
     ) -> Result<(), Error> {
         let gate_input = SumGateInput {
             summand_1: left_sock,
-            summand_2: self.zero(synthesizer)?,
+            summand_2: synthesizer.zero_cell()?,
             sum: right_sock,
         };
         self.0.apply_in_new_region(synthesizer, gate_input)
@@ -54,11 +80,95 @@ impl SumChip {
         synthesizer: &mut impl Synthesizer,
         zero: AssignedCell,
     ) -> Result<(), Error> {
-        let true_zero = self.zero(synthesizer)?;
+        let true_zero = synthesizer.zero_cell()?;
         self.constrain_equal(synthesizer, zero, true_zero)
     }
+}
+
+#[cfg(all(test, feature = "chip-balances"))]
+mod tests {
+    use std::{
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+
+    use halo2_proofs::{
+        circuit::{floor_planner::V1, Layouter},
+        dev::MockProver,
+        plonk::{Advice, Circuit, ConstraintSystem, Error},
+    };
+
+    use crate::{
+        chips::sum::SumChip,
+        column_pool::{ColumnPool, PreSynthesisPhase},
+        config_builder::ConfigsBuilder,
+        embed::Embed,
+        synthesizer::create_synthesizer,
+        Fr,
+    };
+
+    #[derive(Clone, Debug, Default)]
+    struct SumManyCircuit {
+        terms: [Fr; 3],
+        total: Fr,
+    }
+
+    impl Circuit<Fr> for SumManyCircuit {
+        type Config = (ColumnPool<Advice, PreSynthesisPhase>, SumChip);
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let configs_builder = ConfigsBuilder::new(meta).with_sum();
+            let sum = configs_builder.sum_chip();
+            (configs_builder.finish(), sum)
+        }
+
+        fn synthesize(
+            &self,
+            (pool, sum_chip): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let pool = pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+            let terms = self.terms.embed(&mut synthesizer, "terms")?;
+            let total = self.total.embed(&mut synthesizer, "total")?;
+
+            sum_chip.constrain_sum_many(&mut synthesizer, &terms, total)
+        }
+    }
+
+    fn verify(terms: [u64; 3], total: u64) -> Result<(), Vec<String>> {
+        MockProver::run(
+            5,
+            &SumManyCircuit {
+                terms: terms.map(Fr::from),
+                total: Fr::from(total),
+            },
+            vec![],
+        )
+        .expect("Mock prover should run successfully")
+        .verify()
+        .map_err(|errors| {
+            errors
+                .into_iter()
+                .map(|failure| failure.to_string())
+                .collect()
+        })
+    }
+
+    #[test]
+    fn matching_total_passes() {
+        assert!(verify([1, 20, 300], 321).is_ok());
+    }
 
-    fn zero(&self, synthesizer: &mut impl Synthesizer) -> Result<AssignedCell, Error> {
-        synthesizer.assign_constant("zero", Fr::ZERO)
+    #[test]
+    fn mismatching_total_fails() {
+        let errors = verify([1, 20, 300], 320).expect_err("Verification should fail");
+        assert!(errors.iter().all(|error| error.contains("Sum gate")));
     }
 }
@@ -0,0 +1,204 @@
+use halo2_proofs::plonk::{Advice, ConstraintSystem, Error};
+use strum_macros::{EnumCount, EnumIter};
+
+use crate::{
+    chips::{range_check::RangeCheckChip, sum::SumChip},
+    column_pool::{ColumnPool, ConfigPhase},
+    consts::{FIELD_BITS, RANGE_PROOF_CHUNK_SIZE},
+    gates::{sum::SumGate, Gate},
+    instance_wrapper::InstanceWrapper,
+    synthesizer::Synthesizer,
+    AssignedCell, Field, Fr, Value,
+};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, EnumIter, EnumCount)]
+pub enum ThresholdInstance {
+    Threshold,
+}
+
+/// Chip that proves that a private balance is below a public threshold, without revealing the
+/// balance itself.
+#[derive(Clone, Debug)]
+pub struct ThresholdChip {
+    range_check: RangeCheckChip,
+    sum: SumChip,
+    instance: InstanceWrapper<ThresholdInstance>,
+}
+
+impl ThresholdChip {
+    /// Configures the chip together with the range check and sum chips it depends on, and a new
+    /// instance column for the threshold. The circuit must enable constants in some fixed column.
+    pub fn new(
+        system: &mut ConstraintSystem<Fr>,
+        advice_pool: &mut ColumnPool<Advice, ConfigPhase>,
+    ) -> Self {
+        let sum = SumChip::new(SumGate::create_gate(system, advice_pool));
+        Self {
+            range_check: RangeCheckChip::new(system, advice_pool, sum.clone()),
+            sum,
+            instance: InstanceWrapper::new(system),
+        }
+    }
+
+    /// Constrains `balance < threshold` and constrains `threshold` to the `Threshold` public input.
+    ///
+    /// Both `balance` and `threshold - balance - 1` are range-checked to be less than
+    /// `2^(RANGE_PROOF_CHUNK_SIZE * CHUNKS)`. The bound is below `2^(FIELD_BITS - 1)`, so
+    /// `balance + 1 + (threshold - balance - 1) = threshold` cannot wrap around the field modulus
+    /// and holds over the integers too.
+    pub fn constrain_below<const CHUNKS: usize>(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        balance: AssignedCell,
+        threshold: Fr,
+    ) -> Result<(), Error> {
+        const {
+            assert!(
+                RANGE_PROOF_CHUNK_SIZE * CHUNKS < FIELD_BITS - 1,
+                "bit length too large"
+            )
+        };
+
+        let threshold = synthesizer.assign_value("threshold", Value::known(threshold))?;
+        self.instance.constrain_cells(
+            synthesizer,
+            [(threshold.clone(), ThresholdInstance::Threshold)],
+        )?;
+
+        self.range_check
+            .constrain_value::<CHUNKS>(synthesizer, balance.clone())?;
+
+        let one = synthesizer.assign_constant("one", Fr::ONE)?;
+        let gap =
+            synthesizer.assign_value("gap", threshold.value() - balance.value() - one.value())?;
+        self.sum
+            .constrain_sum_many(synthesizer, &[balance, one, gap.clone()], threshold)?;
+
+        self.range_check.constrain_value::<CHUNKS>(synthesizer, gap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+
+    use halo2_proofs::{
+        circuit::{floor_planner::V1, Layouter},
+        dev::MockProver,
+        plonk::{Advice, Circuit, ConstraintSystem, Error},
+    };
+
+    use crate::{
+        chips::{range_check::test_utils::with_forged_witnesses, threshold::ThresholdChip},
+        column_pool::{ColumnPool, PreSynthesisPhase},
+        embed::Embed,
+        synthesizer::create_synthesizer,
+        Field, Fr,
+    };
+
+    const CHUNKS: usize = 2;
+
+    #[derive(Clone, Debug, Default)]
+    struct ThresholdCircuit {
+        balance: Fr,
+        threshold: Fr,
+    }
+
+    impl Circuit<Fr> for ThresholdCircuit {
+        type Config = (ColumnPool<Advice, PreSynthesisPhase>, ThresholdChip);
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            // The range check needs a constant column for its zero cell.
+            let fixed = meta.fixed_column();
+            meta.enable_constant(fixed);
+
+            let mut advice_pool = ColumnPool::<Advice, _>::new();
+            let threshold = ThresholdChip::new(meta, &mut advice_pool);
+
+            (advice_pool.conclude_configuration(), threshold)
+        }
+
+        fn synthesize(
+            &self,
+            (pool, threshold_chip): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let pool = pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+            let balance = self.balance.embed(&mut synthesizer, "balance")?;
+
+            threshold_chip.constrain_below::<CHUNKS>(&mut synthesizer, balance, self.threshold)
+        }
+    }
+
+    fn verify(balance: Fr, threshold: u64, public_threshold: u64) -> Result<(), Vec<String>> {
+        MockProver::run(
+            10,
+            &ThresholdCircuit {
+                balance,
+                threshold: Fr::from(threshold),
+            },
+            vec![vec![Fr::from(public_threshold)]],
+        )
+        .expect("Mock prover should run successfully")
+        .verify()
+        .map_err(|errors| {
+            errors
+                .into_iter()
+                .map(|failure| failure.to_string())
+                .collect()
+        })
+    }
+
+    /// Same as `verify`, but an honest prover would not even be able to generate the witness.
+    fn verify_forged(balance: Fr, threshold: u64) -> Result<(), Vec<String>> {
+        with_forged_witnesses(|| verify(balance, threshold, threshold))
+    }
+
+    #[test]
+    fn balance_just_below_threshold_passes() {
+        assert!(verify(Fr::from(99), 100, 100).is_ok());
+    }
+
+    #[test]
+    fn zero_balance_passes() {
+        assert!(verify(Fr::ZERO, 100, 100).is_ok());
+    }
+
+    #[test]
+    fn balance_at_threshold_fails() {
+        // `threshold - balance - 1 = -1` is out of range.
+        assert!(verify_forged(Fr::from(100), 100).is_err());
+    }
+
+    #[test]
+    fn balance_above_threshold_fails() {
+        assert!(verify_forged(Fr::from(101), 100).is_err());
+    }
+
+    #[test]
+    fn wrapped_balance_fails() {
+        // The gap is `100 - (p - 1) - 1 = 100`, so only the range check of the balance fails.
+        assert!(verify_forged(-Fr::ONE, 100).is_err());
+    }
+
+    #[test]
+    fn incorrect_public_threshold_fails() {
+        let mut errors = verify(Fr::from(99), 100, 101)
+            .expect_err("Verification should fail")
+            .into_iter();
+
+        assert!(errors
+            .any(|error| error
+                .contains("Equality constraint not satisfied by cell (Column('Advice'")));
+    }
+}
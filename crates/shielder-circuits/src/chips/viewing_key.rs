@@ -1,7 +1,10 @@
 use halo2_proofs::plonk::Error;
+use strum_macros::{EnumCount, EnumIter};
 
 use crate::{
+    chips::nullifier::NullifierChip,
     consts::VIEWING_KEY_SALT,
+    instance_wrapper::InstanceWrapper,
     poseidon::circuit::{hash, PoseidonChip},
     synthesizer::Synthesizer,
     AssignedCell,
@@ -15,6 +18,11 @@ pub mod off_circuit {
     }
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, EnumIter, EnumCount)]
+pub enum ViewingKeyInstance {
+    HashedId,
+}
+
 #[derive(Clone, Debug)]
 pub struct ViewingKeyChip {
     poseidon: PoseidonChip,
@@ -33,6 +41,24 @@ impl ViewingKeyChip {
         let salt = synthesizer.assign_constant("ViewingKey salt", *VIEWING_KEY_SALT)?;
         hash(synthesizer, self.poseidon.clone(), [id, salt])
     }
+
+    /// Derives the viewing key from `id` and constrains the hash of `id` (see
+    /// [`NullifierChip::hash_nullifier`]) to the `HashedId` public input.
+    ///
+    /// This proves that the viewing key comes from the same `id` that was publicly committed to
+    /// (e.g., as a prenullifier), without revealing `id` itself.
+    pub fn derive_viewing_key_for_hashed_id(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        id: AssignedCell,
+        public_inputs: &InstanceWrapper<ViewingKeyInstance>,
+    ) -> Result<AssignedCell, Error> {
+        let h_id =
+            NullifierChip::new(self.poseidon.clone()).hash_nullifier(synthesizer, id.clone())?;
+        public_inputs.constrain_cells(synthesizer, [(h_id, ViewingKeyInstance::HashedId)])?;
+
+        self.derive_viewing_key(synthesizer, id)
+    }
 }
 
 #[cfg(test)]
@@ -49,12 +75,15 @@ mod tests {
         plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
     };
     use rand_core::OsRng;
+    use strum_macros::{EnumCount, EnumIter};
 
     use crate::{
-        chips::viewing_key::{off_circuit, ViewingKeyChip},
+        chips::viewing_key::{off_circuit, ViewingKeyChip, ViewingKeyInstance},
         column_pool::{ColumnPool, PreSynthesisPhase},
         config_builder::ConfigsBuilder,
         embed::Embed,
+        instance_wrapper::InstanceWrapper,
+        poseidon::off_circuit::hash,
         synthesizer::create_synthesizer,
         Field, Fr,
     };
@@ -122,6 +151,91 @@ mod tests {
         })
     }
 
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, EnumIter, EnumCount)]
+    enum TestInstance {
+        HashedId,
+        ViewingKey,
+    }
+
+    impl TryFrom<TestInstance> for ViewingKeyInstance {
+        type Error = ();
+
+        fn try_from(value: TestInstance) -> Result<Self, Self::Error> {
+            match value {
+                TestInstance::HashedId => Ok(ViewingKeyInstance::HashedId),
+                _ => Err(()),
+            }
+        }
+    }
+
+    #[derive(Clone, Debug, Default)]
+    struct HashedIdCircuit {
+        id: Fr,
+    }
+
+    impl Circuit<Fr> for HashedIdCircuit {
+        type Config = (
+            ColumnPool<Advice, PreSynthesisPhase>,
+            ViewingKeyChip,
+            InstanceWrapper<TestInstance>,
+        );
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let public_inputs = InstanceWrapper::<TestInstance>::new(meta);
+            let configs_builder = ConfigsBuilder::new(meta).with_poseidon();
+            let viewing_key_chip = ViewingKeyChip::new(configs_builder.poseidon_chip());
+
+            (configs_builder.finish(), viewing_key_chip, public_inputs)
+        }
+
+        fn synthesize(
+            &self,
+            (pool, chip, public_inputs): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let pool = pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+            // 1. Embed id.
+            let id = self.id.embed(&mut synthesizer, "id")?;
+
+            // 2. Derive viewing key, binding it to the hashed id.
+            let viewing_key = chip.derive_viewing_key_for_hashed_id(
+                &mut synthesizer,
+                id,
+                &public_inputs.narrow(),
+            )?;
+
+            // 3. Compare with public input.
+            public_inputs
+                .constrain_cells(&mut synthesizer, [(viewing_key, TestInstance::ViewingKey)])
+        }
+    }
+
+    fn verify_with_hashed_id(
+        id: impl Into<Fr>,
+        hashed_id: impl Into<Fr>,
+        expected_viewing_key: impl Into<Fr>,
+    ) -> Result<(), Vec<String>> {
+        MockProver::run(
+            8,
+            &HashedIdCircuit { id: id.into() },
+            vec![vec![hashed_id.into(), expected_viewing_key.into()]],
+        )
+        .expect("Mock prover should run successfully")
+        .verify()
+        .map_err(|errors| {
+            errors
+                .into_iter()
+                .map(|failure| failure.to_string())
+                .collect()
+        })
+    }
+
     #[test]
     fn correct_input_passes() {
         let id = Fr::random(OsRng);
@@ -142,4 +256,27 @@ mod tests {
             .any(|error| error
                 .contains("Equality constraint not satisfied by cell (Column('Advice'")));
     }
+
+    #[test]
+    fn viewing_key_for_consistent_hashed_id_passes() {
+        let id = Fr::random(OsRng);
+        let viewing_key = off_circuit::derive_viewing_key(id);
+
+        assert!(verify_with_hashed_id(id, hash(&[id]), viewing_key).is_ok());
+    }
+
+    #[test]
+    fn viewing_key_for_inconsistent_hashed_id_fails() {
+        let id = Fr::from(41);
+        let another_id = Fr::from(42);
+        let viewing_key = off_circuit::derive_viewing_key(id);
+
+        let mut errors = verify_with_hashed_id(id, hash(&[another_id]), viewing_key)
+            .expect_err("Verification should fail")
+            .into_iter();
+
+        assert!(errors
+            .any(|error| error
+                .contains("Equality constraint not satisfied by cell (Column('Advice'")));
+    }
 }
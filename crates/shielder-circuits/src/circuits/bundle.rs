@@ -0,0 +1,189 @@
+use alloc::{vec, vec::Vec};
+use core::fmt::{self, Display, Formatter};
+
+use halo2_proofs::halo2curves::serde::SerdeObject;
+
+use crate::{
+    circuits::{
+        marshall::{MarshallError::InvalidContent, MarshallResult},
+        verify, CircuitId, Params, VerifyError, VerifyingKey,
+    },
+    Fr,
+};
+
+/// Self-describing artifact passed from a prover to a verifier: the proof together with its public
+/// input and the fingerprint of the verifying key it was generated for.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProofBundle {
+    /// The circuit the proof is for.
+    pub circuit_id: CircuitId,
+    /// See `vk_fingerprint`.
+    pub vk_fingerprint: Fr,
+    pub public_inputs: Vec<Fr>,
+    pub proof: Vec<u8>,
+}
+
+/// Fingerprint of `vk`, i.e., the hash of the key that the transcript starts with.
+pub fn vk_fingerprint(vk: &VerifyingKey) -> Fr {
+    vk.transcript_repr()
+}
+
+impl ProofBundle {
+    pub fn new(
+        circuit_id: CircuitId,
+        vk: &VerifyingKey,
+        public_inputs: Vec<Fr>,
+        proof: Vec<u8>,
+    ) -> Self {
+        Self {
+            circuit_id,
+            vk_fingerprint: vk_fingerprint(vk),
+            public_inputs,
+            proof,
+        }
+    }
+
+    /// Layout: `circuit_id` (its code, 4 bytes, BE), `vk_fingerprint`, the number of public inputs
+    /// (4 bytes, BE), the public inputs and finally the proof (until the end of the buffer).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = vec![];
+        buf.extend_from_slice(&self.circuit_id.code().to_be_bytes());
+        self.vk_fingerprint
+            .write_raw(&mut buf)
+            .expect("fingerprint should serialize");
+        buf.extend_from_slice(&(self.public_inputs.len() as u32).to_be_bytes());
+        for input in &self.public_inputs {
+            input.write_raw(&mut buf).expect("input should serialize");
+        }
+        buf.extend_from_slice(&self.proof);
+        buf
+    }
+
+    pub fn from_bytes(mut buf: &[u8]) -> MarshallResult<Self> {
+        let circuit_id = CircuitId::from_code(read_u32(&mut buf)?).ok_or(InvalidContent)?;
+        let vk_fingerprint = Fr::read_raw(&mut buf).map_err(|_| InvalidContent)?;
+        let public_inputs = (0..read_u32(&mut buf)?)
+            .map(|_| Fr::read_raw(&mut buf).map_err(|_| InvalidContent))
+            .collect::<MarshallResult<Vec<_>>>()?;
+
+        Ok(Self {
+            circuit_id,
+            vk_fingerprint,
+            public_inputs,
+            proof: buf.to_vec(),
+        })
+    }
+}
+
+fn read_u32(buf: &mut &[u8]) -> MarshallResult<u32> {
+    let (bytes, rest) = buf.split_first_chunk::<4>().ok_or(InvalidContent)?;
+    *buf = rest;
+    Ok(u32::from_be_bytes(*bytes))
+}
+
+#[derive(Debug)]
+pub enum BundleError {
+    /// The bundle was generated for a different verifying key.
+    FingerprintMismatch,
+    Verification(VerifyError),
+}
+
+impl Display for BundleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BundleError::FingerprintMismatch => write!(f, "Verifying key fingerprint mismatch"),
+            BundleError::Verification(e) => write!(f, "Verification failed: {e}"),
+        }
+    }
+}
+
+/// Verify `bundle` against `vk`, checking first that the bundle was generated for `vk`.
+pub fn verify_bundle(
+    params: &Params,
+    vk: &VerifyingKey,
+    bundle: &ProofBundle,
+) -> Result<(), BundleError> {
+    if bundle.vk_fingerprint != vk_fingerprint(vk) {
+        return Err(BundleError::FingerprintMismatch);
+    }
+    verify(params, vk, &bundle.proof, &bundle.public_inputs).map_err(BundleError::Verification)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::{verify_bundle, BundleError, ProofBundle};
+    use crate::{
+        circuits::{
+            generate_keys_with_min_k, generate_proof, generate_setup_params, test_utils::rng,
+            CircuitId,
+        },
+        consts::{merkle_constants::NOTE_TREE_HEIGHT, MAX_K},
+        merkle::MerkleProverKnowledge,
+        Field, Fr, ProverKnowledge, PublicInputProvider,
+    };
+
+    #[test]
+    fn bundle_roundtrip_and_verification() {
+        let mut rng = rng();
+        let knowledge =
+            MerkleProverKnowledge::<NOTE_TREE_HEIGHT, Fr>::random_correct_example(&mut rng);
+        let public_inputs = knowledge.serialize_public_input();
+
+        let (params, _, pk, vk) = generate_keys_with_min_k(
+            knowledge.create_circuit(),
+            generate_setup_params(MAX_K, &mut rng),
+        )
+        .expect("keys should not fail to generate");
+        let proof = generate_proof(
+            &params,
+            &pk,
+            knowledge.create_circuit(),
+            &public_inputs,
+            &mut rng,
+        );
+
+        let bundle = ProofBundle::new(CircuitId::Merkle, &vk, public_inputs, proof);
+        let decoded = ProofBundle::from_bytes(&bundle.to_bytes()).expect("bundle should parse");
+        assert_eq!(decoded, bundle);
+        assert!(verify_bundle(&params, &vk, &decoded).is_ok());
+
+        let foreign = ProofBundle {
+            vk_fingerprint: bundle.vk_fingerprint + Fr::ONE,
+            ..bundle
+        };
+        assert!(matches!(
+            verify_bundle(&params, &vk, &foreign),
+            Err(BundleError::FingerprintMismatch)
+        ));
+    }
+
+    #[test]
+    fn truncated_bundle_is_rejected() {
+        let bundle = ProofBundle {
+            circuit_id: CircuitId::Withdraw,
+            vk_fingerprint: Fr::ONE,
+            public_inputs: vec![Fr::ONE, Fr::ONE],
+            proof: vec![],
+        };
+        let bytes = bundle.to_bytes();
+
+        assert!(ProofBundle::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(ProofBundle::from_bytes(&bytes[..3]).is_err());
+    }
+
+    #[test]
+    fn unknown_circuit_id_is_rejected() {
+        let bundle = ProofBundle {
+            circuit_id: CircuitId::Deposit,
+            vk_fingerprint: Fr::ONE,
+            public_inputs: vec![],
+            proof: vec![],
+        };
+        let mut bytes = bundle.to_bytes();
+        bytes[..4].copy_from_slice(&u32::MAX.to_be_bytes());
+
+        assert!(ProofBundle::from_bytes(&bytes).is_err());
+    }
+}
@@ -0,0 +1,107 @@
+use core::fmt::{self, Display, Formatter};
+
+use halo2_proofs::{
+    dev::CircuitCost,
+    halo2curves::bn256::G1,
+    plonk::{Circuit, ConstraintSystem},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{
+    circuits::{generate_keys_with_min_k, generate_setup_params},
+    consts::MAX_K,
+    Fr, ProverKnowledge,
+};
+
+/// Measures the circuit of `PK` (filled with a correct example) for the minimal `k` found by
+/// `generate_keys_with_min_k`. Meant for tuning `MAX_K`; runs key generation, so it is slow.
+pub fn report_cost<PK: ProverKnowledge>() -> CircuitCost<G1, PK::Circuit> {
+    let (k, circuit) = example_with_min_k::<PK>();
+    CircuitCost::measure(k, &circuit)
+}
+
+/// Human-readable summary of the size of a circuit (see [`report_cost`]).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CostSummary {
+    pub circuit_name: &'static str,
+    pub k: u32,
+    /// Number of rows available for the minimal `k`, i.e., `2^k`.
+    pub rows: usize,
+    pub advice_columns: usize,
+    pub fixed_columns: usize,
+    pub instance_columns: usize,
+    pub gates: usize,
+    pub lookups: usize,
+    /// Estimated proof size in bytes.
+    pub proof_size: usize,
+}
+
+impl CostSummary {
+    pub fn of<PK: ProverKnowledge>() -> Self {
+        let (k, circuit) = example_with_min_k::<PK>();
+        let mut cs = ConstraintSystem::<Fr>::default();
+        PK::Circuit::configure(&mut cs);
+
+        Self {
+            circuit_name: PK::circuit_name(),
+            k,
+            rows: 1 << k,
+            advice_columns: cs.num_advice_columns(),
+            fixed_columns: cs.num_fixed_columns(),
+            instance_columns: cs.num_instance_columns(),
+            gates: cs.gates().len(),
+            lookups: cs.lookups().len(),
+            proof_size: CircuitCost::<G1, _>::measure(k, &circuit)
+                .proof_size(cs.num_instance_columns())
+                .into(),
+        }
+    }
+}
+
+impl Display for CostSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: k = {} ({} rows), columns: {} advice, {} fixed, {} instance; {} gates, {} lookups; \
+             proof size: {} B",
+            self.circuit_name,
+            self.k,
+            self.rows,
+            self.advice_columns,
+            self.fixed_columns,
+            self.instance_columns,
+            self.gates,
+            self.lookups,
+            self.proof_size
+        )
+    }
+}
+
+fn example_with_min_k<PK: ProverKnowledge>() -> (u32, PK::Circuit) {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let circuit = PK::random_correct_example(&mut rng).create_circuit();
+    let (_, k, _, _) =
+        generate_keys_with_min_k(circuit.clone(), generate_setup_params(MAX_K, &mut rng))
+            .expect("keys should not fail to generate");
+    (k, circuit)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::format;
+
+    use super::{report_cost, CostSummary};
+    use crate::{deposit::DepositProverKnowledge, Fr};
+
+    #[test]
+    fn deposit_cost_is_reported() {
+        let cost = report_cost::<DepositProverKnowledge<Fr>>();
+        let summary = CostSummary::of::<DepositProverKnowledge<Fr>>();
+
+        let proof_size: usize = cost.proof_size(summary.instance_columns).into();
+        assert!(proof_size > 0);
+        assert_eq!(proof_size, summary.proof_size);
+        assert!(summary.rows > 0);
+        assert!(format!("{summary}").starts_with("deposit: k = "));
+    }
+}
@@ -2,17 +2,20 @@ use halo2_proofs::plonk::Error;
 
 use crate::{
     chips::{
+        commitment::CommitmentChip,
         mac::{MacChip, MacInput},
         note::{Note, NoteChip},
+        range_check::RangeCheckChip,
         viewing_key::ViewingKeyChip,
     },
     circuits::{
         deposit::knowledge::DepositProverKnowledge,
         merkle::{MerkleChip, MerkleProverKnowledge},
     },
+    consts::RANGE_PROOF_NUM_WORDS,
     deposit::DepositInstance::{self, Commitment, DepositValue, HashedNewNote, HashedOldNullifier},
     instance_wrapper::InstanceWrapper,
-    poseidon::circuit::{hash, PoseidonChip},
+    poseidon::circuit::PoseidonChip,
     synthesizer::Synthesizer,
     version::NOTE_VERSION,
     AssignedCell,
@@ -24,6 +27,8 @@ pub struct DepositChip {
     pub poseidon: PoseidonChip,
     pub merkle: MerkleChip,
     pub note: NoteChip,
+    /// If present, the deposit value and the new balance are range-checked.
+    pub range_check: Option<RangeCheckChip>,
 }
 
 impl DepositChip {
@@ -32,7 +37,7 @@ impl DepositChip {
         synthesizer: &mut impl Synthesizer,
         knowledge: &DepositProverKnowledge<AssignedCell>,
     ) -> Result<(), Error> {
-        let old_note = self.note.note_hash(
+        let old_note = self.note.hash_note_and_expose_nullifier(
             synthesizer,
             &Note {
                 version: NOTE_VERSION,
@@ -41,6 +46,8 @@ impl DepositChip {
                 account_balance: knowledge.account_old_balance.clone(),
                 token_address: knowledge.token_address.clone(),
             },
+            &self.public_inputs,
+            HashedOldNullifier,
         )?;
 
         self.merkle.synthesize(
@@ -49,21 +56,6 @@ impl DepositChip {
         )
     }
 
-    pub fn check_old_nullifier(
-        &self,
-        synthesizer: &mut impl Synthesizer,
-        knowledge: &DepositProverKnowledge<AssignedCell>,
-    ) -> Result<(), Error> {
-        let hashed_old_nullifier = hash(
-            synthesizer,
-            self.poseidon.clone(),
-            [knowledge.nullifier_old.clone()],
-        )?;
-
-        self.public_inputs
-            .constrain_cells(synthesizer, [(hashed_old_nullifier, HashedOldNullifier)])
-    }
-
     pub fn check_new_note(
         &self,
         synthesizer: &mut impl Synthesizer,
@@ -80,6 +72,23 @@ impl DepositChip {
             knowledge.deposit_value.clone(),
         )?;
 
+        if let Some(range_check) = &self.range_check {
+            range_check.constrain_value::<RANGE_PROOF_NUM_WORDS>(
+                synthesizer,
+                knowledge.deposit_value.clone(),
+            )?;
+            range_check.constrain_value::<RANGE_PROOF_NUM_WORDS>(
+                synthesizer,
+                account_balance_new.clone(),
+            )?;
+        }
+
+        self.note.constrain_nullifiers_distinct(
+            synthesizer,
+            knowledge.nullifier_old.clone(),
+            knowledge.nullifier_new.clone(),
+        )?;
+
         let new_note = self.note.note_hash(
             synthesizer,
             &Note {
@@ -118,7 +127,14 @@ impl DepositChip {
         synthesizer: &mut impl Synthesizer,
         knowledge: &DepositProverKnowledge<AssignedCell>,
     ) -> Result<(), Error> {
+        let commitment = CommitmentChip::new(self.poseidon.clone()).compute(
+            synthesizer,
+            knowledge.recipient.clone(),
+            knowledge.deposit_value.clone(),
+            knowledge.nonce.clone(),
+        )?;
+
         self.public_inputs
-            .constrain_cells(synthesizer, [(knowledge.commitment.clone(), Commitment)])
+            .constrain_cells(synthesizer, [(commitment, Commitment)])
     }
 }
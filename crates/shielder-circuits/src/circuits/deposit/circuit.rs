@@ -10,13 +10,20 @@ use crate::{
     deposit::DepositInstance,
     embed::Embed,
     instance_wrapper::InstanceWrapper,
-    synthesizer::create_synthesizer,
-    Fr, Value,
+    synthesizer::{create_synthesizer, Synthesizer},
+    Fr, ProverKnowledge, Value,
 };
 
 #[derive(Clone, Debug, Default)]
 pub struct DepositCircuit(pub DepositProverKnowledge<Value>);
 
+impl DepositCircuit {
+    /// Provable counterpart of `Self::default()`, wrapping `DepositProverKnowledge::example()`.
+    pub fn example() -> Self {
+        DepositProverKnowledge::<Fr>::example().create_circuit()
+    }
+}
+
 impl Circuit<Fr> for DepositCircuit {
     type Config = (DepositChip, ColumnPool<Advice, PreSynthesisPhase>);
     type FloorPlanner = V1;
@@ -26,64 +33,133 @@ impl Circuit<Fr> for DepositCircuit {
     }
 
     fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
-        let public_inputs = InstanceWrapper::<DepositInstance>::new(meta);
-
-        let configs_builder = ConfigsBuilder::new(meta)
-            .with_poseidon()
-            .with_merkle(public_inputs.narrow())
-            .with_note(public_inputs.narrow());
-
-        (
-            DepositChip {
-                public_inputs,
-                poseidon: configs_builder.poseidon_chip(),
-                merkle: configs_builder.merkle_chip(),
-                note: configs_builder.note_chip(),
-            },
-            configs_builder.finish(),
-        )
+        configure(meta, false)
     }
 
-    fn synthesize(
-        &self,
-        (main_chip, column_pool): Self::Config,
-        mut layouter: impl Layouter<Fr>,
-    ) -> Result<(), Error> {
-        let pool = column_pool.start_synthesis();
-        let mut synthesizer = create_synthesizer(&mut layouter, &pool);
-        let knowledge = self.0.embed(&mut synthesizer, "DepositProverKnowledge")?;
-
-        main_chip.check_old_note(&mut synthesizer, &knowledge)?;
-        main_chip.check_old_nullifier(&mut synthesizer, &knowledge)?;
-        main_chip.check_new_note(&mut synthesizer, &knowledge)?;
-        main_chip.check_mac(&mut synthesizer, &knowledge)?;
-        main_chip.check_commitment(&mut synthesizer, &knowledge)
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<Fr>) -> Result<(), Error> {
+        synthesize(&self.0, config, layouter)
+    }
+}
+
+/// `DepositCircuit` that additionally range-checks the deposit value and the new balance (sharing
+/// a single `RangeCheckChip`), for integrators that want the bounds enforced in-circuit rather
+/// than by the contract.
+#[derive(Clone, Debug, Default)]
+pub struct CheckedDepositCircuit(pub DepositProverKnowledge<Value>);
+
+impl CheckedDepositCircuit {
+    /// Same witness as `DepositCircuit::example()`.
+    pub fn example() -> Self {
+        Self(DepositCircuit::example().0)
     }
 }
 
+impl Circuit<Fr> for CheckedDepositCircuit {
+    type Config = (DepositChip, ColumnPool<Advice, PreSynthesisPhase>);
+    type FloorPlanner = V1;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+        configure(meta, true)
+    }
+
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<Fr>) -> Result<(), Error> {
+        synthesize(&self.0, config, layouter)
+    }
+}
+
+fn configure(
+    meta: &mut ConstraintSystem<Fr>,
+    range_checked: bool,
+) -> (DepositChip, ColumnPool<Advice, PreSynthesisPhase>) {
+    let public_inputs = InstanceWrapper::<DepositInstance>::new(meta);
+
+    let mut configs_builder = ConfigsBuilder::new(meta)
+        .with_poseidon()
+        .with_merkle(public_inputs.narrow());
+    if range_checked {
+        configs_builder = configs_builder.with_range_check();
+    }
+    let configs_builder = configs_builder.with_note(public_inputs.narrow());
+
+    (
+        DepositChip {
+            public_inputs,
+            poseidon: configs_builder.poseidon_chip(),
+            merkle: configs_builder.merkle_chip(),
+            note: configs_builder.note_chip(),
+            range_check: range_checked.then(|| configs_builder.range_check_chip()),
+        },
+        configs_builder.finish(),
+    )
+}
+
+fn synthesize(
+    knowledge: &DepositProverKnowledge<Value>,
+    (main_chip, column_pool): (DepositChip, ColumnPool<Advice, PreSynthesisPhase>),
+    mut layouter: impl Layouter<Fr>,
+) -> Result<(), Error> {
+    let pool = column_pool.start_synthesis();
+    let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+    constrain(knowledge, &main_chip, &mut synthesizer)
+}
+
+fn constrain(
+    knowledge: &DepositProverKnowledge<Value>,
+    main_chip: &DepositChip,
+    synthesizer: &mut impl Synthesizer,
+) -> Result<(), Error> {
+    let knowledge = knowledge.embed(synthesizer, "DepositProverKnowledge")?;
+
+    main_chip.check_old_note(synthesizer, &knowledge)?;
+    main_chip.check_new_note(synthesizer, &knowledge)?;
+    main_chip.check_mac(synthesizer, &knowledge)?;
+    main_chip.check_commitment(synthesizer, &knowledge)
+}
+
 #[cfg(test)]
 mod tests {
     use halo2_proofs::{arithmetic::Field, halo2curves::bn256::Fr};
+    #[cfg(feature = "diagnostics")]
+    use halo2_proofs::{
+        circuit::Layouter,
+        plonk::{Circuit, ConstraintSystem, Error},
+    };
     use rand::{rngs::SmallRng, SeedableRng};
     use rand_core::OsRng;
 
+    #[cfg(feature = "diagnostics")]
+    use super::constrain;
+    #[cfg(feature = "bench")]
+    use crate::circuits::test_utils::measure_verification_time;
     use crate::{
-        chips::viewing_key::off_circuit,
+        chips::{
+            commitment::off_circuit::commitment, range_check::test_utils::with_forged_witnesses,
+            viewing_key::off_circuit,
+        },
         circuits::{
-            deposit::knowledge::DepositProverKnowledge,
+            deposit::{circuit::CheckedDepositCircuit, knowledge::DepositProverKnowledge},
             merkle::generate_example_path_with_given_leaf,
             test_utils::{
                 expect_prover_success_and_run_verification, run_full_pipeline,
                 PublicInputProviderExt,
             },
         },
-        consts::merkle_constants::NOTE_TREE_HEIGHT,
+        consts::{merkle_constants::NOTE_TREE_HEIGHT, MAX_ACCOUNT_BALANCE_PASSING_RANGE_CHECK},
         deposit::DepositInstance::{self, *},
         note_hash,
         poseidon::off_circuit::hash,
         test_utils::expect_instance_permutation_failures,
         version::NOTE_VERSION,
-        Note, NoteVersion, ProverKnowledge, PublicInputProvider,
+        Note, NoteVersion, PrimeField, ProverKnowledge, PublicInputProvider,
+    };
+    #[cfg(feature = "diagnostics")]
+    use crate::{
+        circuits::{deposit::DepositCircuit, test_utils::run_mock_prover},
+        synthesizer::{create_synthesizer, Synthesizer},
     };
 
     #[test]
@@ -91,6 +167,27 @@ mod tests {
         run_full_pipeline::<DepositProverKnowledge<Fr>>();
     }
 
+    #[cfg(feature = "bench")]
+    #[test]
+    fn verification_is_fast() {
+        // Verification takes milliseconds; the bound is only meant to catch gross regressions.
+        const MAX_VERIFICATION_TIME: std::time::Duration = std::time::Duration::from_secs(1);
+
+        let elapsed = measure_verification_time::<DepositProverKnowledge<Fr>>();
+        assert!(
+            elapsed < MAX_VERIFICATION_TIME,
+            "verification took {elapsed:?}, expected under {MAX_VERIFICATION_TIME:?}"
+        );
+    }
+
+    // Built only with `--no-default-features`, checking that the deposit circuit does not depend on
+    // the El-Gamal chips.
+    #[cfg(not(feature = "chip-elgamal"))]
+    #[test]
+    fn passes_without_el_gamal_chips() {
+        run_full_pipeline::<DepositProverKnowledge<Fr>>();
+    }
+
     #[test]
     fn fails_if_merkle_proof_uses_wrong_note() {
         let mut pk = DepositProverKnowledge::random_correct_example(&mut OsRng);
@@ -188,7 +285,7 @@ mod tests {
                 // Important note: there is no range check in the circuit for DepositValue, however there is an external constraint
                 // (in the smart contract) guaranteeing that this never exceeds MAX_CONTRACT_BALANCE = 2^{112} - 1.
                 DepositValue => pk.deposit_value,
-                Commitment => pk.commitment,
+                Commitment => commitment(pk.recipient, pk.deposit_value, pk.nonce),
                 TokenAddress => pk.token_address,
                 MacSalt => pk.mac_salt,
                 MacCommitment => hash(&[pk.mac_salt, off_circuit::derive_viewing_key(pk.id)]),
@@ -214,7 +311,7 @@ mod tests {
 
         // Substitute all that changes in `pk` when `token_address` changes.
         let h_note_old = note_hash(&Note {
-            version: NoteVersion::new(0),
+            version: NoteVersion::V0,
             id: pk.id,
             nullifier: pk.nullifier_old,
             account_balance: pk.account_old_balance,
@@ -275,5 +372,79 @@ mod tests {
         );
     }
 
+    #[test]
+    fn checked_circuit_passes_if_inputs_correct() {
+        let pk = DepositProverKnowledge::random_correct_example(&mut OsRng);
+        let circuit = CheckedDepositCircuit(pk.create_circuit().0);
+
+        assert!(
+            expect_prover_success_and_run_verification(circuit, &pk.serialize_public_input())
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn checked_circuit_fails_if_deposit_value_is_over_range() {
+        let mut pk = DepositProverKnowledge::random_correct_example(&mut OsRng);
+
+        // The smallest value failing the range check.
+        pk.deposit_value = Fr::from_u128(MAX_ACCOUNT_BALANCE_PASSING_RANGE_CHECK + 1);
+        let circuit = CheckedDepositCircuit(pk.create_circuit().0);
+
+        // An honest prover refuses to decompose the value, so the witness has to be forged.
+        assert!(with_forged_witnesses(|| {
+            expect_prover_success_and_run_verification(circuit, &pk.serialize_public_input())
+        })
+        .is_err());
+    }
+
+    /// `DepositCircuit` collecting the names of the gates enabled during its synthesis into `self.1`.
+    #[cfg(feature = "diagnostics")]
+    #[derive(Clone, Debug, Default)]
+    struct GateCoverageCircuit(
+        DepositCircuit,
+        std::rc::Rc<core::cell::RefCell<std::collections::BTreeSet<&'static str>>>,
+    );
+
+    #[cfg(feature = "diagnostics")]
+    impl Circuit<Fr> for GateCoverageCircuit {
+        type Config = <DepositCircuit as Circuit<Fr>>::Config;
+        type FloorPlanner = <DepositCircuit as Circuit<Fr>>::FloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self(self.0.without_witnesses(), self.1.clone())
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            DepositCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            (main_chip, column_pool): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let pool = column_pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+            constrain(&self.0 .0, &main_chip, &mut synthesizer)?;
+
+            self.1.borrow_mut().extend(synthesizer.enabled_gates());
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "diagnostics")]
+    #[test]
+    fn enables_membership_and_sum_gates() {
+        let pk = DepositProverKnowledge::random_correct_example(&mut OsRng);
+        let circuit = GateCoverageCircuit(pk.create_circuit(), Default::default());
+
+        run_mock_prover(&circuit, &pk.serialize_public_input());
+
+        let enabled_gates = circuit.1.borrow();
+        assert!(enabled_gates.contains("Membership gate"));
+        assert!(enabled_gates.contains("Sum gate"));
+    }
+
     // TODO: Add more tests, as the above tests do not cover all the logic that should be covered.
 }
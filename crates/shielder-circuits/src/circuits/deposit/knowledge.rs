@@ -1,8 +1,12 @@
+use alloc::{format, string::String};
+
 use macros::embeddable;
 use rand_core::RngCore;
 
+#[cfg(feature = "chip-merkle")]
+use crate::merkle::MerkleTree;
 use crate::{
-    chips::viewing_key,
+    chips::{commitment, nullifier::off_circuit::nullifier_hash, viewing_key},
     consts::merkle_constants::{ARITY, NOTE_TREE_HEIGHT},
     curve_arithmetic,
     deposit::{circuit::DepositCircuit, DepositInstance},
@@ -11,7 +15,7 @@ use crate::{
     note_hash,
     poseidon::off_circuit::hash,
     version::NOTE_VERSION,
-    Field, Fr, Note, ProverKnowledge, PublicInputProvider, Value,
+    Field, Fr, Note, ProverKnowledge, PublicInputProvider, Value, REDACTED,
 };
 
 /// Stores values needed to compute example inputs for `DepositCircuit`. Provides a function
@@ -41,13 +45,20 @@ pub struct DepositProverKnowledge<T> {
     pub mac_salt: T,
 
     pub deposit_value: T,
-    pub commitment: T,
+
+    // Transaction data bound by the commitment
+    pub recipient: T,
+    pub nonce: T,
 }
 
 impl ProverKnowledge for DepositProverKnowledge<Fr> {
     type Circuit = DepositCircuit;
     type PublicInput = DepositInstance;
 
+    fn circuit_name() -> &'static str {
+        "deposit"
+    }
+
     /// Creates a random example with correct inputs. All values are random except for the deposit
     /// amount and the old account balances.
     fn random_correct_example(rng: &mut impl RngCore) -> Self {
@@ -73,7 +84,8 @@ impl ProverKnowledge for DepositProverKnowledge<Fr> {
             nullifier_new: Fr::random(&mut *rng),
             deposit_value: Fr::ONE,
             mac_salt: Fr::random(&mut *rng),
-            commitment: Fr::random(rng),
+            recipient: Fr::random(&mut *rng),
+            nonce: Fr::random(rng),
         }
     }
 
@@ -87,9 +99,47 @@ impl ProverKnowledge for DepositProverKnowledge<Fr> {
             path: self.path.map(|level| level.map(Value::known)),
             deposit_value: Value::known(self.deposit_value),
             mac_salt: Value::known(self.mac_salt),
-            commitment: Value::known(self.commitment),
+            recipient: Value::known(self.recipient),
+            nonce: Value::known(self.nonce),
         })
     }
+
+    /// Redacts the note secrets (`id`, nullifiers), the old balance and the Merkle path.
+    fn redacted_debug(&self) -> String {
+        format!(
+            "DepositProverKnowledge {{ id: {REDACTED}, nullifier_old: {REDACTED}, \
+             account_old_balance: {REDACTED}, token_address: {:?}, path: {REDACTED}, \
+             nullifier_new: {REDACTED}, mac_salt: {:?}, deposit_value: {:?}, recipient: {:?}, \
+             nonce: {:?} }}",
+            self.token_address, self.mac_salt, self.deposit_value, self.recipient, self.nonce,
+        )
+    }
+}
+
+#[cfg(feature = "chip-merkle")]
+impl DepositProverKnowledge<Fr> {
+    /// Like `random_correct_example`, but places the old note in `tree` at `leaf_index` (replacing
+    /// the leaf there) and takes the Merkle path from it. Hence, the Merkle root of the example is
+    /// the root of `tree` with the old note inserted. `tree` itself is left untouched.
+    pub fn random_in_tree(
+        tree: &MerkleTree<NOTE_TREE_HEIGHT>,
+        leaf_index: usize,
+        rng: &mut impl RngCore,
+    ) -> Self {
+        let mut knowledge = Self::random_correct_example(rng);
+        let h_note_old = note_hash(&Note {
+            version: NOTE_VERSION,
+            id: knowledge.id,
+            nullifier: knowledge.nullifier_old,
+            account_balance: knowledge.account_old_balance,
+            token_address: knowledge.token_address,
+        });
+
+        let mut tree = tree.clone();
+        tree.insert(leaf_index, h_note_old);
+        knowledge.path = tree.path(leaf_index);
+        knowledge
+    }
 }
 
 impl PublicInputProvider<DepositInstance> for DepositProverKnowledge<Fr> {
@@ -98,7 +148,7 @@ impl PublicInputProvider<DepositInstance> for DepositProverKnowledge<Fr> {
 
         match instance_id {
             DepositInstance::MerkleRoot => hash(&self.path[NOTE_TREE_HEIGHT - 1]),
-            DepositInstance::HashedOldNullifier => hash(&[self.nullifier_old]),
+            DepositInstance::HashedOldNullifier => nullifier_hash(self.nullifier_old),
             DepositInstance::HashedNewNote => note_hash(&Note {
                 version: NOTE_VERSION,
                 id: self.id,
@@ -107,10 +157,65 @@ impl PublicInputProvider<DepositInstance> for DepositProverKnowledge<Fr> {
                 token_address: self.token_address,
             }),
             DepositInstance::DepositValue => self.deposit_value,
-            DepositInstance::Commitment => self.commitment,
+            DepositInstance::Commitment => {
+                commitment::off_circuit::commitment(self.recipient, self.deposit_value, self.nonce)
+            }
             DepositInstance::TokenAddress => self.token_address,
             DepositInstance::MacSalt => self.mac_salt,
             DepositInstance::MacCommitment => hash(&[self.mac_salt, viewing_key]),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::format;
+
+    use rand_core::OsRng;
+
+    #[cfg(feature = "chip-merkle")]
+    use crate::{
+        circuits::test_utils::expect_prover_success_and_run_verification,
+        consts::merkle_constants::{ARITY, NOTE_TREE_HEIGHT},
+        deposit::DepositInstance,
+        merkle::MerkleTree,
+        PublicInputProvider,
+    };
+    use crate::{deposit::DepositProverKnowledge, ProverKnowledge, REDACTED};
+
+    #[test]
+    fn redacted_debug_hides_secrets() {
+        let pk = DepositProverKnowledge::random_correct_example(&mut OsRng);
+
+        let debug = pk.redacted_debug();
+
+        assert!(!debug.contains(&format!("{:?}", pk.id)));
+        assert!(!debug.contains(&format!("{:?}", pk.nullifier_old)));
+        assert!(!debug.contains(&format!("{:?}", pk.nullifier_new)));
+        assert!(debug.contains(&format!("id: {REDACTED}")));
+        assert!(debug.contains(&format!("deposit_value: {:?}", pk.deposit_value)));
+    }
+
+    #[cfg(feature = "chip-merkle")]
+    #[test]
+    fn example_in_tree_has_tree_root() {
+        let tree = MerkleTree::<NOTE_TREE_HEIGHT>::new();
+
+        for leaf_index in [3, 100_000] {
+            let pk = DepositProverKnowledge::random_in_tree(&tree, leaf_index, &mut OsRng);
+
+            // The old note is the leaf at `leaf_index`, which is the first level of the path.
+            let mut expected_tree = tree.clone();
+            expected_tree.insert(leaf_index, pk.path[0][leaf_index % ARITY]);
+            assert_eq!(
+                pk.compute_public_input(DepositInstance::MerkleRoot),
+                expected_tree.root()
+            );
+            assert!(expect_prover_success_and_run_verification(
+                pk.create_circuit(),
+                &pk.serialize_public_input()
+            )
+            .is_ok());
+        }
+    }
+}
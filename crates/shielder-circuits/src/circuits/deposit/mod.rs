@@ -6,7 +6,7 @@ mod chip;
 mod circuit;
 mod knowledge;
 
-pub use circuit::DepositCircuit;
+pub use circuit::{CheckedDepositCircuit, DepositCircuit};
 pub use knowledge::DepositProverKnowledge;
 
 use crate::chips::mac::MacInstance;
@@ -59,16 +59,12 @@ impl TryFrom<DepositInstance> for MacInstance {
 
 #[cfg(test)]
 mod tests {
-    use std::{vec, vec::Vec};
-
-    use strum::IntoEnumIterator;
-
-    use super::{DepositInstance, DepositInstance::*};
+    use super::DepositInstance::*;
+    use crate::circuits::assert_instance_order;
 
     #[test]
     fn instance_order() {
-        // This is the order used in other parts of the codebase (e.g., in contracts).
-        let expected_order = vec![
+        assert_instance_order(&[
             MerkleRoot,
             HashedOldNullifier,
             HashedNewNote,
@@ -77,7 +73,6 @@ mod tests {
             TokenAddress,
             MacSalt,
             MacCommitment,
-        ];
-        assert_eq!(expected_order, DepositInstance::iter().collect::<Vec<_>>());
+        ]);
     }
 }
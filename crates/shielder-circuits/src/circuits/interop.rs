@@ -0,0 +1,145 @@
+//! Helpers for integrating the circuits with external systems (contracts, wallets).
+
+use sha3::{Digest, Keccak256};
+
+use crate::{Fr, PrimeField};
+
+/// Interprets an Ethereum address as an unsigned 160-bit integer (as `uint160(address)` in
+/// Solidity does).
+pub fn address_to_field(address: [u8; 20]) -> Fr {
+    let mut repr = [0u8; 32];
+    for (byte, address_byte) in repr.iter_mut().zip(address.iter().rev()) {
+        *byte = *address_byte;
+    }
+    Fr::from_repr(repr).expect("160-bit integer fits in the field")
+}
+
+/// EIP-712 type of the signed withdrawal data.
+pub const WITHDRAWAL_TYPE: &str = "Withdrawal(address recipient,uint128 amount,uint64 nonce)";
+
+const EIP712_DOMAIN_TYPE: &str =
+    "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+
+/// EIP-712 domain of the contract verifying the signatures.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Eip712Domain<'a> {
+    pub name: &'a str,
+    pub version: &'a str,
+    pub chain_id: u64,
+    pub verifying_contract: [u8; 20],
+}
+
+impl Eip712Domain<'_> {
+    /// `hashStruct(domain)`, i.e., the `DOMAIN_SEPARATOR` of the contract.
+    pub fn separator(&self) -> [u8; 32] {
+        keccak256(&[
+            &keccak256(&[EIP712_DOMAIN_TYPE.as_bytes()]),
+            &keccak256(&[self.name.as_bytes()]),
+            &keccak256(&[self.version.as_bytes()]),
+            &encode_uint(self.chain_id as u128),
+            &encode_address(self.verifying_contract),
+        ])
+    }
+}
+
+/// EIP-712 digest of the typed withdrawal data (see [`WITHDRAWAL_TYPE`]) in `domain`, i.e., the
+/// message signed by `eth_signTypedData_v4`. The members are the preimage of the `Commitment`
+/// public input of the withdraw circuit (with `recipient` embedded by [`address_to_field`]), so a
+/// signature over the digest binds the signer to the proven withdrawal.
+pub fn withdrawal_typed_data_hash(
+    domain: &Eip712Domain,
+    recipient: [u8; 20],
+    amount: u128,
+    nonce: u64,
+) -> [u8; 32] {
+    let struct_hash = keccak256(&[
+        &keccak256(&[WITHDRAWAL_TYPE.as_bytes()]),
+        &encode_address(recipient),
+        &encode_uint(amount),
+        &encode_uint(nonce as u128),
+    ]);
+    keccak256(&[b"\x19\x01", &domain.separator(), &struct_hash])
+}
+
+/// ABI encoding of an `address` (left-padded to 32 bytes).
+fn encode_address(address: [u8; 20]) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[12..].copy_from_slice(&address);
+    word
+}
+
+/// ABI encoding of an unsigned integer type (big-endian, left-padded to 32 bytes).
+fn encode_uint(value: u128) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+fn keccak256(chunks: &[&[u8]]) -> [u8; 32] {
+    chunks
+        .iter()
+        .fold(Keccak256::new(), |hasher, chunk| hasher.chain_update(chunk))
+        .finalize()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{format, string::String};
+
+    use super::{address_to_field, withdrawal_typed_data_hash, Eip712Domain};
+    use crate::{Field, Fr};
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    #[test]
+    fn address_is_big_endian() {
+        let mut address = [0u8; 20];
+        address[19] = 1;
+        assert_eq!(address_to_field(address), Fr::ONE);
+
+        address[0] = 1;
+        assert_eq!(address_to_field(address), Fr::from(2).pow([152]) + Fr::ONE);
+    }
+
+    #[test]
+    fn domain_separator_matches_eip712_example() {
+        // The domain of the example in the EIP-712 specification.
+        let domain = Eip712Domain {
+            name: "Ether Mail",
+            version: "1",
+            chain_id: 1,
+            verifying_contract: [0xcc; 20],
+        };
+
+        assert_eq!(
+            hex(&domain.separator()),
+            "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+    }
+
+    #[test]
+    fn withdrawal_typed_data_hash_matches_known_answer() {
+        let domain = Eip712Domain {
+            name: "Shielder",
+            version: "1",
+            chain_id: 1,
+            verifying_contract: [0x11; 20],
+        };
+        let mut recipient = [0u8; 20];
+        recipient[18] = 0x12;
+        recipient[19] = 0x34;
+
+        assert_eq!(
+            hex(&withdrawal_typed_data_hash(
+                &domain,
+                recipient,
+                u128::MAX,
+                7
+            )),
+            "8a11d5738de316a95fa021f63c59c695454eb55feb21ce4e71d79cee353ed884"
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use halo2_proofs::plonk::{Circuit, Error};
+
+use crate::{
+    circuits::{
+        generate_keys_with_fixed_k, generate_keys_with_min_k, Params, ProvingKey, VerifyingKey,
+    },
+    deposit::DepositCircuit,
+    withdraw::WithdrawCircuit,
+    Fr,
+};
+#[cfg(feature = "chip-elgamal")]
+use crate::{
+    new_account::NewAccountCircuit, new_account_with_deposit::NewAccountWithDepositCircuit,
+};
+
+/// Keys of a single circuit together with the parameters (downsized to `k`) they were generated
+/// for.
+#[derive(Clone, Debug)]
+pub struct CircuitKeys {
+    pub params: Params,
+    pub k: u32,
+    pub pk: ProvingKey,
+    pub vk: VerifyingKey,
+}
+
+impl CircuitKeys {
+    /// Generates keys for `circuit` with the smallest sufficient `k`.
+    pub fn with_min_k(circuit: impl Circuit<Fr>, params: Params) -> Result<Self, Error> {
+        let (params, k, pk, vk) = generate_keys_with_min_k(circuit, params)?;
+        Ok(Self { params, k, pk, vk })
+    }
+
+    /// Generates keys for `circuit` with exactly `k`. `params` must support at least `2^k` rows.
+    pub fn with_k(circuit: impl Circuit<Fr>, params: Params, k: u32) -> Result<Self, Error> {
+        let (params, pk, vk) = generate_keys_with_fixed_k(circuit, params, k)?;
+        Ok(Self { params, k, pk, vk })
+    }
+}
+
+/// Keys for all the protocol circuits.
+#[derive(Clone, Debug)]
+pub struct KeySet {
+    pub deposit: CircuitKeys,
+    pub withdraw: CircuitKeys,
+    #[cfg(feature = "chip-elgamal")]
+    pub new_account: CircuitKeys,
+    #[cfg(feature = "chip-elgamal")]
+    pub new_account_with_deposit: CircuitKeys,
+}
+
+impl KeySet {
+    /// Generates keys for every circuit, each with its own minimal `k`.
+    pub fn generate(params: &Params) -> Result<Self, Error> {
+        Ok(Self {
+            deposit: CircuitKeys::with_min_k(DepositCircuit::default(), params.clone())?,
+            withdraw: CircuitKeys::with_min_k(WithdrawCircuit::default(), params.clone())?,
+            #[cfg(feature = "chip-elgamal")]
+            new_account: CircuitKeys::with_min_k(NewAccountCircuit::default(), params.clone())?,
+            #[cfg(feature = "chip-elgamal")]
+            new_account_with_deposit: CircuitKeys::with_min_k(
+                NewAccountWithDepositCircuit::default(),
+                params.clone(),
+            )?,
+        })
+    }
+
+    /// All the keys in the set.
+    pub fn keys(&self) -> impl Iterator<Item = &CircuitKeys> {
+        [
+            &self.deposit,
+            &self.withdraw,
+            #[cfg(feature = "chip-elgamal")]
+            &self.new_account,
+            #[cfg(feature = "chip-elgamal")]
+            &self.new_account_with_deposit,
+        ]
+        .into_iter()
+    }
+
+    /// The largest `k` among the circuits in the set.
+    pub fn max_k(&self) -> u32 {
+        self.keys()
+            .map(|keys| keys.k)
+            .max()
+            .expect("set is not empty")
+    }
+
+    /// Regenerates the keys of every circuit at `max_k()`, so that the whole set shares a single
+    /// `k` (and thus a single SRS). The parameters of the largest circuit are reused, so no other
+    /// setup is needed.
+    pub fn pad_to_common_k(self) -> Result<Self, Error> {
+        let k = self.max_k();
+        let params = self
+            .keys()
+            .find(|keys| keys.k == k)
+            .expect("max_k is attained")
+            .params
+            .clone();
+
+        Ok(Self {
+            deposit: pad(self.deposit, DepositCircuit::default(), &params, k)?,
+            withdraw: pad(self.withdraw, WithdrawCircuit::default(), &params, k)?,
+            #[cfg(feature = "chip-elgamal")]
+            new_account: pad(self.new_account, NewAccountCircuit::default(), &params, k)?,
+            #[cfg(feature = "chip-elgamal")]
+            new_account_with_deposit: pad(
+                self.new_account_with_deposit,
+                NewAccountWithDepositCircuit::default(),
+                &params,
+                k,
+            )?,
+        })
+    }
+}
+
+/// Regenerates `keys` at `k`, unless they already are at `k`.
+fn pad(
+    keys: CircuitKeys,
+    circuit: impl Circuit<Fr>,
+    params: &Params,
+    k: u32,
+) -> Result<CircuitKeys, Error> {
+    if keys.k == k {
+        Ok(keys)
+    } else {
+        CircuitKeys::with_k(circuit, params.clone(), k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::poly::commitment::Params as _;
+
+    use super::{CircuitKeys, KeySet};
+    use crate::{
+        circuits::{generate_proof, generate_setup_params, test_utils::rng, verify},
+        consts::MAX_K,
+        deposit::DepositProverKnowledge,
+        withdraw::WithdrawProverKnowledge,
+        Fr, ProverKnowledge, PublicInputProvider,
+    };
+    #[cfg(feature = "chip-elgamal")]
+    use crate::{
+        new_account::NewAccountProverKnowledge,
+        new_account_with_deposit::NewAccountWithDepositProverKnowledge,
+    };
+
+    fn prove_and_verify<PK: ProverKnowledge>(keys: &CircuitKeys) {
+        let mut rng = rng();
+        let knowledge = PK::random_correct_example(&mut rng);
+        let public_input = knowledge.serialize_public_input();
+
+        let proof = generate_proof(
+            &keys.params,
+            &keys.pk,
+            knowledge.create_circuit(),
+            &public_input,
+            &mut rng,
+        );
+        assert!(verify(&keys.params, &keys.vk, &proof, &public_input).is_ok());
+    }
+
+    #[test]
+    fn padded_set_shares_k_and_verifies() {
+        let params = generate_setup_params(MAX_K, &mut rng());
+        let key_set = KeySet::generate(&params).expect("keys should not fail to generate");
+        let max_k = key_set.max_k();
+
+        let padded = key_set
+            .pad_to_common_k()
+            .expect("keys should not fail to regenerate");
+
+        assert_eq!(padded.max_k(), max_k);
+        for keys in padded.keys() {
+            assert_eq!(keys.k, max_k);
+            assert_eq!(keys.params.k(), max_k);
+        }
+
+        prove_and_verify::<DepositProverKnowledge<Fr>>(&padded.deposit);
+        prove_and_verify::<WithdrawProverKnowledge<Fr>>(&padded.withdraw);
+        #[cfg(feature = "chip-elgamal")]
+        {
+            prove_and_verify::<NewAccountProverKnowledge<Fr>>(&padded.new_account);
+            prove_and_verify::<NewAccountWithDepositProverKnowledge<Fr>>(
+                &padded.new_account_with_deposit,
+            );
+        }
+    }
+}
@@ -1,19 +1,28 @@
 use alloc::{vec, vec::Vec};
 use core::fmt::{self, Display, Formatter};
 
-use halo2_proofs::{halo2curves::serde::SerdeObject, plonk::Circuit};
+use halo2_proofs::{
+    halo2curves::{bn256::G1Affine, serde::SerdeObject},
+    plonk::Circuit,
+    transcript::TranscriptRead,
+};
+use transcript::Keccak256Transcript;
 
 use crate::{
-    circuits::{Params, ProvingKey},
+    circuits::{Params, ProvingKey, VerifyingKey},
     consts::merkle_constants::{ARITY, NOTE_TREE_HEIGHT},
-    marshall::MarshallError::{InvalidContent, IoError},
-    Fr, SERDE_FORMAT,
+    marshall::MarshallError::{InvalidContent, InvalidMagic, IoError, UnsupportedVersion},
+    Fr, MARSHALL_VERSION, SERDE_FORMAT,
 };
 
 #[derive(Debug)]
 pub enum MarshallError {
     IoError,
     InvalidContent,
+    /// The data does not start with [`MARSHALL_MAGIC`].
+    InvalidMagic,
+    /// The data was written with a different [`MARSHALL_VERSION`].
+    UnsupportedVersion(u16),
 }
 
 impl Display for MarshallError {
@@ -21,12 +30,53 @@ impl Display for MarshallError {
         match self {
             IoError => write!(f, "IO error"),
             InvalidContent => write!(f, "Invalid content. Couldn't parse the data."),
+            InvalidMagic => write!(
+                f,
+                "Invalid magic bytes. The data is not a versioned artifact."
+            ),
+            UnsupportedVersion(version) => write!(
+                f,
+                "Unsupported format version {version} (expected {MARSHALL_VERSION})."
+            ),
         }
     }
 }
 
 pub type MarshallResult<T> = Result<T, MarshallError>;
 
+/// Bytes that every payload written with [`write_versioned`] starts with.
+pub const MARSHALL_MAGIC: [u8; 4] = *b"zkOS";
+
+/// Prefix `payload` (e.g. the output of [`marshall_pk`]) with [`MARSHALL_MAGIC`] and
+/// [`MARSHALL_VERSION`] (big-endian).
+pub fn write_versioned(payload: &[u8]) -> Vec<u8> {
+    [
+        MARSHALL_MAGIC.as_slice(),
+        &MARSHALL_VERSION.to_be_bytes(),
+        payload,
+    ]
+    .concat()
+}
+
+/// Strip the header written by [`write_versioned`] and return the payload. Fails if the magic bytes
+/// are missing or the data was written with a different [`MARSHALL_VERSION`].
+pub fn read_versioned(buf: &[u8]) -> MarshallResult<&[u8]> {
+    let (magic, rest) = buf
+        .split_at_checked(MARSHALL_MAGIC.len())
+        .ok_or(InvalidContent)?;
+    if magic != MARSHALL_MAGIC {
+        return Err(InvalidMagic);
+    }
+
+    let (version, payload) = rest.split_at_checked(2).ok_or(InvalidContent)?;
+    let version = u16::from_be_bytes(version.try_into().map_err(|_| InvalidContent)?);
+    if version != MARSHALL_VERSION {
+        return Err(UnsupportedVersion(version));
+    }
+
+    Ok(payload)
+}
+
 /// Serialize `params` to bytes.
 pub fn marshall_params(params: &Params) -> MarshallResult<Vec<u8>> {
     let mut buf = vec![];
@@ -78,18 +128,58 @@ pub fn unmarshall_path(mut buf: &[u8]) -> (Fr, [[Fr; ARITY]; NOTE_TREE_HEIGHT])
     (leaf, path)
 }
 
+/// Number of instance columns of the circuit that `vk` was generated for.
+pub fn vk_instance_columns(vk: &VerifyingKey) -> usize {
+    vk.cs().num_instance_columns()
+}
+
+/// Commitments read from the beginning of a proof transcript.
+///
+/// Instance values are absorbed by the transcript as scalars (KZG does not commit to them), so they
+/// are not part of the header.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TranscriptHeader {
+    /// Commitments to the advice columns, in the order in which they were committed (grouped by
+    /// phase).
+    pub advice_commitments: Vec<G1Affine>,
+    /// Commitments to the permuted input and the permuted table of every lookup argument.
+    pub lookup_permuted_commitments: Vec<(G1Affine, G1Affine)>,
+}
+
+/// Read the advice and lookup commitments from `transcript` (a proof generated for `vk`). Meant for
+/// debugging verification failures.
+pub fn parse_transcript_header(
+    transcript: &[u8],
+    vk: &VerifyingKey,
+) -> MarshallResult<TranscriptHeader> {
+    let mut transcript = Keccak256Transcript::<G1Affine, _>::new(transcript);
+    let mut read_point = || transcript.read_point().map_err(|_| InvalidContent);
+
+    let advice_commitments = (0..vk.cs().num_advice_columns())
+        .map(|_| read_point())
+        .collect::<MarshallResult<Vec<_>>>()?;
+    let lookup_permuted_commitments = (0..vk.cs().lookups().len())
+        .map(|_| Ok((read_point()?, read_point()?)))
+        .collect::<MarshallResult<Vec<_>>>()?;
+
+    Ok(TranscriptHeader {
+        advice_commitments,
+        lookup_permuted_commitments,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::format;
 
     use crate::{
         circuits::{
-            generate_keys_with_min_k, generate_setup_params,
+            generate_keys_with_min_k, generate_proof, generate_setup_params,
             merkle::{MerkleCircuit, MerkleProverKnowledge},
         },
         consts::MAX_K,
         marshall::*,
-        Fr, ProverKnowledge,
+        Fr, ProverKnowledge, PublicInputProvider, MARSHALL_VERSION,
     };
 
     fn generate_data() -> (Params, u32, ProvingKey) {
@@ -123,6 +213,44 @@ mod tests {
         assert_eq!(format!("{pk:?}"), format!("{pk2:?}"));
     }
 
+    #[test]
+    fn versioned_marshalling_pk() {
+        let (_, k, pk) = generate_data();
+
+        let bytes = write_versioned(&marshall_pk(k, &pk));
+        let payload = read_versioned(&bytes).unwrap();
+        let (k2, pk2) = unmarshall_pk::<MerkleCircuit<NOTE_TREE_HEIGHT>>(payload).unwrap();
+
+        assert_eq!(k, k2);
+        assert_eq!(format!("{pk:?}"), format!("{pk2:?}"));
+    }
+
+    #[test]
+    fn tampered_version_is_rejected() {
+        let mut bytes = write_versioned(&[1, 2, 3]);
+        bytes[MARSHALL_MAGIC.len() + 1] ^= 0xff;
+
+        assert!(matches!(
+            read_versioned(&bytes),
+            Err(MarshallError::UnsupportedVersion(version)) if version != MARSHALL_VERSION
+        ));
+    }
+
+    #[test]
+    fn missing_magic_is_rejected() {
+        let mut bytes = write_versioned(&[1, 2, 3]);
+        bytes[0] ^= 0xff;
+
+        assert!(matches!(
+            read_versioned(&bytes),
+            Err(MarshallError::InvalidMagic)
+        ));
+        assert!(matches!(
+            read_versioned(&[]),
+            Err(MarshallError::InvalidContent)
+        ));
+    }
+
     #[test]
     fn marshalling_path() {
         let mut rng = rand::thread_rng();
@@ -138,4 +266,35 @@ mod tests {
             (leaf2, path2)
         );
     }
+
+    #[test]
+    fn parsing_transcript_header() {
+        let mut rng = rand::thread_rng();
+        let knowledge =
+            MerkleProverKnowledge::<NOTE_TREE_HEIGHT, Fr>::random_correct_example(&mut rng);
+        let (params, _, pk, vk) = generate_keys_with_min_k(
+            knowledge.create_circuit(),
+            generate_setup_params(MAX_K, &mut rng),
+        )
+        .expect("keys should not fail to generate");
+        let proof = generate_proof(
+            &params,
+            &pk,
+            knowledge.create_circuit(),
+            &knowledge.serialize_public_input(),
+            &mut rng,
+        );
+
+        let header = parse_transcript_header(&proof, &vk).unwrap();
+
+        assert_eq!(
+            header.advice_commitments.len(),
+            vk.cs().num_advice_columns()
+        );
+        assert_eq!(
+            header.lookup_permuted_commitments.len(),
+            vk.cs().lookups().len()
+        );
+        assert!(parse_transcript_header(&proof[..32], &vk).is_err());
+    }
 }
@@ -3,7 +3,7 @@ use MerkleInstance::MerkleRoot;
 
 use crate::{
     circuits::merkle::knowledge::MerkleProverKnowledge,
-    consts::merkle_constants::ARITY,
+    consts::merkle_constants,
     gates::{
         membership::{MembershipGate, MembershipGateInput},
         Gate,
@@ -15,18 +15,20 @@ use crate::{
     AssignedCell,
 };
 
+/// Chip proving membership in a Merkle tree where every node has `ARITY` children. `ARITY` must not
+/// exceed `POSEIDON_RATE`, since each level is hashed with a single Poseidon call.
 #[derive(Clone, Debug)]
-pub struct MerkleChip {
+pub struct MerkleChip<const ARITY: usize = { merkle_constants::ARITY }> {
     pub public_inputs: InstanceWrapper<MerkleInstance>,
     pub membership_gate: MembershipGate<ARITY>,
     pub poseidon: PoseidonChip,
 }
 
-impl MerkleChip {
+impl<const ARITY: usize> MerkleChip<ARITY> {
     pub fn synthesize<const TREE_HEIGHT: usize>(
         &self,
         synthesizer: &mut impl Synthesizer,
-        knowledge: &MerkleProverKnowledge<TREE_HEIGHT, AssignedCell>,
+        knowledge: &MerkleProverKnowledge<TREE_HEIGHT, AssignedCell, ARITY>,
     ) -> Result<(), Error> {
         let mut current_root = knowledge.leaf.clone();
 
@@ -48,3 +50,102 @@ impl MerkleChip {
             .constrain_cells(synthesizer, [(current_root, MerkleRoot)])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{vec, vec::Vec};
+
+    use halo2_proofs::{
+        circuit::{floor_planner::V1, Layouter},
+        dev::{MockProver, VerifyFailure},
+        plonk::{Advice, Circuit, ConstraintSystem, Error},
+    };
+
+    use super::MerkleChip;
+    use crate::{
+        circuits::test_utils::rng,
+        column_pool::{ColumnPool, PreSynthesisPhase},
+        config_builder::ConfigsBuilder,
+        embed::Embed,
+        gates::{membership::MembershipGate, Gate},
+        instance_wrapper::InstanceWrapper,
+        merkle::{
+            generate_example_path_with_given_leaf_and_arity, MerkleInstance, MerkleProverKnowledge,
+        },
+        synthesizer::create_synthesizer,
+        Field, Fr, Value,
+    };
+
+    const TREE_HEIGHT: usize = 3;
+    const ARITY: usize = 4;
+
+    #[derive(Clone, Debug, Default)]
+    struct QuaternaryMerkleCircuit(MerkleProverKnowledge<TREE_HEIGHT, Value, ARITY>);
+
+    impl Circuit<Fr> for QuaternaryMerkleCircuit {
+        type Config = (MerkleChip<ARITY>, ColumnPool<Advice, PreSynthesisPhase>);
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let public_inputs = InstanceWrapper::<MerkleInstance>::new(meta);
+            let configs_builder = ConfigsBuilder::new(meta).with_poseidon();
+            let poseidon = configs_builder.poseidon_chip();
+            let advice_pool = configs_builder.finish();
+
+            let [needle, haystack @ ..] = [(); ARITY + 1].map(|_| {
+                let column = meta.advice_column();
+                meta.enable_equality(column);
+                column
+            });
+            let membership_gate = MembershipGate::create_gate_custom(meta, (needle, haystack));
+
+            let chip = MerkleChip {
+                public_inputs,
+                membership_gate,
+                poseidon,
+            };
+            (chip, advice_pool)
+        }
+
+        fn synthesize(
+            &self,
+            (chip, column_pool): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let pool = column_pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+            let knowledge = self.0.embed(&mut synthesizer, "MerkleProverKnowledge")?;
+            chip.synthesize(&mut synthesizer, &knowledge)
+        }
+    }
+
+    fn verify(
+        path: [[Fr; ARITY]; TREE_HEIGHT],
+        leaf: Fr,
+        root: Fr,
+    ) -> Result<(), Vec<VerifyFailure>> {
+        let circuit = QuaternaryMerkleCircuit(MerkleProverKnowledge {
+            leaf: Value::known(leaf),
+            path: path.map(|level| level.map(Value::known)),
+        });
+        MockProver::run(10, &circuit, vec![vec![root]])
+            .expect("Mock prover should run successfully")
+            .verify()
+    }
+
+    #[test]
+    fn tree_with_arity_four() {
+        let mut rng = rng();
+        let leaf = Fr::random(&mut rng);
+        let (root, path) =
+            generate_example_path_with_given_leaf_and_arity::<TREE_HEIGHT, ARITY>(leaf, &mut rng);
+
+        assert!(verify(path, leaf, root).is_ok());
+        assert!(verify(path, leaf, root + Fr::ONE).is_err());
+        assert!(verify(path, leaf + Fr::ONE, root).is_err());
+    }
+}
@@ -11,12 +11,19 @@ use crate::{
     instance_wrapper::InstanceWrapper,
     merkle::{chip::MerkleChip, MerkleInstance},
     synthesizer::create_synthesizer,
-    Fr, Value,
+    Fr, ProverKnowledge, Value,
 };
 
 #[derive(Clone, Debug, Default)]
 pub struct MerkleCircuit<const TREE_HEIGHT: usize>(pub MerkleProverKnowledge<TREE_HEIGHT, Value>);
 
+impl<const TREE_HEIGHT: usize> MerkleCircuit<TREE_HEIGHT> {
+    /// Provable counterpart of `Self::default()`, wrapping `MerkleProverKnowledge::example()`.
+    pub fn example() -> Self {
+        MerkleProverKnowledge::<TREE_HEIGHT, Fr>::example().create_circuit()
+    }
+}
+
 impl<const TREE_HEIGHT: usize> Circuit<Fr> for MerkleCircuit<TREE_HEIGHT> {
     type Config = (MerkleChip, ColumnPool<Advice, PreSynthesisPhase>);
     type FloorPlanner = V1;
@@ -13,28 +13,29 @@ use crate::{
 
 #[derive(Clone, Debug)]
 #[embeddable(
-    receiver = "MerkleProverKnowledge<TREE_HEIGHT, Value>",
-    impl_generics = "<const TREE_HEIGHT: usize>",
-    embedded = "MerkleProverKnowledge<TREE_HEIGHT, crate::AssignedCell>"
+    receiver = "MerkleProverKnowledge<TREE_HEIGHT, Value, N>",
+    impl_generics = "<const TREE_HEIGHT: usize, const N: usize>",
+    embedded = "MerkleProverKnowledge<TREE_HEIGHT, crate::AssignedCell, N>"
 )]
-pub struct MerkleProverKnowledge<const TREE_HEIGHT: usize, T> {
+/// Knowledge of a path in a tree of arity `N` (by default, the arity of the note tree).
+pub struct MerkleProverKnowledge<const TREE_HEIGHT: usize, T, const N: usize = ARITY> {
     pub leaf: T,
-    pub path: [[T; ARITY]; TREE_HEIGHT],
+    pub path: [[T; N]; TREE_HEIGHT],
 }
 
-impl<const TREE_HEIGHT: usize, T: Default + Copy> Default
-    for MerkleProverKnowledge<TREE_HEIGHT, T>
+impl<const TREE_HEIGHT: usize, T: Default + Copy, const N: usize> Default
+    for MerkleProverKnowledge<TREE_HEIGHT, T, N>
 {
     fn default() -> Self {
         Self {
             leaf: T::default(),
-            path: [[T::default(); ARITY]; TREE_HEIGHT],
+            path: [[T::default(); N]; TREE_HEIGHT],
         }
     }
 }
 
-impl<const TREE_HEIGHT: usize, T: Clone> MerkleProverKnowledge<TREE_HEIGHT, T> {
-    pub fn new(leaf: impl Borrow<T>, path: impl Borrow<[[T; ARITY]; TREE_HEIGHT]>) -> Self {
+impl<const TREE_HEIGHT: usize, T: Clone, const N: usize> MerkleProverKnowledge<TREE_HEIGHT, T, N> {
+    pub fn new(leaf: impl Borrow<T>, path: impl Borrow<[[T; N]; TREE_HEIGHT]>) -> Self {
         Self {
             leaf: leaf.borrow().clone(),
             path: path.borrow().clone(),
@@ -46,6 +47,10 @@ impl<const TREE_HEIGHT: usize> ProverKnowledge for MerkleProverKnowledge<TREE_HE
     type Circuit = MerkleCircuit<TREE_HEIGHT>;
     type PublicInput = MerkleInstance;
 
+    fn circuit_name() -> &'static str {
+        "merkle"
+    }
+
     fn random_correct_example(rng: &mut impl RngCore) -> Self {
         let mut path = [(); TREE_HEIGHT].map(|_| [(); ARITY].map(|_| Fr::random(&mut *rng)));
         for i in 1..TREE_HEIGHT {
@@ -1,31 +1,80 @@
 use rand_core::RngCore;
 use strum_macros::{EnumCount, EnumIter};
 
-use crate::{consts::merkle_constants::ARITY, poseidon::off_circuit::hash, Field, Fr};
+use crate::{
+    consts::merkle_constants::{ARITY, NOTE_TREE_HEIGHT},
+    poseidon::off_circuit::hash,
+    Field, Fr,
+};
 
 mod chip;
 mod circuit;
 mod knowledge;
+#[cfg(feature = "chip-merkle")]
+mod non_membership;
+#[cfg(feature = "chip-merkle")]
+mod tree;
+mod with_leaf;
 
 pub use chip::MerkleChip;
 pub use circuit::MerkleCircuit;
 pub use knowledge::MerkleProverKnowledge;
+#[cfg(feature = "chip-merkle")]
+pub use non_membership::{NonMembershipChip, PositionedLeaf};
+#[cfg(feature = "chip-merkle")]
+pub use tree::MerkleTree;
+pub use with_leaf::{MerkleWithLeafCircuit, MerkleWithLeafProverKnowledge};
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, EnumIter, EnumCount)]
 pub enum MerkleInstance {
     MerkleRoot,
 }
 
+/// Number of leaves in a tree with the given `height` and `arity`, i.e., `arity^height`. Saturates
+/// at `u128::MAX`.
+pub const fn capacity(height: usize, arity: usize) -> u128 {
+    (arity as u128).saturating_pow(height as u32)
+}
+
+/// Maximum number of notes in the note tree.
+pub const NOTE_TREE_CAPACITY: u128 = capacity(NOTE_TREE_HEIGHT, ARITY);
+
+/// Public inputs of `MerkleWithLeafCircuit`, which additionally binds the proven leaf.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, EnumIter, EnumCount)]
+pub enum MerkleInstanceWithLeaf {
+    MerkleRoot,
+    Leaf,
+}
+
+impl TryFrom<MerkleInstanceWithLeaf> for MerkleInstance {
+    type Error = ();
+
+    fn try_from(value: MerkleInstanceWithLeaf) -> Result<Self, Self::Error> {
+        match value {
+            MerkleInstanceWithLeaf::MerkleRoot => Ok(Self::MerkleRoot),
+            _ => Err(()),
+        }
+    }
+}
+
 pub fn generate_example_path_with_given_leaf<const TREE_HEIGHT: usize>(
     leaf: Fr,
     rng: &mut impl RngCore,
 ) -> (Fr, [[Fr; ARITY]; TREE_HEIGHT]) {
-    let mut path: [[Fr; ARITY]; TREE_HEIGHT] =
-        [(); TREE_HEIGHT].map(|_| [(); ARITY].map(|_| Fr::random(&mut *rng)));
+    generate_example_path_with_given_leaf_and_arity(leaf, rng)
+}
+
+/// Same as `generate_example_path_with_given_leaf`, but for a tree of arity `N`.
+pub fn generate_example_path_with_given_leaf_and_arity<const TREE_HEIGHT: usize, const N: usize>(
+    leaf: Fr,
+    rng: &mut impl RngCore,
+) -> (Fr, [[Fr; N]; TREE_HEIGHT]) {
+    let mut path: [[Fr; N]; TREE_HEIGHT] =
+        [(); TREE_HEIGHT].map(|_| [(); N].map(|_| Fr::random(&mut *rng)));
     path[0][0] = leaf;
 
     for i in 1..TREE_HEIGHT {
-        path[i][(rng.next_u32() % (ARITY as u32)) as usize] = hash(&path[i - 1]);
+        path[i][(rng.next_u32() % (N as u32)) as usize] = hash(&path[i - 1]);
     }
 
     let root = hash(&path[TREE_HEIGHT - 1]);
@@ -35,16 +84,31 @@ pub fn generate_example_path_with_given_leaf<const TREE_HEIGHT: usize>(
 
 #[cfg(test)]
 mod tests {
-    use std::{vec, vec::Vec};
+    use super::{capacity, MerkleInstance::*, MerkleInstanceWithLeaf, NOTE_TREE_CAPACITY};
+    use crate::circuits::assert_instance_order;
+
+    #[test]
+    fn instance_order() {
+        assert_instance_order(&[MerkleRoot]);
+    }
 
-    use strum::IntoEnumIterator;
+    #[test]
+    fn instance_with_leaf_order() {
+        assert_instance_order(&[
+            MerkleInstanceWithLeaf::MerkleRoot,
+            MerkleInstanceWithLeaf::Leaf,
+        ]);
+    }
 
-    use super::{MerkleInstance, MerkleInstance::*};
+    #[test]
+    fn note_tree_capacity() {
+        assert_eq!(NOTE_TREE_CAPACITY, 96_889_010_407);
+    }
 
     #[test]
-    fn instance_order() {
-        // This is the order used in other parts of the codebase (e.g., in contracts).
-        let expected_order = vec![MerkleRoot];
-        assert_eq!(expected_order, MerkleInstance::iter().collect::<Vec<_>>());
+    fn capacity_saturates() {
+        assert_eq!(capacity(0, 7), 1);
+        assert_eq!(capacity(2, 3), 9);
+        assert_eq!(capacity(200, 7), u128::MAX);
     }
 }
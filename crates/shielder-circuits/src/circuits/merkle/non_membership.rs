@@ -0,0 +1,355 @@
+use halo2_proofs::plonk::{Advice, ConstraintSystem, Error};
+
+use crate::{
+    chips::{range_check::RangeCheckChip, sum::SumChip},
+    circuits::merkle::{chip::MerkleChip, knowledge::MerkleProverKnowledge, MerkleInstance},
+    column_pool::{ColumnPool, ConfigPhase},
+    consts::merkle_constants,
+    gates::{
+        digit_increment::{DigitIncrementGate, DigitIncrementGateInput},
+        positioned_membership::{PositionedMembershipGate, PositionedMembershipGateInput},
+        sum::SumGate,
+        Gate,
+    },
+    poseidon::circuit::hash,
+    synthesizer::Synthesizer,
+    AssignedCell, Field, Fr,
+};
+
+/// Chips and gates that [`MerkleChip::prove_non_membership`] needs besides the Merkle chip itself.
+#[derive(Clone, Debug)]
+pub struct NonMembershipChip<const ARITY: usize = { merkle_constants::ARITY }> {
+    range_check: RangeCheckChip,
+    sum: SumChip,
+    positioned_membership: PositionedMembershipGate<ARITY>,
+    digit_increment: DigitIncrementGate<ARITY>,
+}
+
+impl<const ARITY: usize> NonMembershipChip<ARITY> {
+    /// Configures the chip together with the range check and sum chips it depends on. The circuit
+    /// must enable constants in some fixed column.
+    pub fn new(
+        system: &mut ConstraintSystem<Fr>,
+        advice_pool: &mut ColumnPool<Advice, ConfigPhase>,
+    ) -> Self {
+        let sum = SumChip::new(SumGate::create_gate(system, advice_pool));
+        Self {
+            range_check: RangeCheckChip::new(system, advice_pool, sum.clone()),
+            sum,
+            positioned_membership: PositionedMembershipGate::create_gate(system, advice_pool),
+            digit_increment: DigitIncrementGate::create_gate(system, advice_pool),
+        }
+    }
+}
+
+/// A leaf together with its path and its position in the tree: `position[h]` is the index of the
+/// level-`h` node on the path within `knowledge.path[h]`, i.e., the `h`-th least significant
+/// base-`ARITY` digit of the leaf index.
+#[derive(Clone, Debug)]
+pub struct PositionedLeaf<
+    const TREE_HEIGHT: usize,
+    const ARITY: usize = { merkle_constants::ARITY },
+> {
+    pub knowledge: MerkleProverKnowledge<TREE_HEIGHT, AssignedCell, ARITY>,
+    pub position: [AssignedCell; TREE_HEIGHT],
+}
+
+impl<const ARITY: usize> MerkleChip<ARITY> {
+    /// Proves that `needle` is not a leaf of the tree. Both `low` and `high` are constrained to be
+    /// members of the tree (with the Merkle root from the public input), the index of `high` is
+    /// constrained to be the index of `low` plus one (the two may lie in different groups), and the
+    /// chip constrains `low < needle < high`.
+    ///
+    /// # Sorted-tree assumption
+    ///
+    /// This proves absence only if the leaves of the tree are sorted. This is not checked here: it
+    /// is the responsibility of whoever maintains the tree (e.g., the contract inserting leaves) to
+    /// keep it sorted.
+    ///
+    /// Moreover, the inequalities are checked by range-checking the differences
+    /// (`needle - low - 1` and `high - needle - 1`) to `CHUNKS` chunks, so all the three values must
+    /// be known to be below `2^(RANGE_PROOF_CHUNK_SIZE * CHUNKS)`.
+    pub fn prove_non_membership<const TREE_HEIGHT: usize, const CHUNKS: usize>(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        chip: &NonMembershipChip<ARITY>,
+        low: &PositionedLeaf<TREE_HEIGHT, ARITY>,
+        high: &PositionedLeaf<TREE_HEIGHT, ARITY>,
+        needle: AssignedCell,
+    ) -> Result<(), Error> {
+        let low_root = self.synthesize_positioned(synthesizer, chip, low)?;
+        let high_root = self.synthesize_positioned(synthesizer, chip, high)?;
+        self.public_inputs.constrain_cells(
+            synthesizer,
+            [
+                (low_root, MerkleInstance::MerkleRoot),
+                (high_root, MerkleInstance::MerkleRoot),
+            ],
+        )?;
+
+        constrain_successor(synthesizer, chip, &low.position, &high.position)?;
+
+        let (low, high) = (low.knowledge.leaf.clone(), high.knowledge.leaf.clone());
+        constrain_less_than::<CHUNKS, ARITY>(synthesizer, chip, low, needle.clone())?;
+        constrain_less_than::<CHUNKS, ARITY>(synthesizer, chip, needle, high)
+    }
+
+    /// Like [`Self::synthesize`], but additionally binds every node on the path to its position and
+    /// returns the computed root instead of constraining it.
+    fn synthesize_positioned<const TREE_HEIGHT: usize>(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        chip: &NonMembershipChip<ARITY>,
+        leaf: &PositionedLeaf<TREE_HEIGHT, ARITY>,
+    ) -> Result<AssignedCell, Error> {
+        let mut current_root = leaf.knowledge.leaf.clone();
+
+        for (level, position) in leaf.knowledge.path.iter().zip(&leaf.position) {
+            chip.positioned_membership.apply_in_new_region(
+                synthesizer,
+                PositionedMembershipGateInput {
+                    needle: current_root,
+                    position: position.clone(),
+                    haystack: level.clone(),
+                },
+            )?;
+            current_root = hash(synthesizer, self.poseidon.clone(), level.clone())?;
+        }
+
+        Ok(current_root)
+    }
+}
+
+/// Constrains `next_position` to be the successor of `position` (both given as base-`ARITY`
+/// digits, least significant first, already known to be below `ARITY`). The increment must not
+/// overflow, i.e., `position` cannot be the last index of the tree.
+fn constrain_successor<const ARITY: usize>(
+    synthesizer: &mut impl Synthesizer,
+    chip: &NonMembershipChip<ARITY>,
+    position: &[AssignedCell],
+    next_position: &[AssignedCell],
+) -> Result<(), Error> {
+    let mut carry = synthesizer.assign_constant("one", Fr::ONE)?;
+
+    for (digit, next_digit) in position.iter().zip(next_position) {
+        let carry_out = synthesizer.assign_value(
+            "carry",
+            (digit.value().cloned() + carry.value().cloned()).map(|sum| {
+                if sum == Fr::from(ARITY as u64) {
+                    Fr::ONE
+                } else {
+                    Fr::ZERO
+                }
+            }),
+        )?;
+        chip.digit_increment.apply_in_new_region(
+            synthesizer,
+            DigitIncrementGateInput {
+                digit: digit.clone(),
+                carry_in: carry,
+                next_digit: next_digit.clone(),
+                carry_out: carry_out.clone(),
+            },
+        )?;
+        carry = carry_out;
+    }
+
+    chip.sum.constrain_zero(synthesizer, carry)
+}
+
+/// Constrains `lhs < rhs` by range-checking `rhs - lhs - 1`.
+fn constrain_less_than<const CHUNKS: usize, const ARITY: usize>(
+    synthesizer: &mut impl Synthesizer,
+    chip: &NonMembershipChip<ARITY>,
+    lhs: AssignedCell,
+    rhs: AssignedCell,
+) -> Result<(), Error> {
+    let one = synthesizer.assign_constant("one", Fr::ONE)?;
+    let lhs_plus_one = synthesizer.assign_value("lhs_plus_one", lhs.value() + one.value())?;
+    chip.sum
+        .constrain_sum(synthesizer, lhs, one, lhs_plus_one.clone())?;
+
+    let gap = synthesizer.assign_value("gap", rhs.value() - lhs_plus_one.value())?;
+    chip.sum
+        .constrain_sum(synthesizer, lhs_plus_one, gap.clone(), rhs)?;
+
+    chip.range_check.constrain_value::<CHUNKS>(synthesizer, gap)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{vec, vec::Vec};
+
+    use halo2_proofs::{
+        circuit::{floor_planner::V1, Layouter},
+        dev::{MockProver, VerifyFailure},
+        plonk::{Advice, Circuit, ConstraintSystem, Error},
+    };
+
+    use crate::{
+        chips::range_check::test_utils::with_forged_witnesses,
+        column_pool::{ColumnPool, PreSynthesisPhase},
+        config_builder::ConfigsBuilder,
+        consts::merkle_constants::ARITY,
+        embed::Embed,
+        instance_wrapper::InstanceWrapper,
+        merkle::{
+            MerkleChip, MerkleInstance, MerkleProverKnowledge, MerkleTree, NonMembershipChip,
+            PositionedLeaf,
+        },
+        synthesizer::create_synthesizer,
+        Fr, Value,
+    };
+
+    const SORTED_TREE_HEIGHT: usize = 2;
+    const CHUNKS: usize = 2;
+
+    #[derive(Clone, Debug, Default)]
+    struct NonMembershipCircuit {
+        low: MerkleProverKnowledge<SORTED_TREE_HEIGHT, Value>,
+        low_position: [Value; SORTED_TREE_HEIGHT],
+        high: MerkleProverKnowledge<SORTED_TREE_HEIGHT, Value>,
+        high_position: [Value; SORTED_TREE_HEIGHT],
+        needle: Value,
+    }
+
+    impl Circuit<Fr> for NonMembershipCircuit {
+        type Config = (
+            MerkleChip,
+            NonMembershipChip,
+            ColumnPool<Advice, PreSynthesisPhase>,
+        );
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let public_inputs = InstanceWrapper::<MerkleInstance>::new(meta);
+
+            // The range check needs a constant column for its zero cell.
+            let fixed = meta.fixed_column();
+            meta.enable_constant(fixed);
+
+            let configs_builder = ConfigsBuilder::new(meta).with_merkle(public_inputs);
+            let merkle = configs_builder.merkle_chip();
+            let advice_pool = configs_builder.finish();
+            // The non-membership chip gets columns of its own, the cells it works on are copied.
+            let non_membership = NonMembershipChip::new(meta, &mut ColumnPool::<Advice, _>::new());
+
+            (merkle, non_membership, advice_pool)
+        }
+
+        fn synthesize(
+            &self,
+            (chip, non_membership, column_pool): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let pool = column_pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+            let low = PositionedLeaf {
+                knowledge: self.low.embed(&mut synthesizer, "low")?,
+                position: self.low_position.embed(&mut synthesizer, "low_position")?,
+            };
+            let high = PositionedLeaf {
+                knowledge: self.high.embed(&mut synthesizer, "high")?,
+                position: self
+                    .high_position
+                    .embed(&mut synthesizer, "high_position")?,
+            };
+            let needle = self.needle.embed(&mut synthesizer, "needle")?;
+
+            chip.prove_non_membership::<SORTED_TREE_HEIGHT, CHUNKS>(
+                &mut synthesizer,
+                &non_membership,
+                &low,
+                &high,
+                needle,
+            )
+        }
+    }
+
+    /// A tree with the sorted leaves `10, 20, …, 10 · (ARITY + 1)`, so that the last two are in
+    /// different groups.
+    fn sorted_tree() -> MerkleTree<SORTED_TREE_HEIGHT> {
+        let mut tree = MerkleTree::new();
+        for index in 0..=ARITY {
+            tree.insert(index, Fr::from(10 * (index as u64 + 1)));
+        }
+        tree
+    }
+
+    fn knowledge(
+        tree: &MerkleTree<SORTED_TREE_HEIGHT>,
+        index: usize,
+    ) -> MerkleProverKnowledge<SORTED_TREE_HEIGHT, Value> {
+        MerkleProverKnowledge {
+            leaf: Value::known(tree.leaf(index)),
+            path: tree.path(index).map(|level| level.map(Value::known)),
+        }
+    }
+
+    fn position(index: usize) -> [Value; SORTED_TREE_HEIGHT] {
+        MerkleTree::<SORTED_TREE_HEIGHT>::position(index)
+            .map(|digit| Value::known(Fr::from(digit as u64)))
+    }
+
+    /// Proves that `needle` is absent from `sorted_tree()`, using the leaves at `low_index` and
+    /// `high_index` (with their actual positions) as the boundaries.
+    fn circuit(low_index: usize, high_index: usize, needle: u64) -> NonMembershipCircuit {
+        let tree = sorted_tree();
+        NonMembershipCircuit {
+            low: knowledge(&tree, low_index),
+            low_position: position(low_index),
+            high: knowledge(&tree, high_index),
+            high_position: position(high_index),
+            needle: Value::known(Fr::from(needle)),
+        }
+    }
+
+    fn verify(circuit: NonMembershipCircuit) -> Result<(), Vec<VerifyFailure>> {
+        MockProver::run(11, &circuit, vec![vec![sorted_tree().root()]])
+            .expect("Mock prover should run successfully")
+            .verify()
+    }
+
+    #[test]
+    fn needle_in_a_gap_is_absent() {
+        assert!(verify(circuit(1, 2, 25)).is_ok());
+        assert!(verify(circuit(1, 2, 21)).is_ok());
+        assert!(verify(circuit(1, 2, 29)).is_ok());
+    }
+
+    #[test]
+    fn needle_between_groups_is_absent() {
+        assert!(verify(circuit(ARITY - 1, ARITY, 10 * ARITY as u64 + 5)).is_ok());
+    }
+
+    #[test]
+    fn needle_equal_to_low_fails() {
+        // `needle - low - 1 = -1` is out of range, so its range check witness must be forged.
+        assert!(with_forged_witnesses(|| verify(circuit(1, 2, 20))).is_err());
+    }
+
+    #[test]
+    fn needle_equal_to_high_fails() {
+        assert!(with_forged_witnesses(|| verify(circuit(1, 2, 30))).is_err());
+    }
+
+    #[test]
+    fn non_adjacent_leaves_fail() {
+        // `30` is the leaf at index 2, skipped over by taking the leaves at indices 1 and 3.
+        assert!(verify(circuit(1, 3, 30)).is_err());
+    }
+
+    #[test]
+    fn misplaced_leaf_fails() {
+        // The leaf at index 3 claims to be at index 2, right after the leaf at index 1.
+        let forged = NonMembershipCircuit {
+            high_position: position(2),
+            ..circuit(1, 3, 30)
+        };
+        assert!(verify(forged).is_err());
+    }
+}
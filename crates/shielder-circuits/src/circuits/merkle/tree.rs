@@ -0,0 +1,185 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use crate::{
+    circuits::merkle::capacity, consts::merkle_constants::ARITY, poseidon::off_circuit::hash,
+    Field, Fr,
+};
+
+/// Sparse, off-circuit Merkle tree with the same shape as the one used by `MerkleCircuit`. Leaves
+/// that were never inserted are zero. Meant for building consistent witnesses in scenarios spanning
+/// several transactions.
+///
+/// Leaves can be set at arbitrary indices with [`Self::insert`] or appended after the last one with
+/// [`Self::append`], which mirrors the on-chain note tree.
+#[derive(Clone, Debug)]
+pub struct MerkleTree<const TREE_HEIGHT: usize> {
+    leaves: BTreeMap<usize, Fr>,
+    /// The index following the highest one set so far.
+    next_index: usize,
+    /// Roots of empty subtrees, indexed by height.
+    empty_nodes: Vec<Fr>,
+}
+
+impl<const TREE_HEIGHT: usize> Default for MerkleTree<TREE_HEIGHT> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const TREE_HEIGHT: usize> MerkleTree<TREE_HEIGHT> {
+    pub fn new() -> Self {
+        let mut empty_nodes = Vec::with_capacity(TREE_HEIGHT + 1);
+        empty_nodes.push(Fr::ZERO);
+        for height in 0..TREE_HEIGHT {
+            empty_nodes.push(hash(&[empty_nodes[height]; ARITY]));
+        }
+
+        Self {
+            leaves: BTreeMap::new(),
+            next_index: 0,
+            empty_nodes,
+        }
+    }
+
+    /// Sets the leaf at `index`. Panics if `index` exceeds the tree capacity.
+    pub fn insert(&mut self, index: usize, leaf: Fr) {
+        assert!(
+            (index as u128) < capacity(TREE_HEIGHT, ARITY),
+            "leaf index out of range"
+        );
+        self.leaves.insert(index, leaf);
+        self.next_index = self.next_index.max(index.saturating_add(1));
+    }
+
+    /// Sets the leaf following the highest one set so far (or the first one in an empty tree) and
+    /// returns its index. Panics if the tree is full.
+    pub fn append(&mut self, leaf: Fr) -> usize {
+        let index = self.next_index;
+        self.insert(index, leaf);
+        index
+    }
+
+    pub fn leaf(&self, index: usize) -> Fr {
+        self.leaves.get(&index).copied().unwrap_or(Fr::ZERO)
+    }
+
+    pub fn root(&self) -> Fr {
+        self.node(TREE_HEIGHT, 0)
+    }
+
+    /// The path of the leaf at `index`, in the format expected by `MerkleProverKnowledge`: level
+    /// `h` holds the node at height `h` together with its siblings, so the first level contains
+    /// the leaf and the root is the hash of the last level.
+    pub fn path(&self, index: usize) -> [[Fr; ARITY]; TREE_HEIGHT] {
+        core::array::from_fn(|height| {
+            // If the subtree is wider than `usize`, all leaves belong to the first one.
+            let parent_index = subtree_width(height + 1).map_or(0, |width| index / width);
+            self.children(height, parent_index)
+        })
+    }
+
+    /// The position of the leaf at `index` along its path: the index of the node at height `h`
+    /// within level `h` of [`Self::path`], i.e., the base-`ARITY` digits of `index`, least
+    /// significant first.
+    pub fn position(index: usize) -> [usize; TREE_HEIGHT] {
+        core::array::from_fn(|height| {
+            subtree_width(height).map_or(0, |width| index / width % ARITY)
+        })
+    }
+
+    /// The node at `height` (leaves are at height 0) and `index` within its level.
+    fn node(&self, height: usize, index: usize) -> Fr {
+        // Leaf indices are `usize`, so the range of a subtree wider than that can be clamped.
+        let width = subtree_width(height).unwrap_or(usize::MAX);
+        let first_leaf = index.saturating_mul(width);
+        let last_leaf = first_leaf.saturating_add(width - 1);
+
+        if self.leaves.range(first_leaf..=last_leaf).next().is_none() {
+            return self.empty_nodes[height];
+        }
+        if height == 0 {
+            return self.leaf(index);
+        }
+        hash(&self.children(height - 1, index))
+    }
+
+    /// The children (at `height`) of the node at `height + 1` and `parent_index`.
+    fn children(&self, height: usize, parent_index: usize) -> [Fr; ARITY] {
+        core::array::from_fn(|i| self.node(height, parent_index * ARITY + i))
+    }
+}
+
+/// Number of leaves below a node at `height`, unless it does not fit in `usize` (which is the case
+/// already for the note tree root on 32-bit targets).
+fn subtree_width(height: usize) -> Option<usize> {
+    u32::try_from(height)
+        .ok()
+        .and_then(|height| ARITY.checked_pow(height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MerkleTree;
+    use crate::{
+        circuits::{
+            merkle::MerkleProverKnowledge, test_utils::expect_prover_success_and_run_verification,
+        },
+        consts::merkle_constants::{ARITY, NOTE_TREE_HEIGHT},
+        poseidon::off_circuit::hash,
+        Field, Fr, ProverKnowledge, PublicInputProvider,
+    };
+
+    #[test]
+    fn path_is_consistent_with_root() {
+        let mut tree = MerkleTree::<NOTE_TREE_HEIGHT>::new();
+        tree.insert(0, Fr::from(1));
+        tree.insert(12345, Fr::from(2));
+        tree.insert(12346, Fr::from(3));
+
+        for index in [0, 12345, 12346, 54321] {
+            let path = tree.path(index);
+
+            assert!(path[0].contains(&tree.leaf(index)));
+            for levels in path.windows(2) {
+                assert!(levels[1].contains(&hash(&levels[0])));
+            }
+            assert_eq!(hash(&path[NOTE_TREE_HEIGHT - 1]), tree.root());
+        }
+    }
+
+    #[test]
+    fn empty_tree_root() {
+        let root = (0..NOTE_TREE_HEIGHT).fold(Fr::ZERO, |node, _| hash(&[node; ARITY]));
+        assert_eq!(MerkleTree::<NOTE_TREE_HEIGHT>::new().root(), root);
+    }
+
+    #[test]
+    fn append_uses_consecutive_indices() {
+        let mut tree = MerkleTree::<NOTE_TREE_HEIGHT>::new();
+
+        assert_eq!(tree.append(Fr::from(1)), 0);
+        assert_eq!(tree.append(Fr::from(2)), 1);
+        assert_eq!(tree.append(Fr::from(3)), 2);
+
+        tree.insert(10, Fr::from(4));
+        assert_eq!(tree.append(Fr::from(5)), 11);
+    }
+
+    #[test]
+    fn appended_leaf_path_verifies_in_circuit() {
+        let mut tree = MerkleTree::<NOTE_TREE_HEIGHT>::new();
+        for leaf in 1..10 {
+            tree.append(Fr::from(leaf));
+        }
+        let leaf = Fr::from(12345);
+        let index = tree.append(leaf);
+
+        let (root, path) = (tree.root(), tree.path(index));
+        let knowledge = MerkleProverKnowledge::<NOTE_TREE_HEIGHT, Fr>::new(leaf, path);
+
+        assert_eq!(knowledge.serialize_public_input(), [root]);
+        assert!(
+            expect_prover_success_and_run_verification(knowledge.create_circuit(), &[root]).is_ok()
+        );
+    }
+}
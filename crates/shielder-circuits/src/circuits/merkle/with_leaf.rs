@@ -0,0 +1,134 @@
+use halo2_proofs::{
+    circuit::{floor_planner::V1, Layouter},
+    plonk::{Advice, Circuit, ConstraintSystem, Error},
+};
+use rand_core::RngCore;
+
+use crate::{
+    column_pool::{ColumnPool, PreSynthesisPhase},
+    config_builder::ConfigsBuilder,
+    embed::Embed,
+    instance_wrapper::InstanceWrapper,
+    merkle::{MerkleChip, MerkleInstance, MerkleInstanceWithLeaf, MerkleProverKnowledge},
+    synthesizer::create_synthesizer,
+    Fr, ProverKnowledge, PublicInputProvider, Value,
+};
+
+/// Same as `MerkleCircuit`, but the leaf is a public input too.
+#[derive(Clone, Debug, Default)]
+pub struct MerkleWithLeafCircuit<const TREE_HEIGHT: usize>(
+    pub MerkleProverKnowledge<TREE_HEIGHT, Value>,
+);
+
+impl<const TREE_HEIGHT: usize> MerkleWithLeafCircuit<TREE_HEIGHT> {
+    /// Same witness as `MerkleCircuit::example()`.
+    pub fn example() -> Self {
+        MerkleWithLeafProverKnowledge::<TREE_HEIGHT>::example().create_circuit()
+    }
+}
+
+impl<const TREE_HEIGHT: usize> Circuit<Fr> for MerkleWithLeafCircuit<TREE_HEIGHT> {
+    type Config = (
+        MerkleChip,
+        InstanceWrapper<MerkleInstanceWithLeaf>,
+        ColumnPool<Advice, PreSynthesisPhase>,
+    );
+    type FloorPlanner = V1;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+        let public_inputs = InstanceWrapper::<MerkleInstanceWithLeaf>::new(meta);
+        let configs_builder = ConfigsBuilder::new(meta).with_merkle(public_inputs.narrow());
+        (
+            configs_builder.merkle_chip(),
+            public_inputs,
+            configs_builder.finish(),
+        )
+    }
+
+    fn synthesize(
+        &self,
+        (main_chip, public_inputs, column_pool): Self::Config,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<(), Error> {
+        let pool = column_pool.start_synthesis();
+        let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+        let knowledge = self.0.embed(&mut synthesizer, "MerkleProverKnowledge")?;
+
+        main_chip.synthesize(&mut synthesizer, &knowledge)?;
+        public_inputs.constrain_cells(
+            &mut synthesizer,
+            [(knowledge.leaf, MerkleInstanceWithLeaf::Leaf)],
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MerkleWithLeafProverKnowledge<const TREE_HEIGHT: usize>(
+    pub MerkleProverKnowledge<TREE_HEIGHT, Fr>,
+);
+
+impl<const TREE_HEIGHT: usize> ProverKnowledge for MerkleWithLeafProverKnowledge<TREE_HEIGHT> {
+    type Circuit = MerkleWithLeafCircuit<TREE_HEIGHT>;
+    type PublicInput = MerkleInstanceWithLeaf;
+
+    fn circuit_name() -> &'static str {
+        "merkle_with_leaf"
+    }
+
+    fn random_correct_example(rng: &mut impl RngCore) -> Self {
+        Self(MerkleProverKnowledge::random_correct_example(rng))
+    }
+
+    fn create_circuit(&self) -> MerkleWithLeafCircuit<TREE_HEIGHT> {
+        MerkleWithLeafCircuit(self.0.create_circuit().0)
+    }
+}
+
+impl<const TREE_HEIGHT: usize> PublicInputProvider<MerkleInstanceWithLeaf>
+    for MerkleWithLeafProverKnowledge<TREE_HEIGHT>
+{
+    fn compute_public_input(&self, instance_id: MerkleInstanceWithLeaf) -> Fr {
+        match instance_id {
+            MerkleInstanceWithLeaf::MerkleRoot => {
+                self.0.compute_public_input(MerkleInstance::MerkleRoot)
+            }
+            MerkleInstanceWithLeaf::Leaf => self.0.leaf,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_core::OsRng;
+
+    use super::MerkleWithLeafProverKnowledge;
+    use crate::{
+        circuits::test_utils::{
+            expect_prover_success_and_run_verification, run_full_pipeline, PublicInputProviderExt,
+        },
+        consts::merkle_constants::NOTE_TREE_HEIGHT,
+        merkle::MerkleInstanceWithLeaf::Leaf,
+        Field, Fr, ProverKnowledge,
+    };
+
+    #[test]
+    fn positive_pipeline_for_merkle_with_leaf_circuit() {
+        run_full_pipeline::<MerkleWithLeafProverKnowledge<{ NOTE_TREE_HEIGHT }>>()
+    }
+
+    #[test]
+    fn fails_if_wrong_leaf_is_published() {
+        let pk = MerkleWithLeafProverKnowledge::<{ NOTE_TREE_HEIGHT }>::random_correct_example(
+            &mut OsRng,
+        );
+        let pub_input = pk.with_substitution(Leaf, |leaf| leaf + Fr::ONE);
+
+        assert!(
+            expect_prover_success_and_run_verification(pk.create_circuit(), &pub_input).is_err()
+        );
+    }
+}
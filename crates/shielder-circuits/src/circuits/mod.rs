@@ -1,4 +1,5 @@
-use alloc::{vec, vec::Vec};
+use alloc::{collections::BTreeMap, format, string::String, sync::Arc, vec, vec::Vec};
+use core::fmt::{self, Debug, Display, Formatter};
 
 use halo2_proofs::{
     dev::MockProver,
@@ -9,24 +10,47 @@ use halo2_proofs::{
         kzg::{
             commitment::{KZGCommitmentScheme, ParamsKZG},
             multiopen::{ProverSHPLONK, VerifierSHPLONK},
-            strategy::SingleStrategy,
+            strategy::{AccumulatorStrategy, SingleStrategy},
         },
+        VerificationStrategy as _,
     },
     transcript::TranscriptWriterBuffer as _,
 };
 use rand_core::RngCore;
+use sha3::{Digest, Keccak256};
+use strum::{EnumCount, IntoEnumIterator};
 use transcript::Keccak256Transcript;
 
-use crate::consts::MAX_K;
+use crate::{
+    consts::MAX_K, deposit::DepositInstance, merkle::MerkleInstance, withdraw::WithdrawInstance,
+    PrimeField,
+};
+#[cfg(feature = "chip-elgamal")]
+use crate::{
+    new_account::NewAccountInstance, new_account_with_deposit::NewAccountWithDepositInstance,
+};
 
 pub mod deposit;
 pub mod merkle;
+#[cfg(feature = "chip-elgamal")]
 pub mod new_account;
+#[cfg(feature = "chip-elgamal")]
+pub mod new_account_with_deposit;
+pub mod reference;
 pub mod withdraw;
 
+pub mod bundle;
+mod cost;
+pub mod interop;
+pub mod key_set;
 pub mod marshall;
+mod range_check_cost;
+pub mod registry;
 #[cfg(test)]
 pub mod test_utils;
+pub use cost::{report_cost, CostSummary};
+pub use range_check_cost::k_delta_with_range_check;
+pub use registry::{by_name, CircuitId};
 #[cfg(test)]
 pub use test_utils::rng;
 
@@ -71,6 +95,22 @@ pub fn generate_keys_with_min_k(
     Err(last_err.expect("Loop has failed at least once"))
 }
 
+// Generates the verifying and proving keys for exactly `k`, downsizing `params` once. Meant for
+// setups where `k` is known up front (e.g., pinned by deployed contracts). Returns the error from
+// key generation if `k` is too small for `circuit`.
+pub fn generate_keys_with_fixed_k(
+    circuit: impl Circuit<Fr>,
+    mut params: Params,
+    k: u32,
+) -> Result<(Params, ProvingKey, VerifyingKey), Error> {
+    let circuit = circuit.without_witnesses();
+    params.downsize(k);
+
+    let vk = keygen_vk_custom(&params, &circuit, COMPRESS_SELECTORS)?;
+    let pk = keygen_pk(&params, vk.clone(), &circuit)?;
+    Ok((params, pk, vk))
+}
+
 // Runs the mock prover and panics in case of an error.
 pub fn run_mock_prover<C: Circuit<Fr>>(k: u32, circuit: &C, pub_input: Vec<Fr>) {
     let prover = MockProver::run(k, circuit, vec![pub_input]).expect("Mock prover should run");
@@ -103,12 +143,116 @@ pub fn generate_proof<C: Circuit<Fr>>(
     transcript.finalize().to_vec()
 }
 
+// Like `generate_proof`, but streams the transcript directly into `writer` instead of collecting
+// it into an intermediate buffer. Returns the writer once the proof is written.
+#[cfg(any(test, feature = "std"))]
+pub fn generate_proof_into<C: Circuit<Fr>, W: std::io::Write>(
+    params: &Params,
+    pk: &ProvingKey,
+    circuit: C,
+    pub_input: &[Fr],
+    rng: &mut impl RngCore,
+    writer: W,
+) -> W {
+    let mut transcript = Keccak256Transcript::new(writer);
+
+    create_proof::<CommitmentScheme, Prover, _, _, _, C>(
+        params,
+        pk,
+        &[circuit],
+        &[&[pub_input]],
+        rng,
+        &mut transcript,
+    )
+    .expect("proof should not fail to generate");
+
+    transcript.finalize()
+}
+
+// Generates proofs for all `circuits` concurrently, the i-th one for the public input
+// `pub_inputs[i]`. Every proof uses its own RNG seeded with `rng_seeds[i]` (rather than a shared,
+// stateful one), so the result does not depend on thread scheduling and matches
+// `generate_proof` run sequentially with the same seeds.
+#[cfg(feature = "multithreading")]
+pub fn generate_proofs_parallel<C: Circuit<Fr> + Send>(
+    params: &Params,
+    pk: &ProvingKey,
+    circuits: Vec<C>,
+    pub_inputs: Vec<Vec<Fr>>,
+    rng_seeds: Vec<[u8; 32]>,
+) -> Vec<Vec<u8>> {
+    use rand::{rngs::StdRng, SeedableRng};
+    use rayon::prelude::*;
+
+    assert_eq!(
+        circuits.len(),
+        pub_inputs.len(),
+        "every circuit needs a public input"
+    );
+    assert_eq!(
+        circuits.len(),
+        rng_seeds.len(),
+        "every circuit needs an RNG seed"
+    );
+
+    circuits
+        .into_par_iter()
+        .zip(pub_inputs)
+        .zip(rng_seeds)
+        .map(|((circuit, pub_input), seed)| {
+            generate_proof(
+                params,
+                pk,
+                circuit,
+                &pub_input,
+                &mut StdRng::from_seed(seed),
+            )
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+pub enum VerifyError {
+    /// The verifying key comes from a circuit with `actual` instance columns instead of the single
+    /// one of our circuits (e.g., from an incompatible circuit version).
+    InstanceColumnsMismatch {
+        actual: usize,
+    },
+    Proof(Error),
+}
+
+impl From<Error> for VerifyError {
+    fn from(error: Error) -> Self {
+        VerifyError::Proof(error)
+    }
+}
+
+impl Display for VerifyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyError::InstanceColumnsMismatch { actual } => write!(
+                f,
+                "Verifying key has {actual} instance columns instead of {INSTANCE_COLUMNS}"
+            ),
+            VerifyError::Proof(e) => write!(f, "Proof verification failed: {e}"),
+        }
+    }
+}
+
+// Number of instance columns of every circuit in this crate.
+const INSTANCE_COLUMNS: usize = 1;
+
+// Verifies the proof against the public input `instance`, which occupies the single instance
+// column of the circuit. Returns `VerifyError::InstanceColumnsMismatch` if `vk` comes from a
+// circuit with a different number of instance columns.
 pub fn verify(
     params: &Params,
     vk: &VerifyingKey,
     transcript: &[u8],
     instance: &[Fr],
-) -> Result<(), Error> {
+) -> Result<(), VerifyError> {
+    check_instance_columns(vk)?;
+
     let mut transcript = Keccak256Transcript::new(transcript);
 
     verify_proof::<CommitmentScheme, Verifier, _, _, _>(
@@ -118,4 +262,725 @@ pub fn verify(
         &[&[instance]],
         &mut transcript,
     )
+    .map_err(VerifyError::Proof)
+}
+
+fn check_instance_columns(vk: &VerifyingKey) -> Result<(), VerifyError> {
+    match marshall::vk_instance_columns(vk) {
+        INSTANCE_COLUMNS => Ok(()),
+        actual => Err(VerifyError::InstanceColumnsMismatch { actual }),
+    }
+}
+
+// Verifies the proof like `verify`, but takes shared ownership of `params` and `vk`. Both are
+// `Send + Sync`, so a single setup can be handed out to many threads (e.g., async request handlers)
+// without borrowing from a common scope.
+pub fn verify_arc(
+    params: Arc<Params>,
+    vk: Arc<VerifyingKey>,
+    transcript: &[u8],
+    instance: &[Fr],
+) -> Result<(), VerifyError> {
+    verify(&params, &vk, transcript, instance)
+}
+
+// Verifies the proof like `verify` and, on success, returns the public inputs keyed by their
+// instance variants. `instance` must list the values in the iteration order of `Id` (otherwise
+// `Error::InvalidInstances` is returned).
+pub fn verify_and_decode<Id: IntoEnumIterator + EnumCount + Copy + Ord>(
+    params: &Params,
+    vk: &VerifyingKey,
+    transcript: &[u8],
+    instance: &[Fr],
+) -> Result<BTreeMap<Id, Fr>, VerifyError> {
+    if instance.len() != Id::COUNT {
+        return Err(Error::InvalidInstances.into());
+    }
+    verify(params, vk, transcript, instance)?;
+    Ok(Id::iter().zip(instance.iter().copied()).collect())
+}
+
+// Parses public inputs received as bytes (e.g., from a contract), each being the little-endian
+// representation of a field element (as produced by `Fr::to_repr`). Returns the index of the first
+// non-canonical element (i.e., not smaller than the field modulus). Verifiers should call this
+// before `verify` rather than reducing such inputs silently.
+pub fn validate_public_inputs(bytes: &[[u8; 32]]) -> Result<Vec<Fr>, usize> {
+    bytes
+        .iter()
+        .enumerate()
+        .map(|(index, repr)| Option::<Fr>::from(Fr::from_repr(*repr)).ok_or(index))
+        .collect()
+}
+
+static_assertions::assert_impl_all!(Params: Send, Sync);
+static_assertions::assert_impl_all!(VerifyingKey: Send, Sync);
+
+// Verifies the proof like `verify`, but rejects `transcript` up front (with
+// `Error::BoundsFailure`) if it is longer than `max_len` bytes. Verifiers exposed to untrusted
+// input should set `max_len` to the expected proof size.
+pub fn verify_bounded(
+    params: &Params,
+    vk: &VerifyingKey,
+    transcript: &[u8],
+    instance: &[Fr],
+    max_len: usize,
+) -> Result<(), VerifyError> {
+    if transcript.len() > max_len {
+        return Err(Error::BoundsFailure.into());
+    }
+    verify(params, vk, transcript, instance)
+}
+
+#[derive(Debug)]
+pub enum BatchVerifyError {
+    /// The verifying key is incompatible with our circuits (see [`VerifyError`]), so none of the
+    /// proofs was checked.
+    Key(VerifyError),
+    /// The proof at `index` is invalid (later proofs have not been checked individually).
+    Proof { index: usize, error: VerifyError },
+    /// The accumulated pairing check failed, but every proof is valid on its own.
+    Accumulation,
+}
+
+impl Display for BatchVerifyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BatchVerifyError::Key(e) => write!(f, "Invalid verifying key: {e}"),
+            BatchVerifyError::Proof { index, error } => write!(f, "Proof {index}: {error}"),
+            BatchVerifyError::Accumulation => {
+                write!(f, "Accumulated check failed although every proof is valid")
+            }
+        }
+    }
+}
+
+// Verifies many proofs for the same `vk` at once: the proofs are accumulated and checked with a
+// single final pairing, which is much cheaper than calling `verify` for each of them. On failure,
+// reports the first invalid proof, or a problem with `vk` that is not specific to any proof.
+pub fn verify_batch(
+    params: &Params,
+    vk: &VerifyingKey,
+    proofs: &[(&[u8], &[Fr])],
+) -> Result<(), BatchVerifyError> {
+    check_instance_columns(vk).map_err(BatchVerifyError::Key)?;
+
+    let mut strategy = AccumulatorStrategy::new(params.verifier_params());
+    for (index, (transcript, instance)) in proofs.iter().enumerate() {
+        let mut transcript = Keccak256Transcript::new(*transcript);
+        strategy = verify_proof::<CommitmentScheme, Verifier, _, _, _>(
+            params.verifier_params(),
+            vk,
+            strategy,
+            &[&[*instance]],
+            &mut transcript,
+        )
+        .map_err(|e| BatchVerifyError::Proof {
+            index,
+            error: VerifyError::Proof(e),
+        })?;
+    }
+
+    if strategy.finalize() {
+        return Ok(());
+    }
+
+    // The accumulated pairing check does not tell which proof is invalid, so fall back to checking
+    // them one by one.
+    for (index, (transcript, instance)) in proofs.iter().enumerate() {
+        verify(params, vk, transcript, instance)
+            .map_err(|error| BatchVerifyError::Proof { index, error })?;
+    }
+    Err(BatchVerifyError::Accumulation)
+}
+
+// Returns `true` iff the proofs are byte-for-byte identical. Meant for checking that proving is
+// reproducible (e.g., with a seeded RNG); see `proof_diff` for a description of a mismatch.
+pub fn proofs_equal(a: &[u8], b: &[u8]) -> bool {
+    proof_diff(a, b).is_none()
+}
+
+// Describes how the proofs differ (lengths and the first differing byte), or returns `None` if they
+// are identical.
+pub fn proof_diff(a: &[u8], b: &[u8]) -> Option<String> {
+    let first_difference = a.iter().zip(b).position(|(x, y)| x != y);
+    match first_difference {
+        None if a.len() == b.len() => None,
+        None => Some(format!(
+            "proofs have different lengths ({} and {} bytes) but a common prefix",
+            a.len(),
+            b.len()
+        )),
+        Some(offset) => Some(format!(
+            "proofs ({} and {} bytes) first differ at byte {offset}: {:#04x} != {:#04x}",
+            a.len(),
+            b.len(),
+            a[offset],
+            b[offset]
+        )),
+    }
+}
+
+// Panics if the variants of `Id`, in declaration order, differ from `expected`. The declaration
+// order determines the layout of the public input, which is also used in other parts of the
+// codebase (e.g., in contracts), so it must not change silently.
+pub fn assert_instance_order<Id: IntoEnumIterator + PartialEq + Debug>(expected: &[Id]) {
+    let actual = Id::iter().collect::<Vec<_>>();
+    assert_eq!(expected, actual.as_slice(), "unexpected instance order");
+}
+
+// Returns the Keccak256 hash of the names of the variants of `Id`, concatenated in declaration
+// order. A contract can pin this value to detect a reordering of the public input.
+pub fn instance_order_hash<Id: IntoEnumIterator + Debug>() -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    for id in Id::iter() {
+        hasher.update(format!("{id:?}"));
+    }
+    hasher.finalize().into()
+}
+
+// Returns the position of `variant` in the public input, i.e., its index in the iteration order
+// of `Id`.
+pub fn instance_index<Id: IntoEnumIterator + PartialEq>(variant: Id) -> usize {
+    Id::iter()
+        .position(|id| id == variant)
+        .expect("every variant is yielded by the iterator")
+}
+
+// Returns a JSON object mapping every circuit to the names of its public inputs, in the order in
+// which they appear in the instance column. Meant for generating code that depends on this order
+// (e.g., contracts). Circuits disabled by cargo features are omitted.
+pub fn instance_order_manifest() -> String {
+    let mut entries = Vec::new();
+    #[cfg(feature = "chip-elgamal")]
+    entries.extend([
+        ("new_account", instance_order_json::<NewAccountInstance>()),
+        (
+            "new_account_with_deposit",
+            instance_order_json::<NewAccountWithDepositInstance>(),
+        ),
+    ]);
+    entries.extend([
+        ("deposit", instance_order_json::<DepositInstance>()),
+        ("withdraw", instance_order_json::<WithdrawInstance>()),
+        ("merkle", instance_order_json::<MerkleInstance>()),
+    ]);
+
+    let entries = entries
+        .into_iter()
+        .map(|(circuit, order)| format!("\"{circuit}\":{order}"))
+        .collect::<Vec<_>>();
+    format!("{{{}}}", entries.join(","))
+}
+
+fn instance_order_json<Id: IntoEnumIterator + Debug>() -> String {
+    let names = Id::iter()
+        .map(|id| format!("\"{id:?}\""))
+        .collect::<Vec<_>>();
+    format!("[{}]", names.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{sync::Arc, vec, vec::Vec};
+    use std::thread;
+
+    use halo2_proofs::{
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+        poly::commitment::Params as _,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_core::OsRng;
+
+    use crate::{
+        circuits::{
+            assert_instance_order,
+            deposit::{
+                CheckedDepositCircuit, DepositCircuit, DepositInstance::*, DepositProverKnowledge,
+            },
+            generate_keys_with_fixed_k, generate_keys_with_min_k, generate_proof,
+            generate_proof_into, generate_setup_params, instance_index, instance_order_hash,
+            instance_order_manifest,
+            marshall::vk_instance_columns,
+            merkle::{
+                MerkleCircuit, MerkleProverKnowledge, MerkleWithLeafCircuit,
+                MerkleWithLeafProverKnowledge,
+            },
+            proof_diff, proofs_equal, rng,
+            test_utils::expect_prover_success_and_run_verification,
+            validate_public_inputs, verify, verify_and_decode, verify_arc, verify_batch,
+            verify_bounded,
+            withdraw::{WithdrawCircuit, WithdrawProverKnowledge},
+            BatchVerifyError, VerifyError,
+        },
+        consts::{merkle_constants::NOTE_TREE_HEIGHT, MAX_K},
+        withdraw::WithdrawInstance,
+        EnumCount, Field, Fr, IntoEnumIterator, PrimeField, ProverKnowledge, PublicInputProvider,
+        SERDE_FORMAT,
+    };
+
+    #[test]
+    fn fixed_k_keys_match_min_k_keys() {
+        let circuit =
+            MerkleProverKnowledge::<NOTE_TREE_HEIGHT, Fr>::random_correct_example(&mut OsRng)
+                .create_circuit();
+        let params = generate_setup_params(MAX_K, &mut OsRng);
+
+        let (_, k, min_pk, min_vk) = generate_keys_with_min_k(circuit.clone(), params.clone())
+            .expect("keys should not fail to generate");
+        let (fixed_params, fixed_pk, fixed_vk) =
+            generate_keys_with_fixed_k(circuit.clone(), params.clone(), k)
+                .expect("keys should not fail to generate");
+
+        assert_eq!(fixed_params.k(), k);
+        assert_eq!(
+            fixed_vk.to_bytes(SERDE_FORMAT),
+            min_vk.to_bytes(SERDE_FORMAT)
+        );
+        assert_eq!(
+            fixed_pk.to_bytes(SERDE_FORMAT),
+            min_pk.to_bytes(SERDE_FORMAT)
+        );
+
+        assert!(generate_keys_with_fixed_k(circuit, params, k - 1).is_err());
+    }
+
+    #[test]
+    fn bounded_verification_rejects_oversized_transcript() {
+        let mut rng = OsRng;
+
+        let knowledge =
+            MerkleProverKnowledge::<NOTE_TREE_HEIGHT, Fr>::random_correct_example(&mut rng);
+        let pub_input = knowledge.serialize_public_input();
+
+        let (params, _, pk, vk) = generate_keys_with_min_k(
+            knowledge.create_circuit(),
+            generate_setup_params(MAX_K, &mut rng),
+        )
+        .expect("keys should not fail to generate");
+        let proof = generate_proof(
+            &params,
+            &pk,
+            knowledge.create_circuit(),
+            &pub_input,
+            &mut rng,
+        );
+
+        assert!(verify_bounded(&params, &vk, &proof, &pub_input, proof.len()).is_ok());
+
+        let oversized = [proof.clone(), vec![0u8]].concat();
+        assert!(matches!(
+            verify_bounded(&params, &vk, &oversized, &pub_input, proof.len()),
+            Err(VerifyError::Proof(Error::BoundsFailure))
+        ));
+    }
+
+    #[test]
+    fn streamed_proof_matches_buffered_proof() {
+        let knowledge =
+            MerkleProverKnowledge::<NOTE_TREE_HEIGHT, Fr>::random_correct_example(&mut rng());
+        let pub_input = knowledge.serialize_public_input();
+
+        let (params, _, pk, _) = generate_keys_with_min_k(
+            knowledge.create_circuit(),
+            generate_setup_params(MAX_K, &mut rng()),
+        )
+        .expect("keys should not fail to generate");
+
+        let buffered = generate_proof(
+            &params,
+            &pk,
+            knowledge.create_circuit(),
+            &pub_input,
+            &mut rng(),
+        );
+        let streamed = generate_proof_into(
+            &params,
+            &pk,
+            knowledge.create_circuit(),
+            &pub_input,
+            &mut rng(),
+            Vec::new(),
+        );
+
+        assert_eq!(streamed, buffered);
+    }
+
+    #[test]
+    fn deposit_instance_order_matches_manifest() {
+        assert_instance_order(&[
+            MerkleRoot,
+            HashedOldNullifier,
+            HashedNewNote,
+            DepositValue,
+            Commitment,
+            TokenAddress,
+            MacSalt,
+            MacCommitment,
+        ]);
+
+        assert!(instance_order_manifest().contains(
+            "\"deposit\":[\"MerkleRoot\",\"HashedOldNullifier\",\"HashedNewNote\",\"DepositValue\",\
+             \"Commitment\",\"TokenAddress\",\"MacSalt\",\"MacCommitment\"]"
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "unexpected instance order")]
+    fn swapped_deposit_instances_are_rejected() {
+        assert_instance_order(&[
+            HashedOldNullifier,
+            MerkleRoot,
+            HashedNewNote,
+            DepositValue,
+            Commitment,
+            TokenAddress,
+            MacSalt,
+            MacCommitment,
+        ]);
+    }
+
+    #[test]
+    fn instance_index_follows_iteration_order() {
+        assert_eq!(instance_index(WithdrawInstance::MerkleRoot), 0);
+        assert_eq!(instance_index(WithdrawInstance::MacCommitment), 7);
+        assert_eq!(instance_index(HashedNewNote), 2);
+    }
+
+    #[cfg(feature = "chip-elgamal")]
+    #[test]
+    fn new_account_instance_index_follows_iteration_order() {
+        use crate::new_account::NewAccountInstance;
+
+        assert_eq!(instance_index(NewAccountInstance::HashedNote), 0);
+
+        for (index, instance) in NewAccountInstance::iter().enumerate() {
+            assert_eq!(instance_index(instance), index);
+            assert_eq!(instance_index(instance), instance as usize);
+        }
+    }
+
+    #[cfg(feature = "chip-elgamal")]
+    #[test]
+    fn new_account_instance_order_hash_is_stable() {
+        use crate::new_account::NewAccountInstance;
+
+        // Pinned by the contracts. Changing it requires redeploying them.
+        assert_eq!(
+            instance_order_hash::<NewAccountInstance>(),
+            [
+                0x82, 0xcf, 0x0d, 0xd7, 0xa4, 0xeb, 0xcd, 0x0b, 0x71, 0x35, 0x3b, 0x35, 0x31, 0xe0,
+                0xd4, 0x29, 0xf0, 0x22, 0xbb, 0x39, 0xc3, 0xe0, 0x49, 0x4e, 0xba, 0xb1, 0xd1, 0x2d,
+                0x9a, 0xcd, 0x17, 0x8a
+            ]
+        );
+    }
+
+    #[test]
+    fn verification_with_shared_params_works_across_threads() {
+        let knowledge =
+            MerkleProverKnowledge::<NOTE_TREE_HEIGHT, Fr>::random_correct_example(&mut rng());
+        let pub_input = knowledge.serialize_public_input();
+
+        let (params, _, pk, vk) = generate_keys_with_min_k(
+            knowledge.create_circuit(),
+            generate_setup_params(MAX_K, &mut rng()),
+        )
+        .expect("keys should not fail to generate");
+        let proof = generate_proof(
+            &params,
+            &pk,
+            knowledge.create_circuit(),
+            &pub_input,
+            &mut rng(),
+        );
+
+        let (params, vk) = (Arc::new(params), Arc::new(vk));
+        let handles = (0..2)
+            .map(|_| {
+                let (params, vk) = (params.clone(), vk.clone());
+                let (proof, pub_input) = (proof.clone(), pub_input.clone());
+                thread::spawn(move || verify_arc(params, vk, &proof, &pub_input))
+            })
+            .collect::<Vec<_>>();
+
+        for handle in handles {
+            assert!(handle.join().expect("thread should not panic").is_ok());
+        }
+    }
+
+    #[test]
+    fn decoded_withdraw_inputs_match_prover_knowledge() {
+        let knowledge = WithdrawProverKnowledge::<Fr>::random_correct_example(&mut rng());
+        let pub_input = knowledge.serialize_public_input();
+
+        let (params, _, pk, vk) = generate_keys_with_min_k(
+            knowledge.create_circuit(),
+            generate_setup_params(MAX_K, &mut rng()),
+        )
+        .expect("keys should not fail to generate");
+        let proof = generate_proof(
+            &params,
+            &pk,
+            knowledge.create_circuit(),
+            &pub_input,
+            &mut rng(),
+        );
+
+        let decoded = verify_and_decode::<WithdrawInstance>(&params, &vk, &proof, &pub_input)
+            .expect("verification should succeed");
+
+        assert_eq!(decoded.len(), WithdrawInstance::COUNT);
+        for instance in WithdrawInstance::iter() {
+            assert_eq!(decoded[&instance], knowledge.compute_public_input(instance));
+        }
+
+        assert!(matches!(
+            verify_and_decode::<WithdrawInstance>(&params, &vk, &proof, &pub_input[1..]),
+            Err(VerifyError::Proof(Error::InvalidInstances))
+        ));
+    }
+
+    fn refs(batch: &[(Vec<u8>, Vec<Fr>)]) -> Vec<(&[u8], &[Fr])> {
+        batch
+            .iter()
+            .map(|(proof, input)| (proof.as_slice(), input.as_slice()))
+            .collect()
+    }
+
+    #[test]
+    fn batch_of_withdraw_proofs() {
+        let mut rng = rng();
+        let (params, _, pk, vk) = generate_keys_with_min_k(
+            WithdrawProverKnowledge::<Fr>::random_correct_example(&mut rng).create_circuit(),
+            generate_setup_params(MAX_K, &mut rng),
+        )
+        .expect("keys should not fail to generate");
+
+        let batch: Vec<(Vec<u8>, Vec<Fr>)> = (0..3)
+            .map(|_| {
+                let knowledge = WithdrawProverKnowledge::<Fr>::random_correct_example(&mut rng);
+                let pub_input = knowledge.serialize_public_input();
+                let proof = generate_proof(
+                    &params,
+                    &pk,
+                    knowledge.create_circuit(),
+                    &pub_input,
+                    &mut rng,
+                );
+                (proof, pub_input)
+            })
+            .collect();
+        assert!(verify_batch(&params, &vk, &refs(&batch)).is_ok());
+
+        let mut tampered = batch.clone();
+        tampered[1].1[0] += Fr::ONE;
+        assert!(matches!(
+            verify_batch(&params, &vk, &refs(&tampered)),
+            Err(BatchVerifyError::Proof { index: 1, .. })
+        ));
+    }
+
+    #[cfg(feature = "multithreading")]
+    #[test]
+    fn parallel_deposit_proofs_verify() {
+        let mut rng = rng();
+        let (params, _, pk, vk) = generate_keys_with_min_k(
+            DepositCircuit::default(),
+            generate_setup_params(MAX_K, &mut rng),
+        )
+        .expect("keys should not fail to generate");
+
+        let knowledge: Vec<_> = (0..2)
+            .map(|_| DepositProverKnowledge::<Fr>::random_correct_example(&mut rng))
+            .collect();
+        let pub_inputs: Vec<_> = knowledge
+            .iter()
+            .map(|knowledge| knowledge.serialize_public_input())
+            .collect();
+        let seeds = vec![[1; 32], [2; 32]];
+
+        let proofs = super::generate_proofs_parallel(
+            &params,
+            &pk,
+            knowledge
+                .iter()
+                .map(|knowledge| knowledge.create_circuit())
+                .collect(),
+            pub_inputs.clone(),
+            seeds.clone(),
+        );
+
+        for (i, proof) in proofs.iter().enumerate() {
+            assert!(verify(&params, &vk, proof, &pub_inputs[i]).is_ok());
+
+            let sequential = generate_proof(
+                &params,
+                &pk,
+                knowledge[i].create_circuit(),
+                &pub_inputs[i],
+                &mut StdRng::from_seed(seeds[i]),
+            );
+            assert!(proofs_equal(proof, &sequential));
+        }
+    }
+
+    fn assert_seeded_example_is_deterministic<PK: ProverKnowledge>() {
+        let public_input = |seed| PK::seeded_example(seed).serialize_public_input();
+
+        assert_eq!(public_input([7; 32]), public_input([7; 32]));
+        assert_ne!(public_input([7; 32]), public_input([8; 32]));
+    }
+
+    #[test]
+    fn seeded_examples_are_deterministic() {
+        #[cfg(feature = "chip-elgamal")]
+        assert_seeded_example_is_deterministic::<crate::new_account::NewAccountProverKnowledge<Fr>>(
+        );
+        assert_seeded_example_is_deterministic::<DepositProverKnowledge<Fr>>();
+        assert_seeded_example_is_deterministic::<WithdrawProverKnowledge<Fr>>();
+        assert_seeded_example_is_deterministic::<MerkleProverKnowledge<NOTE_TREE_HEIGHT, Fr>>();
+    }
+
+    fn assert_example_verifies<PK: ProverKnowledge>(circuit: impl Circuit<Fr> + Clone) {
+        let pub_input = PK::example().serialize_public_input();
+        assert!(expect_prover_success_and_run_verification(circuit, &pub_input).is_ok());
+    }
+
+    #[test]
+    fn examples_produce_verifying_proofs() {
+        #[cfg(feature = "chip-elgamal")]
+        {
+            use crate::{
+                new_account::{NewAccountCircuit, NewAccountProverKnowledge},
+                new_account_with_deposit::{
+                    NewAccountWithDepositCircuit, NewAccountWithDepositProverKnowledge,
+                },
+            };
+
+            assert_example_verifies::<NewAccountProverKnowledge<Fr>>(NewAccountCircuit::example());
+            assert_example_verifies::<NewAccountWithDepositProverKnowledge<Fr>>(
+                NewAccountWithDepositCircuit::example(),
+            );
+        }
+        assert_example_verifies::<DepositProverKnowledge<Fr>>(DepositCircuit::example());
+        assert_example_verifies::<DepositProverKnowledge<Fr>>(CheckedDepositCircuit::example());
+        assert_example_verifies::<WithdrawProverKnowledge<Fr>>(WithdrawCircuit::example());
+        assert_example_verifies::<MerkleProverKnowledge<NOTE_TREE_HEIGHT, Fr>>(MerkleCircuit::<
+            NOTE_TREE_HEIGHT,
+        >::example(
+        ));
+        assert_example_verifies::<MerkleWithLeafProverKnowledge<NOTE_TREE_HEIGHT>>(
+            MerkleWithLeafCircuit::<NOTE_TREE_HEIGHT>::example(),
+        );
+    }
+
+    #[test]
+    fn seeded_proving_is_reproducible() {
+        let knowledge =
+            MerkleProverKnowledge::<NOTE_TREE_HEIGHT, Fr>::random_correct_example(&mut rng());
+        let pub_input = knowledge.serialize_public_input();
+
+        let (params, _, pk, _) = generate_keys_with_min_k(
+            knowledge.create_circuit(),
+            generate_setup_params(MAX_K, &mut rng()),
+        )
+        .expect("keys should not fail to generate");
+        let prove = |rng: &mut StdRng| {
+            generate_proof(&params, &pk, knowledge.create_circuit(), &pub_input, rng)
+        };
+
+        let first = prove(&mut rng());
+        let second = prove(&mut rng());
+        let other_seed = prove(&mut StdRng::from_seed([1; 32]));
+
+        assert!(
+            proofs_equal(&first, &second),
+            "{:?}",
+            proof_diff(&first, &second)
+        );
+        assert!(!proofs_equal(&first, &other_seed));
+    }
+
+    // Exposes a single cell in two instance columns.
+    #[derive(Clone, Default)]
+    struct TwoInstanceColumnsCircuit;
+
+    impl Circuit<Fr> for TwoInstanceColumnsCircuit {
+        type Config = (Column<Advice>, [Column<Instance>; 2]);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let advice = meta.advice_column();
+            meta.enable_equality(advice);
+            let instances = [meta.instance_column(), meta.instance_column()];
+            for instance in instances {
+                meta.enable_equality(instance);
+            }
+            (advice, instances)
+        }
+
+        fn synthesize(
+            &self,
+            (advice, instances): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let cell = layouter.assign_region(
+                || "cell",
+                |mut region| region.assign_advice(|| "cell", advice, 0, || Value::known(Fr::ONE)),
+            )?;
+            for instance in instances {
+                layouter.constrain_instance(cell.cell(), instance, 0)?;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn verification_rejects_vk_with_mismatched_instance_columns() {
+        let (params, _, _, vk) = generate_keys_with_min_k(
+            TwoInstanceColumnsCircuit,
+            generate_setup_params(MAX_K, &mut rng()),
+        )
+        .expect("keys should not fail to generate");
+
+        assert_eq!(vk_instance_columns(&vk), 2);
+        assert!(matches!(
+            verify(&params, &vk, &[], &[Fr::ONE]),
+            Err(VerifyError::InstanceColumnsMismatch { actual: 2 })
+        ));
+        assert!(matches!(
+            verify_batch(&params, &vk, &[]),
+            Err(BatchVerifyError::Key(
+                VerifyError::InstanceColumnsMismatch { actual: 2 }
+            ))
+        ));
+    }
+
+    #[test]
+    fn non_canonical_public_input_is_reported() {
+        let inputs = [Fr::ONE, Fr::from(2), -Fr::ONE];
+        let mut bytes = inputs.map(|input| input.to_repr());
+        assert_eq!(validate_public_inputs(&bytes), Ok(inputs.to_vec()));
+
+        bytes[1] = [0xff; 32];
+        assert_eq!(validate_public_inputs(&bytes), Err(1));
+    }
+
+    #[test]
+    fn proof_diff_reports_first_difference() {
+        assert_eq!(proof_diff(&[1, 2, 3], &[1, 2, 3]), None);
+        assert_eq!(
+            proof_diff(&[1, 2, 3], &[1, 5, 3]).unwrap(),
+            "proofs (3 and 3 bytes) first differ at byte 1: 0x02 != 0x05"
+        );
+        assert_eq!(
+            proof_diff(&[1, 2], &[1, 2, 3]).unwrap(),
+            "proofs have different lengths (2 and 3 bytes) but a common prefix"
+        );
+    }
 }
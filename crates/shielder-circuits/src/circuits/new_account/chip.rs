@@ -15,7 +15,7 @@ use crate::{
     gates::{is_point_on_curve_affine::IsPointOnCurveAffineGate, Gate},
     instance_wrapper::InstanceWrapper,
     new_account::NewAccountInstance::{self, *},
-    poseidon::circuit::{hash, PoseidonChip},
+    poseidon::circuit::PoseidonChip,
     synthesizer::Synthesizer,
     version::NOTE_VERSION,
     AssignedCell, GrumpkinPoint,
@@ -38,36 +38,25 @@ impl NewAccountChip {
         synthesizer: &mut impl Synthesizer,
         knowledge: &NewAccountProverKnowledge<AssignedCell>,
     ) -> Result<(), Error> {
-        let note = self.note.note_hash(
-            synthesizer,
-            &Note {
-                version: NOTE_VERSION,
-                id: knowledge.id.clone(),
-                nullifier: knowledge.nullifier.clone(),
-                account_balance: knowledge.initial_deposit.clone(),
-                token_address: knowledge.token_address.clone(),
-            },
-        )?;
+        let note = Note {
+            version: NOTE_VERSION,
+            id: knowledge.id.clone(),
+            nullifier: knowledge.nullifier.clone(),
+            account_balance: knowledge.initial_deposit.clone(),
+            token_address: knowledge.token_address.clone(),
+        };
+        let hashed_note = self.note.note_hash(synthesizer, &note)?;
+        self.public_inputs
+            .constrain_cells(synthesizer, [(hashed_note, HashedNote)])?;
 
-        self.public_inputs.constrain_cells(
+        self.note.constrain_balance_equals_public(
             synthesizer,
-            [
-                (note, HashedNote),
-                (knowledge.initial_deposit.clone(), InitialDeposit),
-            ],
+            &note,
+            &self.public_inputs,
+            InitialDeposit,
         )
     }
 
-    pub fn constrain_prenullifier(
-        &self,
-        synthesizer: &mut impl Synthesizer,
-        knowledge: &NewAccountProverKnowledge<AssignedCell>,
-    ) -> Result<(), Error> {
-        let h_id = hash(synthesizer, self.poseidon.clone(), [knowledge.id.clone()])?;
-        self.public_inputs
-            .constrain_cells(synthesizer, [(h_id, Prenullifier)])
-    }
-
     /// assert that `key` is an x-coordinate of a point on the Grumpkin curve, i.e.,
     /// y^2 = key^3 - 17, for some y, if yes, outputs one such y (out of two possible)
     fn constrain_viewing_key_encodable(
@@ -92,7 +81,11 @@ impl NewAccountChip {
         knowledge: &NewAccountProverKnowledge<AssignedCell>,
     ) -> Result<(), Error> {
         let viewing_key = ViewingKeyChip::new(self.poseidon.clone())
-            .derive_viewing_key(synthesizer, knowledge.id.clone())?;
+            .derive_viewing_key_for_hashed_id(
+                synthesizer,
+                knowledge.id.clone(),
+                &self.public_inputs.narrow(),
+            )?;
 
         let y = self.constrain_viewing_key_encodable(synthesizer, viewing_key.clone())?;
 
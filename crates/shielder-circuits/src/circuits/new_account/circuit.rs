@@ -11,12 +11,19 @@ use crate::{
     instance_wrapper::InstanceWrapper,
     new_account::NewAccountInstance,
     synthesizer::create_synthesizer,
-    Fr, Value,
+    Fr, ProverKnowledge, Value,
 };
 
 #[derive(Clone, Debug, Default)]
 pub struct NewAccountCircuit(pub NewAccountProverKnowledge<Value>);
 
+impl NewAccountCircuit {
+    /// Provable counterpart of `Self::default()`, wrapping `NewAccountProverKnowledge::example()`.
+    pub fn example() -> Self {
+        NewAccountProverKnowledge::<Fr>::example().create_circuit()
+    }
+}
+
 impl Circuit<Fr> for NewAccountCircuit {
     type Config = (NewAccountChip, ColumnPool<Advice, PreSynthesisPhase>);
     type FloorPlanner = V1;
@@ -33,7 +40,7 @@ impl Circuit<Fr> for NewAccountCircuit {
             .with_is_point_on_curve_affine()
             .with_to_projective_chip()
             .with_to_affine_chip()
-            .with_el_gamal_encryption_chip();
+            .with_el_gamal_encryption_chip_checked();
 
         (
             NewAccountChip {
@@ -41,7 +48,7 @@ impl Circuit<Fr> for NewAccountCircuit {
                 poseidon: configs_builder.poseidon_chip(),
                 note: configs_builder.note_chip(),
                 is_point_on_curve: configs_builder.is_point_on_curve_affine_gate(),
-                el_gamal_encryption: configs_builder.el_gamal_encryption_chip(),
+                el_gamal_encryption: configs_builder.el_gamal_encryption_chip_checked(),
                 to_projective: configs_builder.to_projective_chip(),
                 to_affine: configs_builder.to_affine_chip(),
             },
@@ -63,7 +70,8 @@ impl Circuit<Fr> for NewAccountCircuit {
         main_chip.check_note(&mut synthesizer, &knowledge)?;
         // Instead of a regular nullifier we emit here the hashed id. Think of it as the "public key".
         // Since it is deterministic it can be used as a nullifier to prevent creating a second account with the same id.
-        main_chip.constrain_prenullifier(&mut synthesizer, &knowledge)?;
+        // It is constrained together with the derivation of the viewing key, so that the encrypted
+        // key is bound to the same id.
         main_chip.constrain_encrypting_viewing_key(&mut synthesizer, &knowledge)?;
         main_chip.check_mac(&mut synthesizer, &knowledge)?;
         main_chip.check_commitment(&mut synthesizer, &knowledge)
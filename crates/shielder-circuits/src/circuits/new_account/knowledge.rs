@@ -5,13 +5,16 @@ use rand_core::RngCore;
 use crate::{
     chips::{
         el_gamal::{self},
+        nullifier::off_circuit::nullifier_hash,
         viewing_key,
     },
     consts::FIELD_BITS,
     curve_arithmetic::{self, GrumpkinPointAffine},
     embed::Embed,
     field_element_to_le_bits, le_bits_to_field_element,
-    new_account::{circuit::NewAccountCircuit, NewAccountInstance},
+    new_account::{
+        circuit::NewAccountCircuit, off_circuit::encode_viewing_key_as_point, NewAccountInstance,
+    },
     note_hash,
     poseidon::off_circuit::hash,
     version::NOTE_VERSION,
@@ -53,6 +56,10 @@ impl ProverKnowledge for NewAccountProverKnowledge<Fr> {
     type Circuit = NewAccountCircuit;
     type PublicInput = NewAccountInstance;
 
+    fn circuit_name() -> &'static str {
+        "new_account"
+    }
+
     fn random_correct_example(rng: &mut impl RngCore) -> Self {
         Self {
             id: curve_arithmetic::generate_user_id(Fr::random(&mut *rng).to_bytes()),
@@ -86,14 +93,13 @@ impl ProverKnowledge for NewAccountProverKnowledge<Fr> {
 impl PublicInputProvider<NewAccountInstance> for NewAccountProverKnowledge<Fr> {
     fn compute_public_input(&self, instance_id: NewAccountInstance) -> Fr {
         let viewing_key = viewing_key::off_circuit::derive_viewing_key(self.id);
-        let y = curve_arithmetic::quadratic_residue_given_x_affine(viewing_key)
-            .sqrt()
-            .expect("element has a square root");
+        let encoded_viewing_key =
+            encode_viewing_key_as_point(viewing_key).expect("element has a square root");
 
         let salt: grumpkin::Fr = le_bits_to_field_element(&self.encryption_salt);
 
         let (c1, c2) = el_gamal::off_circuit::encrypt(
-            GrumpkinPointAffine::new(viewing_key, y).into(),
+            encoded_viewing_key.into(),
             self.anonymity_revoker_public_key.into(),
             salt,
         );
@@ -109,7 +115,7 @@ impl PublicInputProvider<NewAccountInstance> for NewAccountProverKnowledge<Fr> {
                 account_balance: self.initial_deposit,
                 token_address: self.token_address,
             }),
-            NewAccountInstance::Prenullifier => hash(&[self.id]),
+            NewAccountInstance::Prenullifier => nullifier_hash(self.id),
             NewAccountInstance::InitialDeposit => self.initial_deposit,
             NewAccountInstance::Commitment => self.commitment,
             NewAccountInstance::TokenAddress => self.token_address,
@@ -1,13 +1,21 @@
+use core::fmt::{self, Display, Formatter};
+
+use strum::EnumCount as _;
 use strum_macros::{EnumCount, EnumIter};
 
 mod chip;
 mod circuit;
 mod knowledge;
 
+pub use chip::NewAccountChip;
 pub use circuit::NewAccountCircuit;
 pub use knowledge::NewAccountProverKnowledge;
 
-use crate::chips::{mac::MacInstance, note::NoteInstance};
+use crate::{
+    chips::{mac::MacInstance, note::NoteInstance, viewing_key::ViewingKeyInstance},
+    curve_arithmetic::{is_point_on_curve_affine, GrumpkinPointAffine},
+    Fr,
+};
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, EnumIter, EnumCount)]
 pub enum NewAccountInstance {
@@ -49,18 +57,175 @@ impl TryFrom<NewAccountInstance> for MacInstance {
     }
 }
 
+impl TryFrom<NewAccountInstance> for ViewingKeyInstance {
+    type Error = ();
+
+    fn try_from(value: NewAccountInstance) -> Result<Self, Self::Error> {
+        match value {
+            NewAccountInstance::Prenullifier => Ok(Self::HashedId),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CiphertextError {
+    WrongInputLength,
+    NotOnCurve,
+}
+
+impl Display for CiphertextError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CiphertextError::WrongInputLength => {
+                write!(f, "Public input length doesn't match `NewAccountInstance`.")
+            }
+            CiphertextError::NotOnCurve => write!(f, "Ciphertext is not a point on the curve."),
+        }
+    }
+}
+
+/// Extracts both parts of the encrypted viewing key from the public input of `NewAccountCircuit`
+/// and checks that they are points on the Grumpkin curve. Meant for the anonymity revoker, to
+/// validate the ciphertext before attempting to decrypt it.
+pub fn verify_ciphertext_public_inputs(
+    inputs: &[Fr],
+) -> Result<(GrumpkinPointAffine<Fr>, GrumpkinPointAffine<Fr>), CiphertextError> {
+    if inputs.len() != NewAccountInstance::COUNT {
+        return Err(CiphertextError::WrongInputLength);
+    }
+    let input = |instance: NewAccountInstance| inputs[instance as usize];
+
+    let ciphertext1 = GrumpkinPointAffine::new(
+        input(NewAccountInstance::EncryptedKeyCiphertext1X),
+        input(NewAccountInstance::EncryptedKeyCiphertext1Y),
+    );
+    let ciphertext2 = GrumpkinPointAffine::new(
+        input(NewAccountInstance::EncryptedKeyCiphertext2X),
+        input(NewAccountInstance::EncryptedKeyCiphertext2Y),
+    );
+
+    if !is_point_on_curve_affine(ciphertext1) || !is_point_on_curve_affine(ciphertext2) {
+        return Err(CiphertextError::NotOnCurve);
+    }
+    Ok((ciphertext1, ciphertext2))
+}
+
+pub mod off_circuit {
+    use halo2_proofs::halo2curves::grumpkin;
+
+    use crate::{
+        chips::{
+            el_gamal, nullifier::off_circuit::nullifier_hash,
+            viewing_key::off_circuit::derive_viewing_key,
+        },
+        curve_arithmetic::{quadratic_residue_given_x_affine, GrumpkinPointAffine},
+        le_bits_to_field_element,
+        new_account::NewAccountProverKnowledge,
+        note_hash,
+        poseidon::off_circuit::hash,
+        version::NOTE_VERSION,
+        Field, Fr, Note,
+    };
+
+    /// All the values derived by `NewAccountCircuit` from `NewAccountProverKnowledge`.
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    pub struct NewAccountTrace {
+        pub hashed_note: Fr,
+        pub prenullifier: Fr,
+        pub viewing_key: Fr,
+        /// `viewing_key` encoded as a point (see `encode_viewing_key_as_point`).
+        pub encoded_viewing_key: GrumpkinPointAffine<Fr>,
+        pub ciphertext1: GrumpkinPointAffine<Fr>,
+        pub ciphertext2: GrumpkinPointAffine<Fr>,
+        pub mac_commitment: Fr,
+    }
+
+    /// Computes the whole new-account relation off-circuit. Meant for debugging: the result can be
+    /// compared field by field with the public input expected by a failing proof.
+    ///
+    /// Panics if the viewing key derived from `knowledge.id` cannot be encoded as a point (the
+    /// circuit is unsatisfiable in that case).
+    pub fn simulate(knowledge: &NewAccountProverKnowledge<Fr>) -> NewAccountTrace {
+        let viewing_key = derive_viewing_key(knowledge.id);
+        let encoded_viewing_key =
+            encode_viewing_key_as_point(viewing_key).expect("element has a square root");
+
+        let salt: grumpkin::Fr = le_bits_to_field_element(&knowledge.encryption_salt);
+        let (ciphertext1, ciphertext2) = el_gamal::off_circuit::encrypt(
+            encoded_viewing_key.into(),
+            knowledge.anonymity_revoker_public_key.into(),
+            salt,
+        );
+
+        NewAccountTrace {
+            hashed_note: note_hash(&Note {
+                version: NOTE_VERSION,
+                id: knowledge.id,
+                nullifier: knowledge.nullifier,
+                account_balance: knowledge.initial_deposit,
+                token_address: knowledge.token_address,
+            }),
+            prenullifier: nullifier_hash(knowledge.id),
+            viewing_key,
+            encoded_viewing_key,
+            ciphertext1: ciphertext1.into(),
+            ciphertext2: ciphertext2.into(),
+            mac_commitment: hash(&[knowledge.mac_salt, viewing_key]),
+        }
+    }
+
+    /// Simulates the encryption performed by `NewAccountCircuit` for `knowledge`, decrypts the
+    /// ciphertexts with the anonymity revoker's `private_key` and checks whether the recovered
+    /// x-coordinate is the viewing key derived from `knowledge.id`.
+    #[cfg(test)]
+    pub fn is_decryptable(
+        knowledge: &NewAccountProverKnowledge<Fr>,
+        private_key: grumpkin::Fr,
+    ) -> bool {
+        let trace = simulate(knowledge);
+        let recovered = el_gamal::off_circuit::recover_viewing_key(
+            trace.ciphertext1,
+            trace.ciphertext2,
+            private_key,
+        );
+
+        recovered == derive_viewing_key(knowledge.id)
+    }
+
+    /// Encodes `viewing_key` as the point `(viewing_key, y)`, where `y = sqrt(viewing_key^3 + b)`,
+    /// exactly like `NewAccountCircuit` does before encrypting the key. Returns `None` if
+    /// `viewing_key` is not an x-coordinate of any point on the Grumpkin curve.
+    pub fn encode_viewing_key_as_point(viewing_key: Fr) -> Option<GrumpkinPointAffine<Fr>> {
+        let y: Option<Fr> = quadratic_residue_given_x_affine(viewing_key).sqrt().into();
+        y.map(|y| GrumpkinPointAffine::new(viewing_key, y))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::{vec, vec::Vec};
-
-    use strum::IntoEnumIterator;
+    use halo2_proofs::halo2curves::grumpkin;
+    use rand_core::OsRng;
 
-    use super::{NewAccountInstance, NewAccountInstance::*};
+    use super::{
+        off_circuit::{encode_viewing_key_as_point, is_decryptable, simulate},
+        verify_ciphertext_public_inputs, CiphertextError,
+        NewAccountInstance::*,
+    };
+    use crate::{
+        chips::{
+            el_gamal::off_circuit::generate_keys, viewing_key::off_circuit::derive_viewing_key,
+        },
+        circuits::assert_instance_order,
+        curve_arithmetic::{is_point_on_curve_affine, quadratic_residue_given_x_affine},
+        field_element_to_le_bits,
+        new_account::NewAccountProverKnowledge,
+        Field, Fr, ProverKnowledge, PublicInputProvider,
+    };
 
     #[test]
     fn instance_order() {
-        // This is the order used in other parts of the codebase (e.g., in contracts).
-        let expected_order = vec![
+        assert_instance_order(&[
             HashedNote,
             Prenullifier,
             InitialDeposit,
@@ -74,10 +239,124 @@ mod tests {
             EncryptedKeyCiphertext2Y,
             MacSalt,
             MacCommitment,
-        ];
+        ]);
+    }
+
+    #[test]
+    fn encoded_viewing_key_is_on_curve() {
+        let pk = NewAccountProverKnowledge::random_correct_example(&mut OsRng);
+        let viewing_key = derive_viewing_key(pk.id);
+
+        let point = encode_viewing_key_as_point(viewing_key).expect("viewing key is encodable");
+
+        assert_eq!(point.x, viewing_key);
+        assert!(is_point_on_curve_affine(point));
+    }
+
+    #[test]
+    fn non_encodable_viewing_key_is_rejected() {
+        let x = (0u64..)
+            .map(Fr::from)
+            .find(|x| bool::from(quadratic_residue_given_x_affine(*x).sqrt().is_none()))
+            .expect("some x is not an x-coordinate");
+
+        assert!(encode_viewing_key_as_point(x).is_none());
+    }
+
+    #[test]
+    fn ciphertext_public_inputs_of_correct_example_are_valid() {
+        let pk = NewAccountProverKnowledge::random_correct_example(&mut OsRng);
+        let pub_input = pk.serialize_public_input();
+
+        let (ciphertext1, ciphertext2) =
+            verify_ciphertext_public_inputs(&pub_input).expect("ciphertext should be valid");
+
         assert_eq!(
-            expected_order,
-            NewAccountInstance::iter().collect::<Vec<_>>()
+            ciphertext1.x,
+            pk.compute_public_input(EncryptedKeyCiphertext1X)
         );
+        assert_eq!(
+            ciphertext1.y,
+            pk.compute_public_input(EncryptedKeyCiphertext1Y)
+        );
+        assert_eq!(
+            ciphertext2.x,
+            pk.compute_public_input(EncryptedKeyCiphertext2X)
+        );
+        assert_eq!(
+            ciphertext2.y,
+            pk.compute_public_input(EncryptedKeyCiphertext2Y)
+        );
+    }
+
+    #[test]
+    fn ciphertext_off_curve_is_rejected() {
+        let pk = NewAccountProverKnowledge::random_correct_example(&mut OsRng);
+        let mut pub_input = pk.serialize_public_input();
+        pub_input[EncryptedKeyCiphertext2Y as usize] += Fr::ONE;
+
+        assert_eq!(
+            verify_ciphertext_public_inputs(&pub_input),
+            Err(CiphertextError::NotOnCurve)
+        );
+    }
+
+    #[test]
+    fn truncated_public_input_is_rejected() {
+        let pk = NewAccountProverKnowledge::random_correct_example(&mut OsRng);
+        let pub_input = pk.serialize_public_input();
+
+        assert_eq!(
+            verify_ciphertext_public_inputs(&pub_input[..pub_input.len() - 1]),
+            Err(CiphertextError::WrongInputLength)
+        );
+    }
+
+    #[test]
+    fn simulated_trace_matches_public_input() {
+        let pk = NewAccountProverKnowledge::random_correct_example(&mut OsRng);
+        let trace = simulate(&pk);
+
+        assert_eq!(trace.encoded_viewing_key.x, trace.viewing_key);
+        assert_eq!(
+            pk.serialize_public_input(),
+            [
+                trace.hashed_note,
+                trace.prenullifier,
+                pk.initial_deposit,
+                pk.commitment,
+                pk.token_address,
+                pk.anonymity_revoker_public_key.x,
+                pk.anonymity_revoker_public_key.y,
+                trace.ciphertext1.x,
+                trace.ciphertext1.y,
+                trace.ciphertext2.x,
+                trace.ciphertext2.y,
+                pk.mac_salt,
+                trace.mac_commitment,
+            ]
+        );
+    }
+
+    fn knowledge_for_revoker() -> (NewAccountProverKnowledge<Fr>, grumpkin::Fr) {
+        let (private_key, public_key) = generate_keys(&mut OsRng);
+        let knowledge = NewAccountProverKnowledge {
+            anonymity_revoker_public_key: public_key.into(),
+            encryption_salt: field_element_to_le_bits(grumpkin::Fr::random(OsRng)),
+            ..NewAccountProverKnowledge::random_correct_example(&mut OsRng)
+        };
+        (knowledge, private_key)
+    }
+
+    #[test]
+    fn revoker_can_decrypt_viewing_key() {
+        let (knowledge, private_key) = knowledge_for_revoker();
+        assert!(is_decryptable(&knowledge, private_key));
+    }
+
+    #[test]
+    fn decryption_with_another_key_fails() {
+        let (knowledge, private_key) = knowledge_for_revoker();
+        assert!(!is_decryptable(&knowledge, private_key + grumpkin::Fr::ONE));
     }
 }
@@ -0,0 +1,73 @@
+use halo2_proofs::plonk::Error;
+
+use crate::{
+    chips::note::{Note, NoteChip},
+    circuits::new_account_with_deposit::knowledge::NewAccountWithDepositProverKnowledge,
+    instance_wrapper::InstanceWrapper,
+    new_account::NewAccountChip,
+    new_account_with_deposit::NewAccountWithDepositInstance::{
+        self, DepositValue, HashedFundedNote,
+    },
+    synthesizer::Synthesizer,
+    version::NOTE_VERSION,
+    AssignedCell,
+};
+
+#[derive(Clone, Debug)]
+pub struct NewAccountWithDepositChip {
+    pub public_inputs: InstanceWrapper<NewAccountWithDepositInstance>,
+    pub new_account: NewAccountChip,
+    pub note: NoteChip,
+}
+
+impl NewAccountWithDepositChip {
+    /// Runs all the checks of `NewAccountCircuit` on the embedded new account knowledge.
+    pub fn check_new_account(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        knowledge: &NewAccountWithDepositProverKnowledge<AssignedCell>,
+    ) -> Result<(), Error> {
+        let new_account = &knowledge.new_account;
+
+        self.new_account.check_note(synthesizer, new_account)?;
+        self.new_account
+            .constrain_encrypting_viewing_key(synthesizer, new_account)?;
+        self.new_account.check_mac(synthesizer, new_account)?;
+        self.new_account.check_commitment(synthesizer, new_account)
+    }
+
+    /// Increases the initial balance by the deposited value and constrains the hash of the
+    /// resulting note (with the same `id` and `nullifier`) to the `HashedFundedNote` public input.
+    pub fn check_funded_note(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        knowledge: &NewAccountWithDepositProverKnowledge<AssignedCell>,
+    ) -> Result<(), Error> {
+        let new_account = &knowledge.new_account;
+
+        self.public_inputs.constrain_cells(
+            synthesizer,
+            [(knowledge.deposit_value.clone(), DepositValue)],
+        )?;
+
+        let funded_balance = self.note.increase_balance(
+            synthesizer,
+            new_account.initial_deposit.clone(),
+            knowledge.deposit_value.clone(),
+        )?;
+
+        let funded_note = self.note.note_hash(
+            synthesizer,
+            &Note {
+                version: NOTE_VERSION,
+                id: new_account.id.clone(),
+                nullifier: new_account.nullifier.clone(),
+                account_balance: funded_balance,
+                token_address: new_account.token_address.clone(),
+            },
+        )?;
+
+        self.public_inputs
+            .constrain_cells(synthesizer, [(funded_note, HashedFundedNote)])
+    }
+}
@@ -0,0 +1,151 @@
+use halo2_proofs::{
+    circuit::{floor_planner::V1, Layouter},
+    plonk::{Advice, Circuit, ConstraintSystem, Error},
+};
+
+use crate::{
+    circuits::new_account_with_deposit::{
+        chip::NewAccountWithDepositChip, knowledge::NewAccountWithDepositProverKnowledge,
+    },
+    column_pool::{ColumnPool, PreSynthesisPhase},
+    config_builder::ConfigsBuilder,
+    embed::Embed,
+    instance_wrapper::InstanceWrapper,
+    new_account::NewAccountChip,
+    new_account_with_deposit::NewAccountWithDepositInstance,
+    synthesizer::create_synthesizer,
+    Fr, ProverKnowledge, Value,
+};
+
+/// Creates a new account and funds it with a deposit in a single proof.
+#[derive(Clone, Debug, Default)]
+pub struct NewAccountWithDepositCircuit(pub NewAccountWithDepositProverKnowledge<Value>);
+
+impl NewAccountWithDepositCircuit {
+    /// Provable counterpart of `Self::default()`, wrapping
+    /// `NewAccountWithDepositProverKnowledge::example()`.
+    pub fn example() -> Self {
+        NewAccountWithDepositProverKnowledge::<Fr>::example().create_circuit()
+    }
+}
+
+impl Circuit<Fr> for NewAccountWithDepositCircuit {
+    type Config = (
+        NewAccountWithDepositChip,
+        ColumnPool<Advice, PreSynthesisPhase>,
+    );
+    type FloorPlanner = V1;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+        let public_inputs = InstanceWrapper::<NewAccountWithDepositInstance>::new(meta);
+        let configs_builder = ConfigsBuilder::new(meta)
+            .with_poseidon()
+            .with_note(public_inputs.narrow())
+            .with_is_point_on_curve_affine()
+            .with_to_projective_chip()
+            .with_to_affine_chip()
+            .with_el_gamal_encryption_chip_checked();
+
+        (
+            NewAccountWithDepositChip {
+                new_account: NewAccountChip {
+                    public_inputs: public_inputs.narrow(),
+                    poseidon: configs_builder.poseidon_chip(),
+                    note: configs_builder.note_chip(),
+                    is_point_on_curve: configs_builder.is_point_on_curve_affine_gate(),
+                    el_gamal_encryption: configs_builder.el_gamal_encryption_chip_checked(),
+                    to_projective: configs_builder.to_projective_chip(),
+                    to_affine: configs_builder.to_affine_chip(),
+                },
+                note: configs_builder.note_chip(),
+                public_inputs,
+            },
+            configs_builder.finish(),
+        )
+    }
+
+    fn synthesize(
+        &self,
+        (main_chip, column_pool): Self::Config,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<(), Error> {
+        let pool = column_pool.start_synthesis();
+        let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+        let knowledge = self
+            .0
+            .embed(&mut synthesizer, "NewAccountWithDepositProverKnowledge")?;
+
+        main_chip.check_new_account(&mut synthesizer, &knowledge)?;
+        main_chip.check_funded_note(&mut synthesizer, &knowledge)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::{arithmetic::Field, halo2curves::bn256::Fr};
+    use rand_core::OsRng;
+
+    use crate::{
+        circuits::{
+            new_account_with_deposit::knowledge::NewAccountWithDepositProverKnowledge,
+            test_utils::{
+                expect_prover_success_and_run_verification, run_full_pipeline,
+                PublicInputProviderExt,
+            },
+        },
+        new_account_with_deposit::NewAccountWithDepositInstance::*,
+        note_hash,
+        version::NOTE_VERSION,
+        Note, ProverKnowledge, PublicInputProvider,
+    };
+
+    #[test]
+    fn passes_if_inputs_correct() {
+        run_full_pipeline::<NewAccountWithDepositProverKnowledge<Fr>>();
+    }
+
+    #[test]
+    fn funded_balance_is_initial_deposit_plus_deposit_value() {
+        let mut pk = NewAccountWithDepositProverKnowledge::random_correct_example(&mut OsRng);
+        pk.new_account.initial_deposit = Fr::from(5);
+        pk.deposit_value = Fr::from(7);
+        let pub_input = pk.serialize_public_input();
+
+        assert!(
+            expect_prover_success_and_run_verification(pk.create_circuit(), &pub_input).is_ok()
+        );
+
+        let funded_note = note_hash(&Note {
+            version: NOTE_VERSION,
+            id: pk.new_account.id,
+            nullifier: pk.new_account.nullifier,
+            account_balance: Fr::from(12),
+            token_address: pk.new_account.token_address,
+        });
+        assert_eq!(funded_note, pub_input[HashedFundedNote as usize]);
+    }
+
+    #[test]
+    fn fails_if_incorrect_funded_note_is_published() {
+        let pk = NewAccountWithDepositProverKnowledge::random_correct_example(&mut OsRng);
+        let pub_input = pk.with_substitution(HashedFundedNote, |v| v + Fr::ONE);
+
+        assert!(
+            expect_prover_success_and_run_verification(pk.create_circuit(), &pub_input).is_err()
+        );
+    }
+
+    #[test]
+    fn fails_if_incorrect_deposit_value_is_published() {
+        let pk = NewAccountWithDepositProverKnowledge::random_correct_example(&mut OsRng);
+        let pub_input = pk.with_substitution(DepositValue, |v| v + Fr::ONE);
+
+        assert!(
+            expect_prover_success_and_run_verification(pk.create_circuit(), &pub_input).is_err()
+        );
+    }
+}
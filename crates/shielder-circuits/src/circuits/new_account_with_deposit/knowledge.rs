@@ -0,0 +1,80 @@
+use macros::embeddable;
+use rand_core::RngCore;
+
+use crate::{
+    embed::Embed,
+    new_account::{NewAccountInstance, NewAccountProverKnowledge},
+    new_account_with_deposit::{
+        circuit::NewAccountWithDepositCircuit, NewAccountWithDepositInstance,
+    },
+    note_hash,
+    version::NOTE_VERSION,
+    Fr, Note, ProverKnowledge, PublicInputProvider, Value,
+};
+
+/// Stores values needed to compute example inputs for `NewAccountWithDepositCircuit`: the
+/// knowledge of a new account together with the value deposited into it.
+#[derive(Clone, Debug)]
+#[embeddable(
+    receiver = "NewAccountWithDepositProverKnowledge<Value>",
+    embedded = "NewAccountWithDepositProverKnowledge<crate::AssignedCell>"
+)]
+pub struct NewAccountWithDepositProverKnowledge<T> {
+    pub new_account: NewAccountProverKnowledge<T>,
+    pub deposit_value: T,
+}
+
+impl<T: Default + Copy> Default for NewAccountWithDepositProverKnowledge<T> {
+    fn default() -> Self {
+        Self {
+            new_account: NewAccountProverKnowledge::default(),
+            deposit_value: T::default(),
+        }
+    }
+}
+
+impl ProverKnowledge for NewAccountWithDepositProverKnowledge<Fr> {
+    type Circuit = NewAccountWithDepositCircuit;
+    type PublicInput = NewAccountWithDepositInstance;
+
+    fn circuit_name() -> &'static str {
+        "new_account_with_deposit"
+    }
+
+    fn random_correct_example(rng: &mut impl RngCore) -> Self {
+        Self {
+            new_account: NewAccountProverKnowledge::random_correct_example(rng),
+            deposit_value: Fr::from(2),
+        }
+    }
+
+    fn create_circuit(&self) -> Self::Circuit {
+        NewAccountWithDepositCircuit(NewAccountWithDepositProverKnowledge {
+            new_account: self.new_account.create_circuit().0,
+            deposit_value: Value::known(self.deposit_value),
+        })
+    }
+}
+
+impl PublicInputProvider<NewAccountWithDepositInstance>
+    for NewAccountWithDepositProverKnowledge<Fr>
+{
+    fn compute_public_input(&self, instance_id: NewAccountWithDepositInstance) -> Fr {
+        match instance_id {
+            NewAccountWithDepositInstance::DepositValue => self.deposit_value,
+            NewAccountWithDepositInstance::HashedFundedNote => note_hash(&Note {
+                version: NOTE_VERSION,
+                id: self.new_account.id,
+                nullifier: self.new_account.nullifier,
+                account_balance: self.new_account.initial_deposit + self.deposit_value,
+                token_address: self.new_account.token_address,
+            }),
+            other => {
+                let instance: NewAccountInstance = other
+                    .try_into()
+                    .expect("remaining instances are shared with `NewAccountInstance`");
+                self.new_account.compute_public_input(instance)
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use strum_macros::{EnumCount, EnumIter};
+
+mod chip;
+mod circuit;
+mod knowledge;
+
+pub use circuit::NewAccountWithDepositCircuit;
+pub use knowledge::NewAccountWithDepositProverKnowledge;
+
+use crate::{chips::note::NoteInstance, new_account::NewAccountInstance};
+
+/// Public inputs of `NewAccountWithDepositCircuit`: those of `NewAccountCircuit` followed by the
+/// deposited value and the hash of the funded note.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, EnumIter, EnumCount)]
+pub enum NewAccountWithDepositInstance {
+    HashedNote,
+    Prenullifier,
+    InitialDeposit,
+    Commitment,
+    TokenAddress,
+    AnonymityRevokerPublicKeyX,
+    AnonymityRevokerPublicKeyY,
+    EncryptedKeyCiphertext1X,
+    EncryptedKeyCiphertext1Y,
+    EncryptedKeyCiphertext2X,
+    EncryptedKeyCiphertext2Y,
+    MacSalt,
+    MacCommitment,
+    DepositValue,
+    HashedFundedNote,
+}
+
+impl TryFrom<NewAccountWithDepositInstance> for NewAccountInstance {
+    type Error = ();
+
+    fn try_from(value: NewAccountWithDepositInstance) -> Result<Self, Self::Error> {
+        use NewAccountWithDepositInstance as Instance;
+
+        match value {
+            Instance::HashedNote => Ok(Self::HashedNote),
+            Instance::Prenullifier => Ok(Self::Prenullifier),
+            Instance::InitialDeposit => Ok(Self::InitialDeposit),
+            Instance::Commitment => Ok(Self::Commitment),
+            Instance::TokenAddress => Ok(Self::TokenAddress),
+            Instance::AnonymityRevokerPublicKeyX => Ok(Self::AnonymityRevokerPublicKeyX),
+            Instance::AnonymityRevokerPublicKeyY => Ok(Self::AnonymityRevokerPublicKeyY),
+            Instance::EncryptedKeyCiphertext1X => Ok(Self::EncryptedKeyCiphertext1X),
+            Instance::EncryptedKeyCiphertext1Y => Ok(Self::EncryptedKeyCiphertext1Y),
+            Instance::EncryptedKeyCiphertext2X => Ok(Self::EncryptedKeyCiphertext2X),
+            Instance::EncryptedKeyCiphertext2Y => Ok(Self::EncryptedKeyCiphertext2Y),
+            Instance::MacSalt => Ok(Self::MacSalt),
+            Instance::MacCommitment => Ok(Self::MacCommitment),
+            Instance::DepositValue | Instance::HashedFundedNote => Err(()),
+        }
+    }
+}
+
+impl TryFrom<NewAccountWithDepositInstance> for NoteInstance {
+    type Error = ();
+
+    fn try_from(value: NewAccountWithDepositInstance) -> Result<Self, Self::Error> {
+        match value {
+            NewAccountWithDepositInstance::TokenAddress => Ok(NoteInstance::TokenAddress),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NewAccountWithDepositInstance::*;
+    use crate::circuits::assert_instance_order;
+
+    #[test]
+    fn instance_order() {
+        assert_instance_order(&[
+            HashedNote,
+            Prenullifier,
+            InitialDeposit,
+            Commitment,
+            TokenAddress,
+            AnonymityRevokerPublicKeyX,
+            AnonymityRevokerPublicKeyY,
+            EncryptedKeyCiphertext1X,
+            EncryptedKeyCiphertext1Y,
+            EncryptedKeyCiphertext2X,
+            EncryptedKeyCiphertext2Y,
+            MacSalt,
+            MacCommitment,
+            DepositValue,
+            HashedFundedNote,
+        ]);
+    }
+}
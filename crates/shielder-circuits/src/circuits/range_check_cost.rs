@@ -0,0 +1,92 @@
+use halo2_proofs::{
+    circuit::Layouter,
+    plonk::{Advice, Circuit, ConstraintSystem, Error},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{
+    chips::range_check::RangeCheckChip,
+    circuits::{generate_keys_with_min_k, generate_setup_params, Params},
+    column_pool::{ColumnPool, PreSynthesisPhase},
+    config_builder::ConfigsBuilder,
+    consts::{MAX_K, RANGE_PROOF_NUM_WORDS},
+    embed::Embed,
+    synthesizer::create_synthesizer,
+    Fr, ProverKnowledge, Value,
+};
+
+/// Difference between the minimal `k` of the circuit of `PK` extended with a single range check
+/// (of `RANGE_PROOF_NUM_WORDS` words) and the minimal `k` of the circuit itself. Quantifies the cost
+/// of enforcing bounds in-circuit before actually adding them to the circuit.
+///
+/// The range check is configured on its own advice columns, so the estimate is only about the rows
+/// (most notably, the lookup table), not about the width of the circuit. Runs key generation twice,
+/// so it is meant for planning rather than for production use.
+pub fn k_delta_with_range_check<PK: ProverKnowledge>() -> i32 {
+    let params = generate_setup_params(MAX_K, &mut StdRng::from_seed([0; 32]));
+
+    let base = min_k(WithRangeCheck::<PK::Circuit, false>::default(), &params);
+    let extended = min_k(WithRangeCheck::<PK::Circuit, true>::default(), &params);
+    extended as i32 - base as i32
+}
+
+fn min_k(circuit: impl Circuit<Fr>, params: &Params) -> u32 {
+    generate_keys_with_min_k(circuit, params.clone())
+        .expect("keys should not fail to generate")
+        .1
+}
+
+/// Circuit `C`, followed by a range check of a single value if `RANGE_CHECKED` is set.
+#[derive(Clone, Debug, Default)]
+struct WithRangeCheck<C, const RANGE_CHECKED: bool>(C);
+
+impl<C: Circuit<Fr>, const RANGE_CHECKED: bool> Circuit<Fr> for WithRangeCheck<C, RANGE_CHECKED> {
+    type Config = (
+        C::Config,
+        Option<(RangeCheckChip, ColumnPool<Advice, PreSynthesisPhase>)>,
+    );
+    type FloorPlanner = C::FloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self(self.0.without_witnesses())
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+        let inner = C::configure(meta);
+        if !RANGE_CHECKED {
+            return (inner, None);
+        }
+
+        let configs_builder = ConfigsBuilder::new(meta).with_range_check();
+        let range_check = configs_builder.range_check_chip();
+        (inner, Some((range_check, configs_builder.finish())))
+    }
+
+    fn synthesize(
+        &self,
+        (inner, range_check): Self::Config,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<(), Error> {
+        self.0
+            .synthesize(inner, layouter.namespace(|| "Inner circuit"))?;
+
+        let Some((range_check, column_pool)) = range_check else {
+            return Ok(());
+        };
+        let pool = column_pool.start_synthesis();
+        let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+        let value = Value::unknown().embed(&mut synthesizer, "range checked value")?;
+        range_check.constrain_value::<RANGE_PROOF_NUM_WORDS>(&mut synthesizer, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::k_delta_with_range_check;
+    use crate::{deposit::DepositProverKnowledge, Fr};
+
+    #[test]
+    fn adding_range_check_to_deposit_does_not_shrink_it() {
+        assert!(k_delta_with_range_check::<DepositProverKnowledge<Fr>>() >= 0);
+    }
+}
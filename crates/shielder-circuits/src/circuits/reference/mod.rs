@@ -0,0 +1,42 @@
+//! Plain Rust models of the relations proven by the circuits.
+//!
+//! Each model decides, for a given witness and public input, whether the corresponding circuit
+//! should be satisfied. They are deliberately written without any circuit machinery, so that they
+//! can serve as an independent cross-check of the circuits (e.g., in fuzz tests).
+
+use halo2_proofs::halo2curves::ff::PrimeFieldBits;
+
+use crate::{
+    consts::{merkle_constants::ARITY, RANGE_PROOF_CHUNK_SIZE},
+    poseidon::off_circuit::hash,
+    Fr,
+};
+
+pub mod withdraw;
+
+/// Returns `true` iff every level of `path` contains the hash of the previous level (the first one
+/// must contain `leaf`) and the hash of the last level is `root`.
+pub fn merkle_path_is_valid<const TREE_HEIGHT: usize>(
+    leaf: Fr,
+    path: &[[Fr; ARITY]; TREE_HEIGHT],
+    root: Fr,
+) -> bool {
+    let mut current = leaf;
+    for level in path {
+        if !level.contains(&current) {
+            return false;
+        }
+        current = hash(level);
+    }
+    current == root
+}
+
+/// Returns `true` iff `value` passes `RangeCheckChip::constrain_value::<CHUNKS>`, i.e., it is less
+/// than `2^(RANGE_PROOF_CHUNK_SIZE * CHUNKS)`.
+pub fn passes_range_check<const CHUNKS: usize>(value: Fr) -> bool {
+    value
+        .to_le_bits()
+        .into_iter()
+        .skip(RANGE_PROOF_CHUNK_SIZE * CHUNKS)
+        .all(|bit| !bit)
+}
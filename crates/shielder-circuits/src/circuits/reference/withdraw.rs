@@ -0,0 +1,186 @@
+use strum::EnumCount;
+
+use crate::{
+    chips::{
+        commitment::off_circuit::commitment, nullifier::off_circuit::nullifier_hash,
+        viewing_key::off_circuit::derive_viewing_key,
+    },
+    circuits::reference::{merkle_path_is_valid, passes_range_check},
+    consts::{RANGE_PROOF_NUM_WORDS, TOKEN_ADDRESS_RANGE_PROOF_NUM_WORDS},
+    note_hash,
+    poseidon::off_circuit::hash,
+    version::NOTE_VERSION,
+    withdraw::{WithdrawInstance, WithdrawProverKnowledge},
+    Fr, Note,
+};
+
+/// Returns `true` iff `WithdrawCircuit` is satisfied by `knowledge` and `public_input`.
+pub fn accepts(knowledge: &WithdrawProverKnowledge<Fr>, public_input: &[Fr]) -> bool {
+    if public_input.len() != WithdrawInstance::COUNT {
+        return false;
+    }
+    let public = |instance: WithdrawInstance| public_input[instance as usize];
+
+    let old_note = note_hash(&Note {
+        version: NOTE_VERSION,
+        id: knowledge.id,
+        nullifier: knowledge.nullifier_old,
+        account_balance: knowledge.account_old_balance,
+        token_address: knowledge.token_address,
+    });
+    let new_balance = knowledge.account_old_balance - knowledge.withdrawal_value;
+    let new_note = note_hash(&Note {
+        version: NOTE_VERSION,
+        id: knowledge.id,
+        nullifier: knowledge.nullifier_new,
+        account_balance: new_balance,
+        token_address: knowledge.token_address,
+    });
+    let viewing_key = derive_viewing_key(knowledge.id);
+
+    merkle_path_is_valid(
+        old_note,
+        &knowledge.path,
+        public(WithdrawInstance::MerkleRoot),
+    ) && nullifier_hash(knowledge.nullifier_old) == public(WithdrawInstance::HashedOldNullifier)
+        && knowledge.nullifier_old != knowledge.nullifier_new
+        && passes_range_check::<RANGE_PROOF_NUM_WORDS>(new_balance)
+        && knowledge.withdrawal_value == public(WithdrawInstance::WithdrawalValue)
+        && new_note == public(WithdrawInstance::HashedNewNote)
+        && knowledge.token_address == public(WithdrawInstance::TokenAddress)
+        && passes_range_check::<TOKEN_ADDRESS_RANGE_PROOF_NUM_WORDS>(knowledge.token_address)
+        && commitment(
+            knowledge.recipient,
+            knowledge.withdrawal_value,
+            knowledge.nonce,
+        ) == public(WithdrawInstance::Commitment)
+        && knowledge.mac_salt == public(WithdrawInstance::MacSalt)
+        && hash(&[knowledge.mac_salt, viewing_key]) == public(WithdrawInstance::MacCommitment)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        panic::{catch_unwind, AssertUnwindSafe},
+        vec,
+        vec::Vec,
+    };
+
+    use halo2_proofs::dev::MockProver;
+    use rand_core::RngCore;
+
+    use super::accepts;
+    use crate::{
+        circuits::{generate_keys_with_min_k, generate_setup_params, test_utils::rng},
+        consts::{
+            merkle_constants::{ARITY, NOTE_TREE_HEIGHT},
+            MAX_K, TOKEN_ADDRESS_BIT_LENGTH,
+        },
+        withdraw::{WithdrawCircuit, WithdrawProverKnowledge},
+        Field, Fr, ProverKnowledge, PublicInputProvider,
+    };
+
+    const ITERATIONS: usize = 16;
+
+    /// Spoils (or not) a random part of the witness or of the public input. Returns the name of
+    /// the applied mutation.
+    fn mutate(
+        knowledge: &mut WithdrawProverKnowledge<Fr>,
+        public_input: &mut Vec<Fr>,
+        rng: &mut impl RngCore,
+    ) -> &'static str {
+        let mutation = match rng.next_u32() % 11 {
+            0 => "none",
+            1 => {
+                knowledge.id = Fr::random(&mut *rng);
+                "id"
+            }
+            2 => {
+                knowledge.nullifier_old = Fr::random(&mut *rng);
+                "nullifier_old"
+            }
+            3 => {
+                knowledge.withdrawal_value = Fr::from(rng.next_u64());
+                "small withdrawal_value"
+            }
+            4 => {
+                knowledge.withdrawal_value = knowledge.account_old_balance + Fr::ONE;
+                "withdrawal_value exceeding balance"
+            }
+            5 => {
+                let level = rng.next_u32() as usize % NOTE_TREE_HEIGHT;
+                let position = rng.next_u32() as usize % ARITY;
+                knowledge.path[level][position] = Fr::random(&mut *rng);
+                "path"
+            }
+            6 => {
+                knowledge.mac_salt = Fr::random(&mut *rng);
+                "mac_salt"
+            }
+            7 => {
+                knowledge.nullifier_new = knowledge.nullifier_old;
+                "reused nullifier"
+            }
+            8 => {
+                knowledge.token_address = Fr::from(rng.next_u64());
+                "small token_address"
+            }
+            9 => {
+                knowledge.token_address =
+                    Fr::from(2).pow([TOKEN_ADDRESS_BIT_LENGTH as u64]) + Fr::from(rng.next_u64());
+                "token_address out of range"
+            }
+            _ => {
+                let index = rng.next_u32() as usize % public_input.len();
+                public_input[index] = Fr::random(&mut *rng);
+                return "public input";
+            }
+        };
+
+        // Half of the time, keep the public input consistent with the spoiled witness.
+        if rng.next_u32() % 2 == 0 {
+            *public_input = knowledge.serialize_public_input();
+        }
+        mutation
+    }
+
+    #[test]
+    fn reference_model_agrees_with_circuit_on_random_witnesses() {
+        let mut rng = rng();
+        let (_, k, _, _) = generate_keys_with_min_k(
+            WithdrawCircuit::default(),
+            generate_setup_params(MAX_K, &mut rng),
+        )
+        .expect("key generation should succeed");
+
+        for _ in 0..ITERATIONS {
+            let mut knowledge = WithdrawProverKnowledge::random_correct_example(&mut rng);
+            let mut public_input = knowledge.serialize_public_input();
+            let mutation = mutate(&mut knowledge, &mut public_input, &mut rng);
+
+            let circuit = knowledge.create_circuit();
+            // Witness generation panics for some invalid witnesses (e.g., failing range check),
+            // which counts as a rejection.
+            let circuit_accepts = catch_unwind(AssertUnwindSafe(|| {
+                MockProver::run(k, &circuit, vec![public_input.clone()])
+                    .expect("Mock prover should run")
+                    .verify()
+                    .is_ok()
+            }))
+            .unwrap_or(false);
+
+            assert_eq!(
+                accepts(&knowledge, &public_input),
+                circuit_accepts,
+                "disagreement after mutating {mutation}"
+            );
+        }
+    }
+
+    #[test]
+    fn reference_model_accepts_correct_example() {
+        let knowledge = WithdrawProverKnowledge::random_correct_example(&mut rng());
+
+        assert!(accepts(&knowledge, &knowledge.serialize_public_input()));
+    }
+}
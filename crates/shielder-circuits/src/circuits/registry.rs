@@ -0,0 +1,240 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::marker::PhantomData;
+
+use halo2_proofs::plonk::Error;
+use rand_core::RngCore;
+use strum_macros::{EnumCount, EnumIter};
+
+use crate::{
+    circuits::{generate_keys_with_min_k, generate_proof, Params, ProvingKey, VerifyingKey},
+    consts::merkle_constants::NOTE_TREE_HEIGHT,
+    deposit::DepositProverKnowledge,
+    merkle::MerkleProverKnowledge,
+    withdraw::WithdrawProverKnowledge,
+    Fr, IntoEnumIterator, ProverKnowledge, PublicInputProvider,
+};
+#[cfg(feature = "chip-elgamal")]
+use crate::{
+    new_account::NewAccountProverKnowledge,
+    new_account_with_deposit::NewAccountWithDepositProverKnowledge,
+};
+
+/// Operations on a circuit that do not depend on its concrete `ProverKnowledge` type, so that
+/// circuits can be handled uniformly (e.g., selected by name at runtime).
+pub trait CircuitProver {
+    /// See `ProverKnowledge::circuit_name`.
+    fn circuit_name(&self) -> &'static str;
+
+    /// Number of public inputs of the circuit.
+    fn instance_count(&self) -> usize;
+
+    /// Generates keys for the circuit with the smallest sufficient `k`. Returns the downsized
+    /// parameters, `k` and the keys.
+    fn generate_keys(
+        &self,
+        params: Params,
+    ) -> Result<(Params, u32, ProvingKey, VerifyingKey), Error>;
+
+    /// Proves a random correct example of the circuit. Returns the proof and its public input.
+    fn prove_random_example(
+        &self,
+        params: &Params,
+        pk: &ProvingKey,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<u8>, Vec<Fr>);
+}
+
+struct TypedProver<PK>(PhantomData<fn() -> PK>);
+
+impl<PK: ProverKnowledge> CircuitProver for TypedProver<PK> {
+    fn circuit_name(&self) -> &'static str {
+        PK::circuit_name()
+    }
+
+    fn instance_count(&self) -> usize {
+        <PK::PublicInput as strum::EnumCount>::COUNT
+    }
+
+    fn generate_keys(
+        &self,
+        params: Params,
+    ) -> Result<(Params, u32, ProvingKey, VerifyingKey), Error> {
+        generate_keys_with_min_k(PK::Circuit::default(), params)
+    }
+
+    fn prove_random_example(
+        &self,
+        params: &Params,
+        pk: &ProvingKey,
+        mut rng: &mut dyn RngCore,
+    ) -> (Vec<u8>, Vec<Fr>) {
+        let knowledge = PK::random_correct_example(&mut rng);
+        let public_input = knowledge.serialize_public_input();
+        let proof = generate_proof(
+            params,
+            pk,
+            knowledge.create_circuit(),
+            &public_input,
+            &mut rng,
+        );
+        (proof, public_input)
+    }
+}
+
+fn prover<PK: ProverKnowledge + 'static>() -> Box<dyn CircuitProver> {
+    Box::new(TypedProver::<PK>(PhantomData))
+}
+
+/// Provers of all the protocol circuits (those disabled by cargo features are omitted).
+pub fn all_provers() -> Vec<Box<dyn CircuitProver>> {
+    vec![
+        prover::<DepositProverKnowledge<Fr>>(),
+        prover::<WithdrawProverKnowledge<Fr>>(),
+        #[cfg(feature = "chip-elgamal")]
+        prover::<NewAccountProverKnowledge<Fr>>(),
+        #[cfg(feature = "chip-elgamal")]
+        prover::<NewAccountWithDepositProverKnowledge<Fr>>(),
+    ]
+}
+
+/// Returns the prover of the protocol circuit called `name`, or `None` if there is no such circuit.
+pub fn by_name(name: &str) -> Option<Box<dyn CircuitProver>> {
+    all_provers()
+        .into_iter()
+        .find(|prover| prover.circuit_name() == name)
+}
+
+/// Runtime identifier of a circuit, for services handling several circuit types without having to
+/// carry their type parameters around. `Merkle` is the standalone Merkle proof over the note tree.
+///
+/// The discriminants are the wire encoding of the identifier (see `code`), so they are fixed
+/// regardless of the enabled features.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, EnumIter, EnumCount)]
+#[repr(u32)]
+pub enum CircuitId {
+    Deposit = 0,
+    Withdraw = 1,
+    #[cfg(feature = "chip-elgamal")]
+    NewAccount = 2,
+    Merkle = 3,
+}
+
+impl CircuitId {
+    /// Wire encoding of the identifier.
+    pub fn code(&self) -> u32 {
+        *self as u32
+    }
+
+    /// Inverse of `code`. Returns `None` for an unknown code (or a circuit that is not enabled).
+    pub fn from_code(code: u32) -> Option<Self> {
+        Self::iter().find(|id| id.code() == code)
+    }
+
+    pub fn prover(&self) -> Box<dyn CircuitProver> {
+        match self {
+            CircuitId::Deposit => prover::<DepositProverKnowledge<Fr>>(),
+            CircuitId::Withdraw => prover::<WithdrawProverKnowledge<Fr>>(),
+            #[cfg(feature = "chip-elgamal")]
+            CircuitId::NewAccount => prover::<NewAccountProverKnowledge<Fr>>(),
+            CircuitId::Merkle => prover::<MerkleProverKnowledge<NOTE_TREE_HEIGHT, Fr>>(),
+        }
+    }
+
+    /// See `ProverKnowledge::circuit_name`.
+    pub fn name(&self) -> &'static str {
+        self.prover().circuit_name()
+    }
+
+    /// Number of public inputs of the circuit.
+    pub fn instance_count(&self) -> usize {
+        self.prover().instance_count()
+    }
+
+    /// See `CircuitProver::generate_keys`.
+    pub fn generate_keys(
+        &self,
+        params: Params,
+    ) -> Result<(Params, u32, ProvingKey, VerifyingKey), Error> {
+        self.prover().generate_keys(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{all_provers, by_name, CircuitId};
+    use crate::{
+        circuits::{generate_setup_params, test_utils::rng, verify},
+        consts::MAX_K,
+        deposit::{DepositInstance, DepositProverKnowledge},
+        withdraw::WithdrawProverKnowledge,
+        EnumCount, Fr, IntoEnumIterator, ProverKnowledge,
+    };
+    #[cfg(feature = "chip-elgamal")]
+    use crate::{
+        new_account::NewAccountProverKnowledge,
+        new_account_with_deposit::NewAccountWithDepositProverKnowledge,
+    };
+
+    #[test]
+    fn names_round_trip() {
+        let names = [
+            DepositProverKnowledge::<Fr>::circuit_name(),
+            WithdrawProverKnowledge::<Fr>::circuit_name(),
+            #[cfg(feature = "chip-elgamal")]
+            NewAccountProverKnowledge::<Fr>::circuit_name(),
+            #[cfg(feature = "chip-elgamal")]
+            NewAccountWithDepositProverKnowledge::<Fr>::circuit_name(),
+        ];
+        assert_eq!(all_provers().len(), names.len());
+
+        for name in names {
+            let prover = by_name(name).expect("every circuit is registered");
+            assert_eq!(prover.circuit_name(), name);
+        }
+        assert!(by_name("unknown").is_none());
+    }
+
+    #[test]
+    fn prover_found_by_name_produces_valid_proofs() {
+        let mut rng = rng();
+        let prover = by_name("deposit").expect("deposit circuit is registered");
+
+        let (params, _, pk, vk) = prover
+            .generate_keys(generate_setup_params(MAX_K, &mut rng))
+            .expect("keys should not fail to generate");
+        let (proof, public_input) = prover.prove_random_example(&params, &pk, &mut rng);
+
+        assert!(verify(&params, &vk, &proof, &public_input).is_ok());
+    }
+
+    #[test]
+    fn keys_are_generated_for_every_circuit_id() {
+        let params = generate_setup_params(MAX_K, &mut rng());
+
+        for id in CircuitId::iter() {
+            assert!(
+                id.generate_keys(params.clone()).is_ok(),
+                "keys for {} should not fail to generate",
+                id.name()
+            );
+            assert!(id.instance_count() > 0);
+        }
+    }
+
+    #[test]
+    fn circuit_id_code_roundtrips() {
+        for id in CircuitId::iter() {
+            assert_eq!(CircuitId::from_code(id.code()), Some(id));
+        }
+        assert_eq!(CircuitId::Merkle.code(), 3);
+        assert_eq!(CircuitId::from_code(4), None);
+    }
+
+    #[test]
+    fn circuit_id_matches_registered_prover() {
+        assert_eq!(CircuitId::Deposit.name(), "deposit");
+        assert_eq!(CircuitId::Deposit.instance_count(), DepositInstance::COUNT);
+        assert_eq!(CircuitId::Merkle.name(), "merkle");
+        assert_eq!(CircuitId::Merkle.instance_count(), 1);
+    }
+}
@@ -59,6 +59,26 @@ pub fn run_full_pipeline<PK: ProverKnowledge>() {
         .expect("Proving-verifying pipeline should succeed");
 }
 
+// Generates keys and a proof for a correct example of `PK`, and returns the time it takes to verify
+// the proof. Panics if verification fails.
+#[cfg(feature = "bench")]
+pub fn measure_verification_time<PK: ProverKnowledge>() -> std::time::Duration {
+    let mut rng = OsRng;
+
+    let prover_knowledge = PK::random_correct_example(&mut rng);
+    let circuit = prover_knowledge.create_circuit();
+    let pub_input = prover_knowledge.serialize_public_input();
+
+    let params = generate_setup_params(MAX_K, &mut rng);
+    let (params, _, pk, vk) =
+        generate_keys_with_min_k(circuit.clone(), params).expect("key generation should succeed");
+    let proof = generate_proof(&params, &pk, circuit, &pub_input, &mut rng);
+
+    let start = std::time::Instant::now();
+    verify(&params, &vk, &proof, &pub_input).expect("verification should succeed");
+    start.elapsed()
+}
+
 // Runs key generation, proof production on `prove_pub_input`, and proof verification
 // on `verify_pub_input`. In case of failure before verification, panics.
 // In case of verification failure, returns `VerifyFailure`s from `MockProver`.
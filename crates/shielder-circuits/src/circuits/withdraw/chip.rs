@@ -2,6 +2,7 @@ use halo2_proofs::plonk::Error;
 
 use crate::{
     chips::{
+        commitment::CommitmentChip,
         mac::{MacChip, MacInput},
         note::{Note, NoteChip},
         range_check::RangeCheckChip,
@@ -14,7 +15,7 @@ use crate::{
     },
     consts::RANGE_PROOF_NUM_WORDS,
     instance_wrapper::InstanceWrapper,
-    poseidon::circuit::{hash, PoseidonChip},
+    poseidon::circuit::PoseidonChip,
     synthesizer::Synthesizer,
     version::NOTE_VERSION,
     withdraw::WithdrawInstance::{self, *},
@@ -37,7 +38,7 @@ impl WithdrawChip {
         synthesizer: &mut impl Synthesizer,
         knowledge: &WithdrawProverKnowledge<AssignedCell>,
     ) -> Result<(), Error> {
-        let old_note = self.note.note_hash(
+        let old_note = self.note.hash_note_and_expose_nullifier(
             synthesizer,
             &Note {
                 version: NOTE_VERSION,
@@ -46,6 +47,8 @@ impl WithdrawChip {
                 account_balance: knowledge.account_old_balance.clone(),
                 token_address: knowledge.token_address.clone(),
             },
+            &self.public_inputs,
+            HashedOldNullifier,
         )?;
 
         self.merkle.synthesize(
@@ -54,19 +57,24 @@ impl WithdrawChip {
         )
     }
 
-    pub fn check_old_nullifier(
+    /// Constrains `withdrawal_value <= account_old_balance` by range checking the difference to
+    /// the bit length of account balances (a negative difference wraps around to a huge field
+    /// element). Returns the difference, i.e., the new account balance.
+    pub fn constrain_sufficient_balance(
         &self,
         synthesizer: &mut impl Synthesizer,
         knowledge: &WithdrawProverKnowledge<AssignedCell>,
-    ) -> Result<(), Error> {
-        let hashed_old_nullifier = hash(
+    ) -> Result<AssignedCell, Error> {
+        let new_balance = self.note.decrease_balance(
             synthesizer,
-            self.poseidon.clone(),
-            [knowledge.nullifier_old.clone()],
+            knowledge.account_old_balance.clone(),
+            knowledge.withdrawal_value.clone(),
         )?;
 
-        self.public_inputs
-            .constrain_cells(synthesizer, [(hashed_old_nullifier, HashedOldNullifier)])
+        self.range_check
+            .constrain_value::<RANGE_PROOF_NUM_WORDS>(synthesizer, new_balance.clone())?;
+
+        Ok(new_balance)
     }
 
     pub fn check_new_note(
@@ -74,20 +82,19 @@ impl WithdrawChip {
         synthesizer: &mut impl Synthesizer,
         knowledge: &WithdrawProverKnowledge<AssignedCell>,
     ) -> Result<(), Error> {
-        let new_balance = self.note.decrease_balance(
-            synthesizer,
-            knowledge.account_old_balance.clone(),
-            knowledge.withdrawal_value.clone(),
-        )?;
-
-        self.range_check
-            .constrain_value::<RANGE_PROOF_NUM_WORDS>(synthesizer, new_balance.clone())?;
+        let new_balance = self.constrain_sufficient_balance(synthesizer, knowledge)?;
 
         self.public_inputs.constrain_cells(
             synthesizer,
             [(knowledge.withdrawal_value.clone(), WithdrawalValue)],
         )?;
 
+        self.note.constrain_nullifiers_distinct(
+            synthesizer,
+            knowledge.nullifier_old.clone(),
+            knowledge.nullifier_new.clone(),
+        )?;
+
         let new_note = self.note.note_hash(
             synthesizer,
             &Note {
@@ -108,8 +115,15 @@ impl WithdrawChip {
         synthesizer: &mut impl Synthesizer,
         knowledge: &WithdrawProverKnowledge<AssignedCell>,
     ) -> Result<(), Error> {
+        let commitment = CommitmentChip::new(self.poseidon.clone()).compute(
+            synthesizer,
+            knowledge.recipient.clone(),
+            knowledge.withdrawal_value.clone(),
+            knowledge.nonce.clone(),
+        )?;
+
         self.public_inputs
-            .constrain_cells(synthesizer, [(knowledge.commitment.clone(), Commitment)])
+            .constrain_cells(synthesizer, [(commitment, Commitment)])
     }
 
     pub fn check_mac(
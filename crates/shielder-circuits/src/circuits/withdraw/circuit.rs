@@ -11,12 +11,21 @@ use crate::{
     instance_wrapper::InstanceWrapper,
     synthesizer::create_synthesizer,
     withdraw::{WithdrawInstance, WithdrawProverKnowledge},
-    Fr, Value,
+    Fr, ProverKnowledge, Value,
 };
 
 #[derive(Clone, Debug, Default)]
 pub struct WithdrawCircuit(pub WithdrawProverKnowledge<Value>);
 
+impl WithdrawCircuit {
+    /// Circuit with a known, correct witness. Unlike `Self::default()` (which is all-unknown and
+    /// suitable only for key generation), it can be proven. The matching public input is
+    /// `WithdrawProverKnowledge::example().serialize_public_input()`.
+    pub fn example() -> Self {
+        WithdrawProverKnowledge::<Fr>::example().create_circuit()
+    }
+}
+
 impl Circuit<Fr> for WithdrawCircuit {
     type Config = (WithdrawChip, ColumnPool<Advice, PreSynthesisPhase>);
     type FloorPlanner = V1;
@@ -56,7 +65,6 @@ impl Circuit<Fr> for WithdrawCircuit {
         let knowledge = self.0.embed(&mut synthesizer, "WithdrawProverKnowledge")?;
 
         main_chip.check_old_note(&mut synthesizer, &knowledge)?;
-        main_chip.check_old_nullifier(&mut synthesizer, &knowledge)?;
         main_chip.check_new_note(&mut synthesizer, &knowledge)?;
         main_chip.check_commitment(&mut synthesizer, &knowledge)?;
         main_chip.check_mac(&mut synthesizer, &knowledge)
@@ -70,7 +78,10 @@ mod tests {
     use rand_core::OsRng;
 
     use crate::{
-        chips::viewing_key::off_circuit,
+        chips::{
+            commitment::off_circuit::commitment, range_check::test_utils::with_forged_witnesses,
+            viewing_key::off_circuit,
+        },
         circuits::{
             merkle::generate_example_path_with_given_leaf,
             test_utils::{
@@ -103,7 +114,7 @@ mod tests {
 
         // Substitute all that changes in `pk` when `token_address` changes.
         let h_note_old = note_hash(&Note {
-            version: NoteVersion::new(0),
+            version: NoteVersion::V0,
             id: pk.id,
             nullifier: pk.nullifier_old,
             account_balance: pk.account_old_balance,
@@ -231,7 +242,7 @@ mod tests {
                 HashedOldNullifier => h_nullifier_old,
                 HashedNewNote => h_note_new,
                 WithdrawalValue => pk.withdrawal_value,
-                Commitment => pk.commitment,
+                Commitment => commitment(pk.recipient, pk.withdrawal_value, pk.nonce),
                 TokenAddress => pk.token_address,
                 MacSalt => pk.mac_salt,
                 MacCommitment => hash(&[pk.mac_salt, off_circuit::derive_viewing_key(pk.id)]),
@@ -248,6 +259,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn fails_if_recipient_is_changed() {
+        let mut pk = WithdrawProverKnowledge::random_correct_example(&mut OsRng);
+        let pub_input = pk.serialize_public_input();
+
+        pk.recipient += Fr::ONE;
+
+        assert!(
+            expect_prover_success_and_run_verification(pk.create_circuit(), &pub_input).is_err()
+        );
+    }
+
     #[test]
     fn fails_if_commitment_provided_during_verify_is_not_one_provided_during_proof() {
         let pk = WithdrawProverKnowledge::random_correct_example(&mut OsRng);
@@ -290,6 +313,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fails_if_withdrawal_exceeds_balance() {
+        let mut rng = SmallRng::from_seed([42; 32]);
+        let mut pk = WithdrawProverKnowledge::random_correct_example(&mut rng);
+        pk.withdrawal_value = pk.account_old_balance + Fr::ONE;
+
+        // An honest prover refuses to decompose the negative difference, so the witness has to be
+        // forged.
+        assert!(with_forged_witnesses(|| {
+            expect_prover_success_and_run_verification(
+                pk.create_circuit(),
+                &pk.serialize_public_input(),
+            )
+        })
+        .is_err());
+    }
+
     #[test]
     fn fails_if_token_address_pub_input_incorrect() {
         let mut rng = SmallRng::from_seed([42; 32]);
@@ -3,7 +3,7 @@ use macros::embeddable;
 use rand_core::RngCore;
 
 use crate::{
-    chips::viewing_key,
+    chips::{commitment, nullifier::off_circuit::nullifier_hash, viewing_key},
     consts::{
         merkle_constants::{ARITY, NOTE_TREE_HEIGHT},
         MAX_ACCOUNT_BALANCE_PASSING_RANGE_CHECK,
@@ -26,8 +26,9 @@ use crate::{
 pub struct WithdrawProverKnowledge<T> {
     pub withdrawal_value: T,
 
-    // Additional public parameters that need to be included in proof
-    pub commitment: T,
+    // Transaction data bound by the commitment
+    pub recipient: T,
+    pub nonce: T,
 
     // Old note
     pub id: T,
@@ -49,6 +50,10 @@ impl ProverKnowledge for WithdrawProverKnowledge<Fr> {
     type Circuit = WithdrawCircuit;
     type PublicInput = WithdrawInstance;
 
+    fn circuit_name() -> &'static str {
+        "withdraw"
+    }
+
     /// TODO: Refactor this test. Having `MAX_ACCOUNT_BALANCE_PASSING_RANGE_CHECK` as the only
     /// non-random, non-trivial value is inconsistent with the function name and easy to overlook.
     /// Consider moving it to a separate test. Also evaluate removing randomness completely, as
@@ -76,7 +81,8 @@ impl ProverKnowledge for WithdrawProverKnowledge<Fr> {
 
         Self {
             withdrawal_value: Fr::ONE,
-            commitment: Fr::random(&mut *rng),
+            recipient: Fr::random(&mut *rng),
+            nonce: Fr::random(&mut *rng),
             id,
             nullifier_old,
             account_old_balance,
@@ -100,7 +106,8 @@ impl ProverKnowledge for WithdrawProverKnowledge<Fr> {
             path: self.path.map(|level| level.map(Value::known)),
 
             withdrawal_value: Value::known(self.withdrawal_value),
-            commitment: Value::known(self.commitment),
+            recipient: Value::known(self.recipient),
+            nonce: Value::known(self.nonce),
             mac_salt: Value::known(self.mac_salt),
         })
     }
@@ -112,7 +119,7 @@ impl PublicInputProvider<WithdrawInstance> for WithdrawProverKnowledge<Fr> {
 
         match instance_id {
             WithdrawInstance::MerkleRoot => hash(&self.path[NOTE_TREE_HEIGHT - 1]),
-            WithdrawInstance::HashedOldNullifier => hash(&[self.nullifier_old]),
+            WithdrawInstance::HashedOldNullifier => nullifier_hash(self.nullifier_old),
             WithdrawInstance::HashedNewNote => note_hash(&Note {
                 version: NOTE_VERSION,
                 id: self.id,
@@ -121,7 +128,11 @@ impl PublicInputProvider<WithdrawInstance> for WithdrawProverKnowledge<Fr> {
                 token_address: self.token_address,
             }),
             WithdrawInstance::WithdrawalValue => self.withdrawal_value,
-            WithdrawInstance::Commitment => self.commitment,
+            WithdrawInstance::Commitment => commitment::off_circuit::commitment(
+                self.recipient,
+                self.withdrawal_value,
+                self.nonce,
+            ),
             WithdrawInstance::TokenAddress => self.token_address,
             WithdrawInstance::MacSalt => self.mac_salt,
             WithdrawInstance::MacCommitment => hash(&[self.mac_salt, viewing_key]),
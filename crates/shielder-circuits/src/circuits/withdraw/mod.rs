@@ -1,3 +1,4 @@
+use strum::EnumCount as _;
 use strum_macros::{EnumCount, EnumIter};
 
 use crate::{chips::note::NoteInstance, merkle::MerkleInstance};
@@ -23,6 +24,19 @@ pub enum WithdrawInstance {
     MacCommitment,
 }
 
+/// Names under which the public inputs of `WithdrawCircuit` are known in the Shielder contract.
+/// The array length ties the table to the number of instance variants.
+pub const WITHDRAW_CONTRACT_PARAMS: [(WithdrawInstance, &str); WithdrawInstance::COUNT] = [
+    (WithdrawInstance::MerkleRoot, "merkleRoot"),
+    (WithdrawInstance::HashedOldNullifier, "oldNullifierHash"),
+    (WithdrawInstance::HashedNewNote, "newNote"),
+    (WithdrawInstance::WithdrawalValue, "amount"),
+    (WithdrawInstance::TokenAddress, "token"),
+    (WithdrawInstance::Commitment, "commitment"),
+    (WithdrawInstance::MacSalt, "macSalt"),
+    (WithdrawInstance::MacCommitment, "macCommitment"),
+];
+
 impl TryFrom<WithdrawInstance> for MerkleInstance {
     type Error = ();
 
@@ -59,16 +73,14 @@ impl TryFrom<WithdrawInstance> for MacInstance {
 
 #[cfg(test)]
 mod tests {
-    use std::{vec, vec::Vec};
-
-    use strum::IntoEnumIterator;
+    use alloc::collections::BTreeSet;
 
-    use super::{WithdrawInstance, WithdrawInstance::*};
+    use super::{WithdrawInstance::*, WITHDRAW_CONTRACT_PARAMS};
+    use crate::{circuits::assert_instance_order, withdraw::WithdrawInstance, IntoEnumIterator};
 
     #[test]
     fn instance_order() {
-        // This is the order used in other parts of the codebase (e.g., in contracts).
-        let expected_order = vec![
+        assert_instance_order(&[
             MerkleRoot,
             HashedOldNullifier,
             HashedNewNote,
@@ -77,7 +89,21 @@ mod tests {
             Commitment,
             MacSalt,
             MacCommitment,
-        ];
-        assert_eq!(expected_order, WithdrawInstance::iter().collect::<Vec<_>>());
+        ]);
+    }
+
+    #[test]
+    fn contract_params_cover_every_instance_once() {
+        let instances = WITHDRAW_CONTRACT_PARAMS
+            .iter()
+            .map(|(instance, _)| *instance)
+            .collect::<BTreeSet<_>>();
+        let names = WITHDRAW_CONTRACT_PARAMS
+            .iter()
+            .map(|(_, name)| *name)
+            .collect::<BTreeSet<_>>();
+
+        assert_eq!(instances, WithdrawInstance::iter().collect());
+        assert_eq!(names.len(), WITHDRAW_CONTRACT_PARAMS.len());
     }
 }
@@ -1,29 +1,103 @@
 use halo2_proofs::plonk::{Advice, ConstraintSystem, Fixed};
 
+#[cfg(feature = "chip-elgamal")]
+use crate::{
+    chips::{
+        bits_recompose::BitsRecomposeChip, el_gamal::ElGamalEncryptionChip,
+        points_add::PointsAddChip, scalar_multiply::ScalarMultiplyChip, to_affine::ToAffineChip,
+        to_projective::ToProjectiveChip,
+    },
+    gates::{
+        is_point_on_curve_affine::IsPointOnCurveAffineGate, points_add::PointsAddGate,
+        scalar_multiply::ScalarMultiplyGate, to_affine::ToAffineGate,
+    },
+};
 use crate::{
     chips::{
-        el_gamal::ElGamalEncryptionChip,
         note::{NoteChip, NoteInstance},
-        points_add::PointsAddChip,
+        nullifier::NullifierChip,
         range_check::RangeCheckChip,
-        scalar_multiply::ScalarMultiplyChip,
         sum::SumChip,
-        to_affine::ToAffineChip,
-        to_projective::ToProjectiveChip,
     },
     column_pool::{AccessColumn, ColumnPool, ConfigPhase, PreSynthesisPhase},
-    consts::merkle_constants::WIDTH,
-    gates::{
-        is_point_on_curve_affine::IsPointOnCurveAffineGate, membership::MembershipGate,
-        points_add::PointsAddGate, scalar_multiply::ScalarMultiplyGate, sum::SumGate,
-        to_affine::ToAffineGate, Gate,
-    },
+    consts::merkle_constants::{ARITY, WIDTH},
+    gates::{membership::MembershipGate, non_zero::NonZeroGate, sum::SumGate, Gate},
     instance_wrapper::InstanceWrapper,
     merkle::{MerkleChip, MerkleInstance},
     poseidon::{circuit::PoseidonChip, spec::PoseidonSpec},
     Fr,
 };
 
+/// Chips (and standalone gates) that circuits configure with `ConfigsBuilder`. Meant for estimating
+/// the size of a chip combination before building the circuit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ChipKind {
+    Poseidon,
+    Merkle,
+    RangeCheck,
+    Sum,
+    PointsAdd,
+    ScalarMultiply,
+    ToAffine,
+    ToProjective,
+    IsPointOnCurveAffine,
+    NonZero,
+    Note,
+    Nullifier,
+    ElGamalEncryption,
+}
+
+impl ChipKind {
+    /// Number of advice columns that configuring the chip (together with its dependencies) requires.
+    pub const fn advice_columns(self) -> usize {
+        match self {
+            ChipKind::Poseidon => WIDTH + 1,
+            ChipKind::Merkle => max(ARITY + 1, ChipKind::Poseidon.advice_columns()),
+            ChipKind::RangeCheck => max(1, ChipKind::Sum.advice_columns()),
+            ChipKind::Sum => 3,
+            ChipKind::PointsAdd => 9,
+            ChipKind::ScalarMultiply => 7,
+            ChipKind::ToAffine => 6,
+            ChipKind::ToProjective => 0,
+            ChipKind::IsPointOnCurveAffine => 2,
+            ChipKind::NonZero => 2,
+            ChipKind::Nullifier => ChipKind::Poseidon.advice_columns(),
+            ChipKind::Note => max(
+                ChipKind::RangeCheck.advice_columns(),
+                max(
+                    ChipKind::Poseidon.advice_columns(),
+                    ChipKind::NonZero.advice_columns(),
+                ),
+            ),
+            ChipKind::ElGamalEncryption => max(
+                ChipKind::Sum.advice_columns(),
+                max(
+                    ChipKind::PointsAdd.advice_columns(),
+                    ChipKind::ScalarMultiply.advice_columns(),
+                ),
+            ),
+        }
+    }
+
+    /// Number of advice columns that `ConfigsBuilder` creates for `chips`. All chips draw from a
+    /// single column pool, so this is the maximum (not the sum) of the individual requirements.
+    pub fn required_advice_columns(chips: &[ChipKind]) -> usize {
+        chips
+            .iter()
+            .map(|chip| chip.advice_columns())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+const fn max(a: usize, b: usize) -> usize {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
 pub struct ConfigsBuilder<'cs> {
     system: &'cs mut ConstraintSystem<Fr>,
     advice_pool: ColumnPool<Advice, ConfigPhase>,
@@ -33,13 +107,23 @@ pub struct ConfigsBuilder<'cs> {
     poseidon: Option<PoseidonChip>,
     range_check: Option<RangeCheckChip>,
     sum: Option<SumChip>,
+    #[cfg(feature = "chip-elgamal")]
     points_add: Option<PointsAddChip>,
+    #[cfg(feature = "chip-elgamal")]
     scalar_multiply: Option<ScalarMultiplyChip>,
+    #[cfg(feature = "chip-elgamal")]
     to_affine: Option<ToAffineChip>,
+    #[cfg(feature = "chip-elgamal")]
     to_projective: Option<ToProjectiveChip>,
+    #[cfg(feature = "chip-elgamal")]
     is_point_on_curve_affine: Option<IsPointOnCurveAffineGate>,
+    non_zero: Option<NonZeroGate>,
+    #[cfg(feature = "chip-elgamal")]
     el_gamal_encryption: Option<ElGamalEncryptionChip>,
+    #[cfg(feature = "chip-elgamal")]
+    el_gamal_encryption_checked: Option<ElGamalEncryptionChip>,
     note: Option<NoteChip>,
+    nullifier: Option<NullifierChip>,
 }
 
 macro_rules! check_if_cached {
@@ -61,13 +145,23 @@ impl<'cs> ConfigsBuilder<'cs> {
             poseidon: None,
             range_check: None,
             sum: None,
+            #[cfg(feature = "chip-elgamal")]
             points_add: None,
+            #[cfg(feature = "chip-elgamal")]
             scalar_multiply: None,
+            #[cfg(feature = "chip-elgamal")]
             to_affine: None,
+            #[cfg(feature = "chip-elgamal")]
             to_projective: None,
+            #[cfg(feature = "chip-elgamal")]
             is_point_on_curve_affine: None,
+            non_zero: None,
+            #[cfg(feature = "chip-elgamal")]
             el_gamal_encryption: None,
+            #[cfg(feature = "chip-elgamal")]
+            el_gamal_encryption_checked: None,
             note: None,
+            nullifier: None,
         }
     }
 
@@ -142,6 +236,68 @@ impl<'cs> ConfigsBuilder<'cs> {
         self.sum.clone().expect("Sum not configured")
     }
 
+    pub fn with_non_zero(mut self) -> Self {
+        check_if_cached!(self, non_zero);
+        self.non_zero = Some(NonZeroGate::create_gate(self.system, &mut self.advice_pool));
+        self
+    }
+
+    pub fn non_zero_gate(&self) -> NonZeroGate {
+        self.non_zero.expect("NonZeroGate is not configured")
+    }
+
+    pub fn with_note(mut self, public_inputs: InstanceWrapper<NoteInstance>) -> Self {
+        check_if_cached!(self, note);
+        self = self.with_sum();
+        self = self.with_poseidon();
+        self = self.with_non_zero();
+        self = self.with_nullifier();
+        self = self.with_range_check();
+
+        self.note = Some(NoteChip {
+            public_inputs,
+            sum: self.sum_chip(),
+            poseidon: self.poseidon_chip(),
+            range_check: self.range_check_chip(),
+            non_zero: self.non_zero_gate(),
+            nullifier: self.nullifier_chip(),
+        });
+        self
+    }
+
+    pub fn note_chip(&self) -> NoteChip {
+        self.note.clone().expect("Note not configured")
+    }
+
+    pub fn with_nullifier(mut self) -> Self {
+        check_if_cached!(self, nullifier);
+        self = self.with_poseidon();
+
+        self.nullifier = Some(NullifierChip::new(self.poseidon_chip()));
+        self
+    }
+
+    pub fn nullifier_chip(&self) -> NullifierChip {
+        self.nullifier.clone().expect("Nullifier not configured")
+    }
+
+    pub fn advice_pool_with_capacity(
+        &mut self,
+        capacity: usize,
+    ) -> &ColumnPool<Advice, ConfigPhase> {
+        self.advice_pool.ensure_capacity(self.system, capacity);
+        &self.advice_pool
+    }
+
+    fn fixed_pool_with_capacity(&mut self, capacity: usize) -> &ColumnPool<Fixed, ConfigPhase> {
+        self.fixed_pool.ensure_capacity(self.system, capacity);
+        &self.fixed_pool
+    }
+}
+
+/// Chips needed only by the circuits that encrypt with El-Gamal (i.e., the new account circuits).
+#[cfg(feature = "chip-elgamal")]
+impl ConfigsBuilder<'_> {
     pub fn with_points_add_chip(mut self) -> Self {
         check_if_cached!(self, points_add);
         self.points_add = Some(PointsAddChip {
@@ -210,23 +366,8 @@ impl<'cs> ConfigsBuilder<'cs> {
             .expect("IsPointOnCurveAffineGate is not configured")
     }
 
-    pub fn with_note(mut self, public_inputs: InstanceWrapper<NoteInstance>) -> Self {
-        check_if_cached!(self, note);
-        self = self.with_sum();
-        self = self.with_poseidon();
-
-        self.note = Some(NoteChip {
-            public_inputs,
-            sum: self.sum_chip(),
-            poseidon: self.poseidon_chip(),
-        });
-        self
-    }
-
-    pub fn note_chip(&self) -> NoteChip {
-        self.note.clone().expect("Note not configured")
-    }
-
+    /// Configures the El-Gamal encryption chip without the salt check (see
+    /// [`Self::with_el_gamal_encryption_chip_checked`]).
     pub fn with_el_gamal_encryption_chip(mut self) -> Self {
         check_if_cached!(self, el_gamal_encryption);
         self = self.with_sum();
@@ -237,6 +378,7 @@ impl<'cs> ConfigsBuilder<'cs> {
             multiply_chip: self.scalar_multiply_chip(),
             add_chip: self.points_add_chip(),
             sum_chip: self.sum_chip(),
+            salt_check: None,
         });
         self
     }
@@ -247,16 +389,68 @@ impl<'cs> ConfigsBuilder<'cs> {
             .expect("ElGamalEncryptionChip not configured")
     }
 
-    pub fn advice_pool_with_capacity(
-        &mut self,
-        capacity: usize,
-    ) -> &ColumnPool<Advice, ConfigPhase> {
-        self.advice_pool.ensure_capacity(self.system, capacity);
-        &self.advice_pool
+    /// Like [`Self::with_el_gamal_encryption_chip`], but the chip additionally rejects zero
+    /// encryption salts (see `ElGamalEncryptionChip::encrypt`).
+    pub fn with_el_gamal_encryption_chip_checked(mut self) -> Self {
+        check_if_cached!(self, el_gamal_encryption_checked);
+        self = self.with_el_gamal_encryption_chip();
+        self = self.with_non_zero();
+        let bits_recompose = BitsRecomposeChip::new(self.system, &mut self.advice_pool);
+
+        self.el_gamal_encryption_checked = Some(ElGamalEncryptionChip {
+            salt_check: Some((bits_recompose, self.non_zero_gate())),
+            ..self.el_gamal_encryption_chip()
+        });
+        self
     }
 
-    fn fixed_pool_with_capacity(&mut self, capacity: usize) -> &ColumnPool<Fixed, ConfigPhase> {
-        self.fixed_pool.ensure_capacity(self.system, capacity);
-        &self.fixed_pool
+    pub fn el_gamal_encryption_chip_checked(&self) -> ElGamalEncryptionChip {
+        self.el_gamal_encryption_checked
+            .clone()
+            .expect("Checked ElGamalEncryptionChip not configured")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::plonk::{Circuit, ConstraintSystem};
+
+    use super::ChipKind;
+    #[cfg(feature = "chip-elgamal")]
+    use crate::new_account::NewAccountCircuit;
+    use crate::{withdraw::WithdrawCircuit, Fr};
+
+    fn configured_advice_columns<C: Circuit<Fr>>() -> usize {
+        let mut cs = ConstraintSystem::default();
+        C::configure(&mut cs);
+        cs.num_advice_columns()
+    }
+
+    #[test]
+    fn estimate_matches_withdraw_configuration() {
+        let chips = [ChipKind::Merkle, ChipKind::RangeCheck, ChipKind::Note];
+
+        assert_eq!(
+            ChipKind::required_advice_columns(&chips),
+            configured_advice_columns::<WithdrawCircuit>()
+        );
+    }
+
+    #[cfg(feature = "chip-elgamal")]
+    #[test]
+    fn estimate_matches_new_account_configuration() {
+        let chips = [
+            ChipKind::Poseidon,
+            ChipKind::Note,
+            ChipKind::IsPointOnCurveAffine,
+            ChipKind::ToProjective,
+            ChipKind::ToAffine,
+            ChipKind::ElGamalEncryption,
+        ];
+
+        assert_eq!(
+            ChipKind::required_advice_columns(&chips),
+            configured_advice_columns::<NewAccountCircuit>()
+        );
     }
 }
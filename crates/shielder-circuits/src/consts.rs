@@ -22,6 +22,15 @@ static_assertions::const_assert_eq!(
     RANGE_PROOF_CHUNK_SIZE * NONCE_RANGE_PROOF_NUM_WORDS
 );
 
+/// Token addresses are Ethereum addresses, i.e., 160-bit numbers.
+pub const TOKEN_ADDRESS_BIT_LENGTH: usize = 160;
+
+pub const TOKEN_ADDRESS_RANGE_PROOF_NUM_WORDS: usize = 20;
+static_assertions::const_assert_eq!(
+    TOKEN_ADDRESS_BIT_LENGTH,
+    RANGE_PROOF_CHUNK_SIZE * TOKEN_ADDRESS_RANGE_PROOF_NUM_WORDS
+);
+
 /// The number of bits needed to encode a field element.
 pub const FIELD_BITS: usize = Fr::NUM_BITS as usize;
 
@@ -48,4 +57,19 @@ lazy_static! {
     ///
     /// This is the ASCII encoding of "key for AR".
     pub static ref VIEWING_KEY_SALT: Fr = Fr::from_u128(0x6B657920666F72204152);
+
+    /// Domain for hashing notes with `hash_with_domain`.
+    ///
+    /// This is the ASCII encoding of "note".
+    pub static ref NOTE_HASH_DOMAIN: Fr = Fr::from_u128(0x6E6F7465);
+
+    /// Domain for hashing nullifiers with `hash_with_domain`.
+    ///
+    /// This is the ASCII encoding of "nullifier".
+    pub static ref NULLIFIER_HASH_DOMAIN: Fr = Fr::from_u128(0x6E756C6C6966696572);
+
+    /// Domain for computing MACs with `hash_with_domain`.
+    ///
+    /// This is the ASCII encoding of "mac".
+    pub static ref MAC_HASH_DOMAIN: Fr = Fr::from_u128(0x6D6163);
 }
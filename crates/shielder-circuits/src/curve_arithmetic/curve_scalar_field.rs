@@ -87,3 +87,33 @@ impl CurveScalarField for Expression<Fr> {
         Expression::Constant(Fr::one())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::{arithmetic::CurveExt, halo2curves::grumpkin::G1, plonk::Expression};
+
+    use super::CurveScalarField;
+    use crate::{curve_arithmetic::V, Fr, Value};
+
+    fn expected_b3() -> Fr {
+        G1::b() + G1::b() + G1::b()
+    }
+
+    #[test]
+    fn b_is_grumpkin_parameter() {
+        assert_eq!(<Fr as CurveScalarField>::b(), -Fr::from(17));
+    }
+
+    #[test]
+    fn b3_is_consistent_across_representations() {
+        assert_eq!(Fr::b3(), expected_b3());
+
+        Value::b3().assert_if_known(|b3| *b3 == expected_b3());
+        V::b3().0.assert_if_known(|b3| *b3 == expected_b3());
+
+        match Expression::<Fr>::b3() {
+            Expression::Constant(b3) => assert_eq!(b3, expected_b3()),
+            _ => panic!("b3 should be a constant expression"),
+        }
+    }
+}
@@ -1,12 +1,20 @@
 use core::ops::Sub;
 
-use halo2_proofs::halo2curves::{group::Group, grumpkin::G1};
+use halo2_proofs::halo2curves::{ff::PrimeField, group::Group, grumpkin::G1};
 use rand_core::RngCore;
 
 use crate::{
-    curve_arithmetic::curve_scalar_field::CurveScalarField, AssignedCell, Field, Fr, Value,
+    curve_arithmetic::{
+        curve_scalar_field::CurveScalarField, is_point_on_curve_affine,
+        quadratic_residue_given_x_affine,
+    },
+    AssignedCell, Field, Fr, Value,
 };
 
+/// Flag in the last byte of a compressed point, set iff the y coordinate is odd. `Fr` elements fit
+/// in 254 bits, so the most significant bits of the x encoding are otherwise always zero.
+const Y_IS_ODD_FLAG: u8 = 0x80;
+
 #[derive(Copy, Clone, Debug, PartialEq, Default)]
 pub struct GrumpkinPoint<T> {
     pub x: T,
@@ -107,6 +115,81 @@ impl GrumpkinPointAffine<Fr> {
     pub fn random(rng: &mut impl RngCore) -> Self {
         GrumpkinPoint::from(G1::random(rng)).into()
     }
+
+    /// Encodes the point as the little-endian representation of `x`, with the most significant bit
+    /// storing the parity of `y`.
+    ///
+    /// Grumpkin has prime order, so it admits neither a Montgomery nor a twisted Edwards model. The
+    /// short Weierstrass form is the only one supported and this encoding is the interop format.
+    pub fn to_compressed(&self) -> [u8; 32] {
+        let mut bytes = self.x.to_repr();
+        if bool::from(self.y.is_odd()) {
+            bytes[31] |= Y_IS_ODD_FLAG;
+        }
+        bytes
+    }
+
+    /// Decodes a point produced by [`Self::to_compressed`]. Returns `None` if `x` is not a canonical
+    /// field element or if there is no point on the curve with such `x` and `y` parity.
+    pub fn from_compressed(bytes: &[u8; 32]) -> Option<Self> {
+        let mut x_bytes = *bytes;
+        let y_is_odd = x_bytes[31] & Y_IS_ODD_FLAG != 0;
+        x_bytes[31] &= !Y_IS_ODD_FLAG;
+
+        Self::from_x_and_parity(x_bytes, y_is_odd)
+    }
+
+    /// Encodes the point as the little-endian representations of `x` and `y` (`x || y`).
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&self.x.to_repr());
+        bytes[32..].copy_from_slice(&self.y.to_repr());
+        bytes
+    }
+
+    /// Decodes a point produced by [`Self::to_bytes`]. Returns `None` if any coordinate is not a
+    /// canonical field element or if the point is not on the curve.
+    pub fn from_bytes(bytes: &[u8; 64]) -> Option<Self> {
+        let (x, y) = bytes.split_at(32);
+        let x: Fr = Option::from(Fr::from_repr(x.try_into().ok()?))?;
+        let y: Fr = Option::from(Fr::from_repr(y.try_into().ok()?))?;
+
+        let point = Self::new(x, y);
+        is_point_on_curve_affine(point).then_some(point)
+    }
+
+    /// Same as [`Self::to_compressed`], but with the parity of `y` stored in a separate (last)
+    /// byte, which is `1` for odd `y` and `0` otherwise.
+    pub fn to_bytes_compressed(&self) -> [u8; 33] {
+        let mut bytes = [0u8; 33];
+        bytes[..32].copy_from_slice(&self.x.to_repr());
+        bytes[32] = bool::from(self.y.is_odd()) as u8;
+        bytes
+    }
+
+    /// Decodes a point produced by [`Self::to_bytes_compressed`]. Returns `None` if `x` is not a
+    /// canonical field element, if the parity byte is neither `0` nor `1` or if there is no point on
+    /// the curve with such `x` and `y` parity.
+    pub fn from_bytes_compressed(bytes: &[u8; 33]) -> Option<Self> {
+        let y_is_odd = match bytes[32] {
+            0 => false,
+            1 => true,
+            _ => return None,
+        };
+        Self::from_x_and_parity(bytes[..32].try_into().ok()?, y_is_odd)
+    }
+
+    fn from_x_and_parity(x_bytes: [u8; 32], y_is_odd: bool) -> Option<Self> {
+        let x: Fr = Option::from(Fr::from_repr(x_bytes))?;
+        let y: Fr = Option::from(quadratic_residue_given_x_affine(x).sqrt())?;
+        let y = if bool::from(y.is_odd()) == y_is_odd {
+            y
+        } else {
+            -y
+        };
+
+        (bool::from(y.is_odd()) == y_is_odd).then(|| Self::new(x, y))
+    }
 }
 
 impl<T: Field> From<GrumpkinPoint<T>> for GrumpkinPointAffine<T> {
@@ -118,3 +201,94 @@ impl<T: Field> From<GrumpkinPoint<T>> for GrumpkinPointAffine<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{GrumpkinPointAffine, Y_IS_ODD_FLAG};
+    use crate::{
+        curve_arithmetic::{is_point_on_curve_affine, quadratic_residue_given_x_affine},
+        rng, Field, Fr,
+    };
+
+    #[test]
+    fn compression_round_trips() {
+        let mut rng = rng();
+        for _ in 0..16 {
+            let point = GrumpkinPointAffine::random(&mut rng);
+            let decompressed = GrumpkinPointAffine::from_compressed(&point.to_compressed());
+
+            assert_eq!(decompressed, Some(point));
+        }
+    }
+
+    #[test]
+    fn flipped_parity_decompresses_to_negated_point() {
+        let point = GrumpkinPointAffine::random(&mut rng());
+        let mut bytes = point.to_compressed();
+        bytes[31] ^= Y_IS_ODD_FLAG;
+
+        let decompressed = GrumpkinPointAffine::from_compressed(&bytes).unwrap();
+
+        assert_eq!(decompressed, GrumpkinPointAffine::new(point.x, -point.y));
+        assert!(is_point_on_curve_affine(decompressed));
+    }
+
+    #[test]
+    fn x_without_point_on_curve_is_rejected() {
+        let x = (0u64..)
+            .map(Fr::from)
+            .find(|x| GrumpkinPointAffine::from_compressed(&x.to_bytes()).is_none())
+            .unwrap();
+
+        let mut odd = x.to_bytes();
+        odd[31] |= Y_IS_ODD_FLAG;
+        assert!(GrumpkinPointAffine::from_compressed(&odd).is_none());
+    }
+
+    #[test]
+    fn non_canonical_x_is_rejected() {
+        assert!(GrumpkinPointAffine::from_compressed(&[0x7f; 32]).is_none());
+    }
+
+    #[test]
+    fn byte_encodings_round_trip() {
+        let mut rng = rng();
+        for _ in 0..16 {
+            let point = GrumpkinPointAffine::random(&mut rng);
+
+            assert_eq!(
+                GrumpkinPointAffine::from_bytes(&point.to_bytes()),
+                Some(point)
+            );
+            assert_eq!(
+                GrumpkinPointAffine::from_bytes_compressed(&point.to_bytes_compressed()),
+                Some(point)
+            );
+        }
+    }
+
+    #[test]
+    fn point_off_curve_is_rejected() {
+        let point = GrumpkinPointAffine::random(&mut rng());
+        let mut bytes = GrumpkinPointAffine::new(point.x, point.y + Fr::ONE).to_bytes();
+        assert!(GrumpkinPointAffine::from_bytes(&bytes).is_none());
+
+        bytes[63] = 0xff; // Non-canonical `y`.
+        assert!(GrumpkinPointAffine::from_bytes(&bytes).is_none());
+    }
+
+    #[test]
+    fn compressed_bytes_with_non_residue_or_invalid_parity_are_rejected() {
+        let x = (0u64..)
+            .map(Fr::from)
+            .find(|&x| bool::from(quadratic_residue_given_x_affine(x).sqrt().is_none()))
+            .unwrap();
+        let mut bytes = [0u8; 33];
+        bytes[..32].copy_from_slice(&x.to_bytes());
+        assert!(GrumpkinPointAffine::from_bytes_compressed(&bytes).is_none());
+
+        let mut bytes = GrumpkinPointAffine::random(&mut rng()).to_bytes_compressed();
+        bytes[32] = 2;
+        assert!(GrumpkinPointAffine::from_bytes_compressed(&bytes).is_none());
+    }
+}
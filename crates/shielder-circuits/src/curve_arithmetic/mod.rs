@@ -99,6 +99,31 @@ pub fn normalize_point<T: Field>(p: GrumpkinPoint<T>) -> GrumpkinPoint<T> {
     GrumpkinPoint::new(x * z_inv, y * z_inv, T::ONE)
 }
 
+/// Same as [`normalize_point`] applied to every point, but with a single field inversion (using
+/// Montgomery's batch inversion trick). Points at infinity (`z = 0`) are left untouched.
+pub fn normalize_points<T: Field>(points: &mut [GrumpkinPoint<T>]) {
+    // `prefix_products[i]` is the product of all non-zero `z`s before `points[i]`.
+    let mut prefix_products = Vec::with_capacity(points.len());
+    let mut product = T::ONE;
+    for point in points.iter() {
+        prefix_products.push(product);
+        if point.z != T::ZERO {
+            product *= point.z;
+        }
+    }
+
+    // Invariant: `inverse` is the inverse of the product of all non-zero `z`s up to `points[i]`.
+    let mut inverse = product.invert().unwrap();
+    for (point, prefix_product) in points.iter_mut().zip(prefix_products).rev() {
+        if point.z == T::ZERO {
+            continue;
+        }
+        let z_inv = inverse * prefix_product;
+        inverse *= point.z;
+        *point = GrumpkinPoint::new(point.x * z_inv, point.y * z_inv, T::ONE);
+    }
+}
+
 pub fn scalar_multiply<S: CurveScalarField + PartialEq>(
     input: GrumpkinPoint<S>,
     scalar_bits: [S; FIELD_BITS],
@@ -116,6 +141,136 @@ pub fn scalar_multiply<S: CurveScalarField + PartialEq>(
     result
 }
 
+/// The table `[G, 2G, 4G, ..., 2^(FIELD_BITS - 1) G]` for the Grumpkin generator `G`, with all points
+/// normalized. Multiplying `G` by a scalar then takes only additions (see
+/// `FixedBaseMultiplyChip::fixed_base_multiply`).
+pub fn precompute_generator_table() -> [GrumpkinPoint<Fr>; FIELD_BITS] {
+    let mut power = GrumpkinPoint::generator();
+    let mut table = [power; FIELD_BITS];
+    for entry in table.iter_mut().skip(1) {
+        power = point_double(power);
+        *entry = power;
+    }
+    normalize_points(&mut table);
+    table
+}
+
+/// Same as [`scalar_multiply`], but performs the same sequence of operations for every scalar: the
+/// sum is always computed and then arithmetically selected (`result + bit · (sum - result)`), so
+/// there is no branch on the scalar bits (which must be binary). Note that only the sequence of
+/// operations is scalar-independent; the arithmetic of `S` itself gives no constant-time guarantees.
+pub fn scalar_multiply_ct<S: CurveScalarField>(
+    input: GrumpkinPoint<S>,
+    scalar_bits: [S; FIELD_BITS],
+) -> GrumpkinPoint<S> {
+    let mut result = GrumpkinPoint::zero();
+
+    let mut doubled = input;
+
+    for bit in scalar_bits {
+        let sum = points_add(result.clone(), doubled.clone());
+        result = conditional_select(bit, sum, result);
+        doubled = point_double(doubled);
+    }
+    result
+}
+
+/// Returns `if_one` if `bit` is `1` and `if_zero` if it is `0`, without branching on `bit`.
+fn conditional_select<S: CurveScalarField>(
+    bit: S,
+    if_one: GrumpkinPoint<S>,
+    if_zero: GrumpkinPoint<S>,
+) -> GrumpkinPoint<S> {
+    let select = |if_one: S, if_zero: S| if_zero.clone() + bit.clone() * (if_one - if_zero);
+    GrumpkinPoint::new(
+        select(if_one.x, if_zero.x),
+        select(if_one.y, if_zero.y),
+        select(if_one.z, if_zero.z),
+    )
+}
+
+static_assertions::const_assert_eq!(FIELD_BITS % 2, 0);
+
+/// Same as [`scalar_multiply`], but consumes the bits in LE pairs (windows): for every window, one of
+/// `0`, `P`, `2P` and `3P` is added to the result and `P` is quadrupled. The resulting projective
+/// coordinates are exactly the ones computed in-circuit by `ScalarMultiplyGateW2`.
+pub fn scalar_multiply_windowed<S: CurveScalarField>(
+    input: GrumpkinPoint<S>,
+    scalar_bits: [S; FIELD_BITS],
+) -> GrumpkinPoint<S> {
+    let mut result = GrumpkinPoint::zero();
+    let mut base = input;
+
+    for window in scalar_bits.chunks_exact(2) {
+        let doubled = point_double(base.clone());
+        let tripled = points_add(doubled.clone(), base.clone());
+        let addend = select_window(
+            [window[0].clone(), window[1].clone()],
+            base,
+            doubled.clone(),
+            tripled,
+        );
+
+        result = points_add(result, addend);
+        base = point_double(doubled);
+    }
+    result
+}
+
+/// Returns `0`, `P`, `2P` or `3P` (passed as `base`, `doubled` and `tripled`) for the window
+/// `[bit0, bit1]` (LE), i.e., `(bit0 + 2 · bit1) · P`. The choice is made arithmetically, so it
+/// also works for `Expression`s (as long as the bits are binary).
+pub fn select_window<S: CurveScalarField>(
+    [bit0, bit1]: [S; 2],
+    base: GrumpkinPoint<S>,
+    doubled: GrumpkinPoint<S>,
+    tripled: GrumpkinPoint<S>,
+) -> GrumpkinPoint<S> {
+    let not_bit0 = S::one() - bit0.clone();
+    let not_bit1 = S::one() - bit1.clone();
+
+    let none = not_bit0.clone() * not_bit1.clone();
+    let only_bit0 = bit0.clone() * not_bit1;
+    let only_bit1 = not_bit0 * bit1.clone();
+    let both = bit0 * bit1;
+
+    let combine = |base: S, doubled: S, tripled: S| {
+        only_bit0.clone() * base + only_bit1.clone() * doubled + both.clone() * tripled
+    };
+
+    // The point at infinity is `(0, 1, 0)`.
+    GrumpkinPoint::new(
+        combine(base.x, doubled.x, tripled.x),
+        none + combine(base.y, doubled.y, tripled.y),
+        combine(base.z, doubled.z, tripled.z),
+    )
+}
+
+/// Computes `sum_i scalars[i] * points[i]`, where the scalars are given as little-endian bits (as
+/// in [`scalar_multiply`]). The points are processed simultaneously (most significant bit first),
+/// so that the doublings are shared.
+pub fn multi_scalar_multiply<S: CurveScalarField + PartialEq>(
+    points: &[GrumpkinPoint<S>],
+    scalars: &[[S; FIELD_BITS]],
+) -> GrumpkinPoint<S> {
+    assert_eq!(points.len(), scalars.len());
+
+    let mut result = GrumpkinPoint::zero();
+    if points.is_empty() {
+        return result;
+    }
+
+    for bit_index in (0..FIELD_BITS).rev() {
+        result = point_double(result);
+        for (point, scalar_bits) in points.iter().zip(scalars) {
+            if scalar_bits[bit_index] == S::one() {
+                result = points_add(result, point.clone());
+            }
+        }
+    }
+    result
+}
+
 pub fn projective_to_affine<T>(p: GrumpkinPoint<T>, z_inverse: T) -> GrumpkinPointAffine<T>
 where
     T: Mul<Output = T> + Clone,
@@ -138,6 +293,42 @@ pub fn quadratic_residue_given_x_affine<S: CurveScalarField>(x: S) -> S {
     x.clone() * x.clone() * x + S::b()
 }
 
+/// Returns the number of bits needed to represent `x`, i.e., the index of its highest set bit plus
+/// one (0 for zero).
+pub fn bit_length(x: Fr) -> u32 {
+    x.to_repr()
+        .iter()
+        .enumerate()
+        .rev()
+        .find(|(_, byte)| **byte != 0)
+        .map(|(index, byte)| 8 * index as u32 + (8 - byte.leading_zeros()))
+        .unwrap_or(0)
+}
+
+/// Splits `scalar` into `window_bits`-bit digits, least significant first, so that
+/// `scalar = Σ digits[i] · 2^(i · window_bits)`. There are `ceil(FIELD_BITS / window_bits)` digits
+/// (the most significant one is padded with zeros). For `window_bits = 2`, these are the windows
+/// consumed by [`scalar_multiply_windowed`].
+///
+/// Panics unless `1 <= window_bits <= 8`.
+pub fn scalar_windows(scalar: Fr, window_bits: usize) -> Vec<u8> {
+    assert!(
+        (1..=8).contains(&window_bits),
+        "window digits must fit in a byte"
+    );
+
+    let repr = scalar.to_repr();
+    let bit = |index: usize| (repr[index / 8] >> (index % 8)) & 1;
+
+    (0..FIELD_BITS.div_ceil(window_bits))
+        .map(|window| {
+            (window * window_bits..((window + 1) * window_bits).min(FIELD_BITS))
+                .enumerate()
+                .fold(0, |digit, (i, index)| digit | (bit(index) << i))
+        })
+        .collect()
+}
+
 /// Given a 32 byte array with a field element generates a random `id` such
 /// that it's hash, along with a specific salt is the x-coordinate of a point on the (affine) Grumpkin curve:
 /// For x = hash(id, SALT), y = sqrt(x^3 + b) P(x,y) \in E
@@ -212,6 +403,42 @@ pub fn le_bits_to_field_element<T: PrimeField<Repr = [u8; 32]>>(le_bits: &[Fr; F
     T::from_repr(bitwise_representation).expect("not a field element representation")
 }
 
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Writes `x` to `buf` as a `0x`-prefixed, big-endian, lowercase hex string (the same format as
+/// the `Debug` implementation of `Fr`), without allocating.
+pub fn fr_to_hex_nostd(x: &Fr, buf: &mut [u8; 66]) {
+    buf[0] = b'0';
+    buf[1] = b'x';
+    for (i, byte) in x.to_repr().iter().rev().enumerate() {
+        buf[2 + 2 * i] = HEX_DIGITS[(byte >> 4) as usize];
+        buf[3 + 2 * i] = HEX_DIGITS[(byte & 0x0f) as usize];
+    }
+}
+
+/// Parses a string written by `fr_to_hex_nostd` (upper case digits are accepted as well). Returns
+/// `None` if `buf` is not a valid hex string or encodes a value not smaller than the modulus.
+pub fn fr_from_hex_nostd(buf: &[u8; 66]) -> Option<Fr> {
+    fn nibble(digit: u8) -> Option<u8> {
+        match digit {
+            b'0'..=b'9' => Some(digit - b'0'),
+            b'a'..=b'f' => Some(digit - b'a' + 10),
+            b'A'..=b'F' => Some(digit - b'A' + 10),
+            _ => None,
+        }
+    }
+
+    if &buf[..2] != b"0x" {
+        return None;
+    }
+
+    let mut repr = [0u8; 32];
+    for (i, digits) in buf[2..].chunks(2).enumerate() {
+        repr[31 - i] = (nibble(digits[0])? << 4) | nibble(digits[1])?;
+    }
+    Fr::from_repr(repr).into()
+}
+
 /// newtype wrapper to account for the fact we do not have PartialEq nor Eq traits on the Value type
 #[derive(Clone, Debug)]
 pub struct V(pub Value);
@@ -251,6 +478,8 @@ impl Mul for V {
 
 #[cfg(test)]
 mod tests {
+    use std::format;
+
     use halo2_proofs::{
         arithmetic::CurveExt,
         halo2curves::{
@@ -261,12 +490,17 @@ mod tests {
         },
     };
 
-    use super::{field_element_to_le_bits, GrumpkinPointAffine};
+    use super::{
+        bit_length, field_element_to_le_bits, fr_from_hex_nostd, fr_to_hex_nostd,
+        GrumpkinPointAffine,
+    };
     use crate::{
         chips::viewing_key,
+        consts::FIELD_BITS,
         curve_arithmetic::{
-            self, grumpkin_point::GrumpkinPoint, normalize_point, point_double, points_add,
-            scalar_multiply,
+            self, grumpkin_point::GrumpkinPoint, multi_scalar_multiply, normalize_point,
+            normalize_points, point_double, points_add, scalar_multiply, scalar_multiply_ct,
+            scalar_multiply_windowed, scalar_windows,
         },
         le_bits_to_field_element, rng, Field,
     };
@@ -288,6 +522,133 @@ mod tests {
         assert_eq!(expected, result);
     }
 
+    #[test]
+    fn constant_sequence_scalar_multiply_matches_branching_one() {
+        let mut rng = rng();
+
+        for _ in 0..8 {
+            let p = G1::random(&mut rng);
+            let bits = field_element_to_le_bits(Fr::random(&mut rng));
+
+            let expected = normalize_point(scalar_multiply(p.into(), bits));
+            let result = normalize_point(scalar_multiply_ct(p.into(), bits));
+
+            assert_eq!(expected, result);
+        }
+    }
+
+    #[test]
+    fn windowed_scalar_multiply_matches_bitwise_one() {
+        let mut rng = rng();
+
+        for _ in 0..8 {
+            let p = G1::random(&mut rng);
+            let bits = field_element_to_le_bits(Fr::random(&mut rng));
+
+            let expected = normalize_point(scalar_multiply(p.into(), bits));
+            let result = normalize_point(scalar_multiply_windowed(p.into(), bits));
+
+            assert_eq!(expected, result);
+        }
+    }
+
+    fn recompose_windows(digits: &[u8], window_bits: usize) -> Fr {
+        let base = Fr::from(1u64 << window_bits);
+        digits.iter().rev().fold(Fr::ZERO, |acc, &digit| {
+            acc * base + Fr::from(u64::from(digit))
+        })
+    }
+
+    #[test]
+    fn scalar_windows_recompose_to_scalar() {
+        let mut rng = rng();
+
+        for window_bits in [2, 4] {
+            for scalar in [Fr::ZERO, Fr::ONE, -Fr::ONE, Fr::random(&mut rng)] {
+                let digits = scalar_windows(scalar, window_bits);
+
+                assert_eq!(digits.len(), FIELD_BITS.div_ceil(window_bits));
+                assert!(digits.iter().all(|&digit| digit < 1 << window_bits));
+                assert_eq!(recompose_windows(&digits, window_bits), scalar);
+            }
+        }
+    }
+
+    #[test]
+    fn two_bit_windows_match_bit_pairs() {
+        let scalar = Fr::random(rng());
+        let bits = field_element_to_le_bits(scalar);
+
+        for (digit, pair) in scalar_windows(scalar, 2)
+            .into_iter()
+            .zip(bits.chunks_exact(2))
+        {
+            assert_eq!(Fr::from(u64::from(digit)), pair[0] + pair[1].double());
+        }
+    }
+
+    #[test]
+    fn multi_scalar_multiply_matches_sum_of_scalar_multiplications() {
+        let mut rng = rng();
+
+        let points: [GrumpkinPoint<Fr>; 3] = core::array::from_fn(|_| G1::random(&mut rng).into());
+        let scalars: [_; 3] =
+            core::array::from_fn(|_| field_element_to_le_bits(Fr::random(&mut rng)));
+
+        let expected = points
+            .iter()
+            .zip(scalars)
+            .fold(GrumpkinPoint::zero(), |acc, (point, bits)| {
+                points_add(acc, scalar_multiply(*point, bits))
+            });
+        let result = multi_scalar_multiply(&points, &scalars);
+
+        assert_eq!(normalize_point(expected), normalize_point(result));
+    }
+
+    #[test]
+    fn multi_scalar_multiply_of_nothing_is_zero() {
+        assert_eq!(multi_scalar_multiply::<Fr>(&[], &[]), GrumpkinPoint::zero());
+    }
+
+    #[test]
+    fn batch_normalization_matches_normalizing_each_point() {
+        let mut rng = rng();
+
+        let mut points: std::vec::Vec<GrumpkinPoint<Fr>> = (0..100)
+            .map(|_| {
+                let p: GrumpkinPoint<Fr> = G1::random(&mut rng).into();
+                let z = Fr::random(&mut rng);
+                GrumpkinPoint::new(p.x * z, p.y * z, p.z * z)
+            })
+            .collect();
+        points.insert(50, GrumpkinPoint::zero());
+
+        let expected: std::vec::Vec<_> = points
+            .iter()
+            .map(|&p| {
+                if p.z == Fr::ZERO {
+                    p
+                } else {
+                    normalize_point(p)
+                }
+            })
+            .collect();
+        normalize_points(&mut points);
+
+        assert_eq!(points, expected);
+    }
+
+    #[test]
+    fn bit_length_of_edge_values() {
+        assert_eq!(bit_length(Fr::ZERO), 0);
+        assert_eq!(bit_length(Fr::ONE), 1);
+        assert_eq!(bit_length(Fr::from(2).pow([112])), 113);
+        assert_eq!(bit_length(Fr::from(2).pow([112]) - Fr::ONE), 112);
+        // The modulus has 254 bits.
+        assert_eq!(bit_length(-Fr::ONE), Fr::NUM_BITS);
+    }
+
     #[test]
     fn adding_random_points() {
         let rng = rng();
@@ -299,6 +660,38 @@ mod tests {
         assert_eq!(expected, points_add(p.into(), q.into()));
     }
 
+    #[test]
+    fn adding_random_points_stays_on_curve() {
+        let mut rng = rng();
+        let on_curve = |p: GrumpkinPoint<Fr>| {
+            curve_arithmetic::is_point_on_curve_affine(normalize_point(p).into())
+        };
+
+        for _ in 0..64 {
+            let p: GrumpkinPoint<Fr> = G1::random(&mut rng).into();
+            let q: GrumpkinPoint<Fr> = G1::random(&mut rng).into();
+
+            assert!(on_curve(points_add(p, q)));
+            // Doubling through the addition formula.
+            assert!(on_curve(points_add(p, p)));
+            assert_eq!(
+                normalize_point(points_add(p, p)),
+                normalize_point(point_double(p))
+            );
+            // Adding the identity, on either side.
+            for sum in [
+                points_add(p, GrumpkinPoint::zero()),
+                points_add(GrumpkinPoint::zero(), p),
+            ] {
+                assert!(on_curve(sum));
+                assert_eq!(normalize_point(sum), normalize_point(p));
+            }
+            // Adding the inverse yields the identity.
+            let inverse = GrumpkinPoint::new(p.x, -p.y, p.z);
+            assert_eq!(points_add(p, inverse).z, Fr::ZERO);
+        }
+    }
+
     #[test]
     fn doubling_random_point() {
         let rng = rng();
@@ -372,4 +765,80 @@ mod tests {
         let bits = field_element_to_le_bits(field_element);
         assert_eq!(field_element, le_bits_to_field_element(&bits));
     }
+
+    #[test]
+    fn hex_nostd_roundtrip() {
+        let mut rng = rng();
+        let mut buf = [0u8; 66];
+
+        for value in [Fr::ZERO, Fr::ONE, -Fr::ONE, Fr::random(&mut rng)] {
+            fr_to_hex_nostd(&value, &mut buf);
+
+            assert_eq!(
+                core::str::from_utf8(&buf).expect("hex is valid UTF-8"),
+                format!("{value:?}")
+            );
+            assert_eq!(fr_from_hex_nostd(&buf), Some(value));
+        }
+    }
+
+    #[test]
+    fn hex_nostd_rejects_invalid_input() {
+        let mut buf = [0u8; 66];
+        fr_to_hex_nostd(&Fr::ONE, &mut buf);
+
+        let mut no_prefix = buf;
+        no_prefix[1] = b'0';
+        assert_eq!(fr_from_hex_nostd(&no_prefix), None);
+
+        let mut bad_digit = buf;
+        bad_digit[10] = b'g';
+        assert_eq!(fr_from_hex_nostd(&bad_digit), None);
+
+        // All bits set exceeds the modulus.
+        let mut too_big = [b'f'; 66];
+        too_big[..2].copy_from_slice(b"0x");
+        assert_eq!(fr_from_hex_nostd(&too_big), None);
+    }
+
+    #[test]
+    fn le_bits_roundtrip_for_small_and_boundary_values() {
+        let boundary = [
+            -Fr::ONE,
+            -Fr::from(2),
+            Fr::from(2).pow([253]),
+            Fr::from(2).pow([253]) - Fr::ONE,
+            Fr::from(u64::MAX),
+            Fr::from_u128(u128::MAX),
+        ];
+
+        for value in (0..1024).map(Fr::from).chain(boundary) {
+            let bits = field_element_to_le_bits(value);
+            assert_eq!(value, le_bits_to_field_element(&bits));
+        }
+
+        for value in [-Fq::ONE, Fq::from(2).pow([253])] {
+            let bits = field_element_to_le_bits(value);
+            assert_eq!(value, le_bits_to_field_element(&bits));
+        }
+    }
+
+    #[test]
+    fn le_bits_start_with_least_significant_bit() {
+        let one = field_element_to_le_bits(Fr::ONE);
+        assert_eq!(one[0], Fr::ONE);
+        assert!(one[1..].iter().all(|bit| *bit == Fr::ZERO));
+
+        // 6 = 0b110
+        let six = field_element_to_le_bits(Fr::from(6));
+        assert_eq!(six[..3], [Fr::ZERO, Fr::ONE, Fr::ONE]);
+        assert!(six[3..].iter().all(|bit| *bit == Fr::ZERO));
+
+        let mut top_bit = [Fr::ZERO; FIELD_BITS];
+        top_bit[FIELD_BITS - 1] = Fr::ONE;
+        assert_eq!(
+            le_bits_to_field_element::<Fr>(&top_bit),
+            Fr::from(2).pow([FIELD_BITS as u64 - 1])
+        );
+    }
 }
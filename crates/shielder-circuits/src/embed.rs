@@ -21,6 +21,23 @@ pub trait Embed {
     ) -> Result<Self::Embedded, Error>;
 }
 
+/// Assigns `values` as constants, i.e., the cells are fixed by the circuit rather than witnessed by
+/// the prover. Meant for tables known when designing the circuit (e.g., a public set of allowed
+/// values for a membership check).
+#[cfg(feature = "chip-elgamal")]
+pub fn embed_constants<const N: usize>(
+    synthesizer: &mut impl Synthesizer,
+    values: [Fr; N],
+    annotation: impl Into<String>,
+) -> Result<[AssignedCell; N], Error> {
+    let annotation = annotation.into();
+    let mut embedded = vec![];
+    for (i, value) in values.into_iter().enumerate() {
+        embedded.push(synthesizer.assign_constant(format!("{}[{}]", annotation, i), value)?);
+    }
+    Ok(embedded.try_into().map_err(|_| ()).expect("Safe unwrap"))
+}
+
 impl Embed for Fr {
     type Embedded = AssignedCell;
 
@@ -71,6 +88,20 @@ impl Embed for V {
     }
 }
 
+/// Identity embedding: the cell is already assigned, so it is returned as is (no new cell is created
+/// and `annotation` is ignored). Lets generic code over `T: Embed` accept both values and cells.
+impl Embed for AssignedCell {
+    type Embedded = AssignedCell;
+
+    fn embed(
+        &self,
+        _synthesizer: &mut impl Synthesizer,
+        _annotation: impl Into<String>,
+    ) -> Result<Self::Embedded, Error> {
+        Ok(self.clone())
+    }
+}
+
 impl<E: Embed, const N: usize> Embed for [E; N] {
     type Embedded = [E::Embedded; N];
 
@@ -144,3 +175,195 @@ where
         })
     }
 }
+
+#[cfg(all(test, feature = "chip-elgamal"))]
+mod tests {
+    use std::vec;
+
+    use halo2_proofs::{
+        circuit::{floor_planner::V1, Layouter},
+        dev::MockProver,
+        plonk::{Advice, Circuit, ConstraintSystem, Error, Fixed},
+    };
+    use macros::embeddable;
+    use strum_macros::{EnumCount, EnumIter};
+
+    use super::{embed_constants, Embed};
+    use crate::{
+        column_pool::{ColumnPool, PreSynthesisPhase},
+        instance_wrapper::InstanceWrapper,
+        synthesizer::{create_synthesizer, Synthesizer},
+        AssignedCell, Fr, Value,
+    };
+
+    const CONSTANTS: [u64; 2] = [10, 20];
+
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, EnumIter, EnumCount)]
+    enum TestInstance {
+        First,
+        Second,
+    }
+
+    #[derive(Clone, Debug, Default)]
+    struct ConstantsCircuit;
+
+    impl Circuit<Fr> for ConstantsCircuit {
+        type Config = (
+            ColumnPool<Advice, PreSynthesisPhase>,
+            InstanceWrapper<TestInstance>,
+        );
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let instance = InstanceWrapper::new(meta);
+
+            let mut advice_pool = ColumnPool::<Advice, _>::new();
+            advice_pool.ensure_capacity(meta, 1);
+            let mut fixed_pool = ColumnPool::<Fixed, _>::new();
+            fixed_pool.ensure_capacity(meta, 1);
+
+            (advice_pool.conclude_configuration(), instance)
+        }
+
+        fn synthesize(
+            &self,
+            (pool, instance): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let pool = pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+
+            let [first, second] =
+                embed_constants(&mut synthesizer, CONSTANTS.map(Fr::from), "constants")?;
+
+            instance.constrain_cells(
+                &mut synthesizer,
+                [(first, TestInstance::First), (second, TestInstance::Second)],
+            )
+        }
+    }
+
+    #[derive(Clone, Debug, Default)]
+    #[embeddable(receiver = "Pair<Value>", embedded = "Pair<AssignedCell>")]
+    struct Pair<T>(T, T);
+
+    #[derive(Clone, Debug, Default)]
+    struct PairCircuit(Pair<Value>);
+
+    impl Circuit<Fr> for PairCircuit {
+        type Config = (
+            ColumnPool<Advice, PreSynthesisPhase>,
+            InstanceWrapper<TestInstance>,
+        );
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            ConstantsCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            (pool, instance): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let pool = pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+
+            let Pair(first, second) = self.0.embed(&mut synthesizer, "pair")?;
+
+            instance.constrain_cells(
+                &mut synthesizer,
+                [(first, TestInstance::First), (second, TestInstance::Second)],
+            )
+        }
+    }
+
+    fn embed_generic<T: Embed<Embedded = AssignedCell>>(
+        synthesizer: &mut impl Synthesizer,
+        items: &[T; 2],
+    ) -> Result<[AssignedCell; 2], Error> {
+        items.embed(synthesizer, "items")
+    }
+
+    #[derive(Clone, Debug, Default)]
+    struct PassThroughCircuit([Value; 2]);
+
+    impl Circuit<Fr> for PassThroughCircuit {
+        type Config = (
+            ColumnPool<Advice, PreSynthesisPhase>,
+            InstanceWrapper<TestInstance>,
+        );
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            ConstantsCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            (pool, instance): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let pool = pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+
+            let cells = embed_generic(&mut synthesizer, &self.0)?;
+            let [first, second] = embed_generic(&mut synthesizer, &cells)?;
+
+            instance.constrain_cells(
+                &mut synthesizer,
+                [(first, TestInstance::First), (second, TestInstance::Second)],
+            )
+        }
+    }
+
+    fn verify_circuit(circuit: &impl Circuit<Fr>, public_input: [u64; 2]) -> bool {
+        MockProver::run(4, circuit, vec![public_input.map(Fr::from).to_vec()])
+            .expect("Mock prover should run successfully")
+            .verify()
+            .is_ok()
+    }
+
+    fn verify(public_input: [u64; 2]) -> bool {
+        verify_circuit(&ConstantsCircuit, public_input)
+    }
+
+    #[test]
+    fn constants_are_assigned() {
+        assert!(verify(CONSTANTS));
+    }
+
+    #[test]
+    fn cells_are_constrained_to_constants() {
+        assert!(!verify([11, 20]));
+        assert!(!verify([10, 21]));
+    }
+
+    #[test]
+    fn tuple_struct_fields_are_embedded_in_order() {
+        let circuit = PairCircuit(Pair(Value::known(Fr::from(10)), Value::known(Fr::from(20))));
+
+        assert!(verify_circuit(&circuit, [10, 20]));
+        assert!(!verify_circuit(&circuit, [20, 10]));
+    }
+
+    #[test]
+    fn assigned_cells_are_embedded_as_themselves() {
+        let circuit = PassThroughCircuit([Value::known(Fr::from(10)), Value::known(Fr::from(20))]);
+
+        assert!(verify_circuit(&circuit, [10, 20]));
+        assert!(!verify_circuit(&circuit, [10, 21]));
+    }
+}
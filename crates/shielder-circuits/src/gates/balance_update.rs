@@ -0,0 +1,190 @@
+use alloc::{vec, vec::Vec};
+
+use halo2_proofs::{
+    plonk::{Advice, Column, ConstraintSystem, Constraints, Error, Expression, Selector},
+    poly::Rotation,
+};
+use macros::embeddable;
+
+use crate::{
+    column_pool::{AccessColumn, ColumnPool, ConfigPhase},
+    embed::Embed,
+    gates::{ensure_unique_columns, Gate},
+    synthesizer::Synthesizer,
+    AssignedCell, Field, Fr,
+};
+
+/// Represents the relation: `balance_new = balance_old + selected · update_value`, where `selected`
+/// is a binary value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BalanceUpdateGate {
+    balance_old: Column<Advice>,
+    balance_new: Column<Advice>,
+    selected: Column<Advice>,
+    update_value: Column<Advice>,
+    selector: Selector,
+}
+
+#[derive(Clone, Debug, Default)]
+#[embeddable(
+    receiver = "BalanceUpdateGateInput<Fr>",
+    embedded = "BalanceUpdateGateInput<AssignedCell>"
+)]
+pub struct BalanceUpdateGateInput<T> {
+    pub balance_old: T,
+    pub balance_new: T,
+    pub selected: T,
+    pub update_value: T,
+}
+
+const SELECTOR_OFFSET: usize = 0;
+const ADVICE_OFFSET: usize = 0;
+const GATE_NAME: &str = "Balance update gate";
+
+impl Gate for BalanceUpdateGate {
+    type Input = BalanceUpdateGateInput<AssignedCell>;
+    type Advice = [Column<Advice>; 4];
+
+    /// The gate operates on four advice columns `O`, `N`, `S` and `U`. It enforces that:
+    ///
+    /// `S[x] · (1 - S[x]) = 0` and `N[x] - O[x] - S[x] · U[x] = 0`, where `x` is the row where the
+    /// gate is enabled.
+    fn create_gate_custom(cs: &mut ConstraintSystem<Fr>, advice: Self::Advice) -> Self {
+        ensure_unique_columns(&advice);
+        let [balance_old, balance_new, selected, update_value] = advice;
+        let selector = cs.selector();
+
+        cs.create_gate(GATE_NAME, |vc| {
+            let old = vc.query_advice(balance_old, Rotation(ADVICE_OFFSET as i32));
+            let new = vc.query_advice(balance_new, Rotation(ADVICE_OFFSET as i32));
+            let bit = vc.query_advice(selected, Rotation(ADVICE_OFFSET as i32));
+            let update = vc.query_advice(update_value, Rotation(ADVICE_OFFSET as i32));
+
+            Constraints::with_selector(
+                vc.query_selector(selector),
+                vec![
+                    (
+                        "selected is a binary value",
+                        bit.clone() * (Expression::Constant(Fr::ONE) - bit.clone()),
+                    ),
+                    (
+                        "balance_new = balance_old + selected · update_value",
+                        new - old - bit * update,
+                    ),
+                ],
+            )
+        });
+
+        Self {
+            balance_old,
+            balance_new,
+            selected,
+            update_value,
+            selector,
+        }
+    }
+
+    fn apply_in_new_region(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        input: Self::Input,
+    ) -> Result<(), Error> {
+        synthesizer.record_enabled_gate(GATE_NAME);
+        synthesizer.assign_region(
+            || GATE_NAME,
+            |mut region| {
+                self.selector.enable(&mut region, SELECTOR_OFFSET)?;
+
+                input.balance_old.copy_advice(
+                    || "balance_old",
+                    &mut region,
+                    self.balance_old,
+                    ADVICE_OFFSET,
+                )?;
+                input.balance_new.copy_advice(
+                    || "balance_new",
+                    &mut region,
+                    self.balance_new,
+                    ADVICE_OFFSET,
+                )?;
+                input.selected.copy_advice(
+                    || "selected",
+                    &mut region,
+                    self.selected,
+                    ADVICE_OFFSET,
+                )?;
+                input.update_value.copy_advice(
+                    || "update_value",
+                    &mut region,
+                    self.update_value,
+                    ADVICE_OFFSET,
+                )?;
+
+                Ok(())
+            },
+        )
+    }
+
+    fn organize_advice_columns(
+        pool: &mut ColumnPool<Advice, ConfigPhase>,
+        cs: &mut ConstraintSystem<Fr>,
+    ) -> Self::Advice {
+        pool.ensure_capacity(cs, 4);
+        pool.get_column_array()
+    }
+
+    fn advice_columns(&self) -> Vec<Column<Advice>> {
+        vec![
+            self.balance_old,
+            self.balance_new,
+            self.selected,
+            self.update_value,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::halo2curves::bn256::Fr;
+
+    use crate::gates::{
+        balance_update::{BalanceUpdateGate, BalanceUpdateGateInput},
+        test_utils::verify,
+    };
+
+    fn input(old: u64, new: u64, selected: u64, update: u64) -> BalanceUpdateGateInput<Fr> {
+        BalanceUpdateGateInput {
+            balance_old: Fr::from(old),
+            balance_new: Fr::from(new),
+            selected: Fr::from(selected),
+            update_value: Fr::from(update),
+        }
+    }
+
+    #[test]
+    fn selected_balance_is_updated() {
+        assert!(verify::<BalanceUpdateGate, _>(input(10, 15, 1, 5)).is_ok());
+    }
+
+    #[test]
+    fn unselected_balance_is_unchanged() {
+        assert!(verify::<BalanceUpdateGate, _>(input(10, 10, 0, 5)).is_ok());
+    }
+
+    #[test]
+    fn unselected_balance_cannot_change() {
+        let errors = verify::<BalanceUpdateGate, _>(input(10, 15, 0, 5))
+            .expect_err("Verification should fail");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("balance_new = balance_old + selected · update_value"));
+    }
+
+    #[test]
+    fn non_binary_selection_fails() {
+        // `10 + 2 · 5 = 20`, but the selection is not binary.
+        let errors = verify::<BalanceUpdateGate, _>(input(10, 20, 2, 5))
+            .expect_err("Verification should fail");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("selected is a binary value"));
+    }
+}
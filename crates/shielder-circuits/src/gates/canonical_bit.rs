@@ -0,0 +1,215 @@
+use alloc::{vec, vec::Vec};
+
+use halo2_proofs::{
+    plonk::{Advice, Column, ConstraintSystem, Constraints, Error, Expression, Selector},
+    poly::Rotation,
+};
+use macros::embeddable;
+
+use crate::{
+    column_pool::{AccessColumn, ColumnPool, ConfigPhase},
+    embed::Embed,
+    gates::{ensure_unique_columns, Gate},
+    synthesizer::Synthesizer,
+    AssignedCell, Field, Fr,
+};
+
+/// Represents a single step of comparing a bit decomposition with the bits of a constant (`p - 1`
+/// in practice), starting from the most significant bit: `bit` is a binary value, and given that
+/// `prefix_equal` (binary) tells whether all the more significant bits are equal to the constant,
+/// `prefix_equal_next` tells the same including `bit`. A decomposition is forbidden to exceed the
+/// constant at the first position where it differs, i.e., `bit = 1` with `modulus_bit = 0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CanonicalBitGate {
+    bit: Column<Advice>,
+    modulus_bit: Column<Advice>,
+    prefix_equal: Column<Advice>,
+    prefix_equal_next: Column<Advice>,
+    selector: Selector,
+}
+
+#[derive(Clone, Debug, Default)]
+#[embeddable(
+    receiver = "CanonicalBitGateInput<Fr>",
+    embedded = "CanonicalBitGateInput<AssignedCell>"
+)]
+pub struct CanonicalBitGateInput<T> {
+    pub bit: T,
+    /// The bit of the constant at the same position. Expected to be constrained to a constant.
+    pub modulus_bit: T,
+    pub prefix_equal: T,
+    pub prefix_equal_next: T,
+}
+
+const SELECTOR_OFFSET: usize = 0;
+const ADVICE_OFFSET: usize = 0;
+const GATE_NAME: &str = "Canonical bit gate";
+
+impl Gate for CanonicalBitGate {
+    type Input = CanonicalBitGateInput<AssignedCell>;
+    type Advice = [Column<Advice>; 4];
+
+    /// The gate operates on four advice columns `B`, `M`, `E` and `E'`. It enforces that:
+    ///
+    /// `B[x] · (1 - B[x]) = 0`, `(1 - M[x]) · E[x] · B[x] = 0` and
+    /// `E'[x] - E[x] · (1 - M[x] + M[x] · B[x]) = 0`, where `x` is the row where the gate is enabled.
+    fn create_gate_custom(cs: &mut ConstraintSystem<Fr>, advice: Self::Advice) -> Self {
+        ensure_unique_columns(&advice);
+        let [bit, modulus_bit, prefix_equal, prefix_equal_next] = advice;
+        let selector = cs.selector();
+
+        cs.create_gate(GATE_NAME, |vc| {
+            let one = Expression::Constant(Fr::ONE);
+            let b = vc.query_advice(bit, Rotation(ADVICE_OFFSET as i32));
+            let m = vc.query_advice(modulus_bit, Rotation(ADVICE_OFFSET as i32));
+            let e = vc.query_advice(prefix_equal, Rotation(ADVICE_OFFSET as i32));
+            let e_next = vc.query_advice(prefix_equal_next, Rotation(ADVICE_OFFSET as i32));
+
+            Constraints::with_selector(
+                vc.query_selector(selector),
+                vec![
+                    (
+                        "bit is a binary value",
+                        b.clone() * (one.clone() - b.clone()),
+                    ),
+                    (
+                        "bit does not exceed the modulus bit",
+                        (one.clone() - m.clone()) * e.clone() * b.clone(),
+                    ),
+                    (
+                        "prefix_equal_next = prefix_equal · (bit == modulus_bit)",
+                        e_next - e * (one - m.clone() + m * b),
+                    ),
+                ],
+            )
+        });
+
+        Self {
+            bit,
+            modulus_bit,
+            prefix_equal,
+            prefix_equal_next,
+            selector,
+        }
+    }
+
+    fn apply_in_new_region(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        input: Self::Input,
+    ) -> Result<(), Error> {
+        synthesizer.record_enabled_gate(GATE_NAME);
+        synthesizer.assign_region(
+            || GATE_NAME,
+            |mut region| {
+                self.selector.enable(&mut region, SELECTOR_OFFSET)?;
+
+                input
+                    .bit
+                    .copy_advice(|| "bit", &mut region, self.bit, ADVICE_OFFSET)?;
+                input.modulus_bit.copy_advice(
+                    || "modulus_bit",
+                    &mut region,
+                    self.modulus_bit,
+                    ADVICE_OFFSET,
+                )?;
+                input.prefix_equal.copy_advice(
+                    || "prefix_equal",
+                    &mut region,
+                    self.prefix_equal,
+                    ADVICE_OFFSET,
+                )?;
+                input.prefix_equal_next.copy_advice(
+                    || "prefix_equal_next",
+                    &mut region,
+                    self.prefix_equal_next,
+                    ADVICE_OFFSET,
+                )?;
+
+                Ok(())
+            },
+        )
+    }
+
+    fn organize_advice_columns(
+        pool: &mut ColumnPool<Advice, ConfigPhase>,
+        cs: &mut ConstraintSystem<Fr>,
+    ) -> Self::Advice {
+        pool.ensure_capacity(cs, 4);
+        pool.get_column_array()
+    }
+
+    fn advice_columns(&self) -> Vec<Column<Advice>> {
+        vec![
+            self.bit,
+            self.modulus_bit,
+            self.prefix_equal,
+            self.prefix_equal_next,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::halo2curves::bn256::Fr;
+
+    use crate::gates::{
+        canonical_bit::{CanonicalBitGate, CanonicalBitGateInput},
+        test_utils::verify,
+    };
+
+    fn input(
+        bit: u64,
+        modulus_bit: u64,
+        prefix_equal: u64,
+        prefix_equal_next: u64,
+    ) -> CanonicalBitGateInput<Fr> {
+        CanonicalBitGateInput {
+            bit: Fr::from(bit),
+            modulus_bit: Fr::from(modulus_bit),
+            prefix_equal: Fr::from(prefix_equal),
+            prefix_equal_next: Fr::from(prefix_equal_next),
+        }
+    }
+
+    #[test]
+    fn equal_bits_keep_the_prefix_equal() {
+        assert!(verify::<CanonicalBitGate, _>(input(1, 1, 1, 1)).is_ok());
+        assert!(verify::<CanonicalBitGate, _>(input(0, 0, 1, 1)).is_ok());
+    }
+
+    #[test]
+    fn smaller_bit_ends_the_equal_prefix() {
+        assert!(verify::<CanonicalBitGate, _>(input(0, 1, 1, 0)).is_ok());
+    }
+
+    #[test]
+    fn any_bit_is_allowed_after_the_equal_prefix() {
+        assert!(verify::<CanonicalBitGate, _>(input(1, 0, 0, 0)).is_ok());
+        assert!(verify::<CanonicalBitGate, _>(input(1, 1, 0, 0)).is_ok());
+    }
+
+    #[test]
+    fn bit_exceeding_the_modulus_fails() {
+        let errors =
+            verify::<CanonicalBitGate, _>(input(1, 0, 1, 1)).expect_err("Verification should fail");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("bit does not exceed the modulus bit"));
+    }
+
+    #[test]
+    fn incorrect_prefix_equal_next_fails() {
+        let errors =
+            verify::<CanonicalBitGate, _>(input(0, 1, 1, 1)).expect_err("Verification should fail");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("prefix_equal_next = prefix_equal · (bit == modulus_bit)"));
+    }
+
+    #[test]
+    fn non_binary_bit_fails() {
+        let errors =
+            verify::<CanonicalBitGate, _>(input(2, 1, 0, 0)).expect_err("Verification should fail");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("bit is a binary value"));
+    }
+}
@@ -0,0 +1,198 @@
+use alloc::{vec, vec::Vec};
+
+use halo2_proofs::{
+    plonk::{Advice, Column, ConstraintSystem, Constraints, Error, Expression, Selector},
+    poly::Rotation,
+};
+use macros::embeddable;
+
+use crate::{
+    column_pool::{AccessColumn, ColumnPool, ConfigPhase},
+    embed::Embed,
+    gates::{ensure_unique_columns, Gate},
+    synthesizer::Synthesizer,
+    AssignedCell, Field, Fr,
+};
+
+/// Represents a single step of incrementing a number written in base `BASE`, starting from the
+/// least significant digit: `digit + carry_in = next_digit + BASE · carry_out`, with `carry_out`
+/// being a binary value.
+///
+/// The gate does not check that the digits are below `BASE`; this must be enforced separately.
+/// Given that, chaining applications (with `carry_out` becoming the next `carry_in`, the first
+/// `carry_in` set to 1 and the last `carry_out` set to 0) constrains `next_digits` to represent the
+/// successor of `digits`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DigitIncrementGate<const BASE: usize> {
+    digit: Column<Advice>,
+    carry_in: Column<Advice>,
+    next_digit: Column<Advice>,
+    carry_out: Column<Advice>,
+    selector: Selector,
+}
+
+#[derive(Clone, Debug, Default)]
+#[embeddable(
+    receiver = "DigitIncrementGateInput<Fr>",
+    embedded = "DigitIncrementGateInput<AssignedCell>"
+)]
+pub struct DigitIncrementGateInput<T> {
+    pub digit: T,
+    pub carry_in: T,
+    pub next_digit: T,
+    pub carry_out: T,
+}
+
+const SELECTOR_OFFSET: usize = 0;
+const ADVICE_OFFSET: usize = 0;
+const GATE_NAME: &str = "Digit increment gate";
+
+impl<const BASE: usize> Gate for DigitIncrementGate<BASE> {
+    type Input = DigitIncrementGateInput<AssignedCell>;
+    type Advice = [Column<Advice>; 4];
+
+    /// The gate operates on four advice columns `D`, `C`, `D'` and `C'`. It enforces that:
+    ///
+    /// `C'[x] · (1 - C'[x]) = 0` and `D[x] + C[x] - D'[x] - BASE · C'[x] = 0`, where `x` is the row
+    /// where the gate is enabled.
+    fn create_gate_custom(cs: &mut ConstraintSystem<Fr>, advice: Self::Advice) -> Self {
+        ensure_unique_columns(&advice);
+        let [digit, carry_in, next_digit, carry_out] = advice;
+        let selector = cs.selector();
+
+        cs.create_gate(GATE_NAME, |vc| {
+            let d = vc.query_advice(digit, Rotation(ADVICE_OFFSET as i32));
+            let c = vc.query_advice(carry_in, Rotation(ADVICE_OFFSET as i32));
+            let d_next = vc.query_advice(next_digit, Rotation(ADVICE_OFFSET as i32));
+            let c_next = vc.query_advice(carry_out, Rotation(ADVICE_OFFSET as i32));
+
+            Constraints::with_selector(
+                vc.query_selector(selector),
+                vec![
+                    (
+                        "carry_out is a binary value",
+                        c_next.clone() * (Expression::Constant(Fr::ONE) - c_next.clone()),
+                    ),
+                    (
+                        "digit + carry_in = next_digit + BASE · carry_out",
+                        d + c - d_next - Expression::Constant(Fr::from(BASE as u64)) * c_next,
+                    ),
+                ],
+            )
+        });
+
+        Self {
+            digit,
+            carry_in,
+            next_digit,
+            carry_out,
+            selector,
+        }
+    }
+
+    fn apply_in_new_region(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        input: Self::Input,
+    ) -> Result<(), Error> {
+        synthesizer.record_enabled_gate(GATE_NAME);
+        synthesizer.assign_region(
+            || GATE_NAME,
+            |mut region| {
+                self.selector.enable(&mut region, SELECTOR_OFFSET)?;
+
+                input
+                    .digit
+                    .copy_advice(|| "digit", &mut region, self.digit, ADVICE_OFFSET)?;
+                input.carry_in.copy_advice(
+                    || "carry_in",
+                    &mut region,
+                    self.carry_in,
+                    ADVICE_OFFSET,
+                )?;
+                input.next_digit.copy_advice(
+                    || "next_digit",
+                    &mut region,
+                    self.next_digit,
+                    ADVICE_OFFSET,
+                )?;
+                input.carry_out.copy_advice(
+                    || "carry_out",
+                    &mut region,
+                    self.carry_out,
+                    ADVICE_OFFSET,
+                )?;
+
+                Ok(())
+            },
+        )
+    }
+
+    fn organize_advice_columns(
+        pool: &mut ColumnPool<Advice, ConfigPhase>,
+        cs: &mut ConstraintSystem<Fr>,
+    ) -> Self::Advice {
+        pool.ensure_capacity(cs, 4);
+        pool.get_column_array()
+    }
+
+    fn advice_columns(&self) -> Vec<Column<Advice>> {
+        vec![self.digit, self.carry_in, self.next_digit, self.carry_out]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::halo2curves::bn256::Fr;
+
+    use crate::gates::{
+        digit_increment::{DigitIncrementGate, DigitIncrementGateInput},
+        test_utils::verify,
+    };
+
+    fn input(
+        digit: u64,
+        carry_in: u64,
+        next_digit: u64,
+        carry_out: u64,
+    ) -> DigitIncrementGateInput<Fr> {
+        DigitIncrementGateInput {
+            digit: Fr::from(digit),
+            carry_in: Fr::from(carry_in),
+            next_digit: Fr::from(next_digit),
+            carry_out: Fr::from(carry_out),
+        }
+    }
+
+    #[test]
+    fn increment_without_carry_passes() {
+        assert!(verify::<DigitIncrementGate<7>, _>(input(3, 1, 4, 0)).is_ok());
+        assert!(verify::<DigitIncrementGate<7>, _>(input(3, 0, 3, 0)).is_ok());
+    }
+
+    #[test]
+    fn increment_with_carry_passes() {
+        assert!(verify::<DigitIncrementGate<7>, _>(input(6, 1, 0, 1)).is_ok());
+    }
+
+    #[test]
+    fn wrong_next_digit_fails() {
+        let errors = verify::<DigitIncrementGate<7>, _>(input(3, 1, 5, 0))
+            .expect_err("Verification should fail");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("digit + carry_in = next_digit + BASE · carry_out"));
+    }
+
+    #[test]
+    fn non_binary_carry_fails() {
+        // `6 + 1 = -6 + 7 · 2`, but the carry must be binary.
+        let forged = DigitIncrementGateInput {
+            next_digit: -Fr::from(6),
+            ..input(6, 1, 0, 2)
+        };
+        let errors =
+            verify::<DigitIncrementGate<7>, _>(forged).expect_err("Verification should fail");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("carry_out is a binary value"));
+    }
+}
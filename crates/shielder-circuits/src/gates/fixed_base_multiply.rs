@@ -0,0 +1,292 @@
+use alloc::{vec, vec::Vec};
+
+use halo2_proofs::{
+    arithmetic::Field,
+    halo2curves::bn256::Fr,
+    plonk::{Advice, Column, ConstraintSystem, Constraints, Error, Expression, Selector},
+    poly::Rotation,
+};
+use macros::embeddable;
+
+use super::{assign_grumpkin_advices, assign_grumpkin_point_at_infinity, copy_grumpkin_advices};
+use crate::{
+    column_pool::{AccessColumn, ColumnPool, ConfigPhase},
+    consts::FIELD_BITS,
+    curve_arithmetic::{self, GrumpkinPoint},
+    embed::Embed,
+    gates::{ensure_unique_columns, Gate},
+    synthesizer::Synthesizer,
+    AssignedCell,
+};
+
+/// Variant of [`ScalarMultiplyGate`](super::scalar_multiply::ScalarMultiplyGate) for a fixed base
+/// point: the successive doublings of the base are not computed, but supplied as a table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FixedBaseMultiplyGate {
+    pub selector: Selector,
+    pub scalar_bits: Column<Advice>,
+    pub table: [Column<Advice>; 3],
+    pub result: [Column<Advice>; 3],
+}
+
+#[derive(Clone, Debug)]
+#[embeddable(
+    receiver = "FixedBaseMultiplyGateInput<Fr>",
+    embedded = "FixedBaseMultiplyGateInput<crate::AssignedCell>"
+)]
+pub struct FixedBaseMultiplyGateInput<T> {
+    pub scalar_bits: [T; FIELD_BITS],
+    /// `table[i]` is `2^i` times the base point.
+    pub table: [GrumpkinPoint<T>; FIELD_BITS],
+    pub final_result: GrumpkinPoint<T>,
+}
+
+impl<T: Default + Copy> Default for FixedBaseMultiplyGateInput<T> {
+    fn default() -> Self {
+        Self {
+            scalar_bits: [T::default(); FIELD_BITS],
+            table: [GrumpkinPoint::default(); FIELD_BITS],
+            final_result: GrumpkinPoint::default(),
+        }
+    }
+}
+
+const SELECTOR_OFFSET: i32 = 0;
+const ADVICE_OFFSET: i32 = 0;
+const GATE_NAME: &str = "Fixed base multiply gate";
+
+impl Gate for FixedBaseMultiplyGate {
+    type Input = FixedBaseMultiplyGateInput<AssignedCell>;
+
+    type Advice = (
+        Column<Advice>,      // scalar_bits
+        [Column<Advice>; 3], // table
+        [Column<Advice>; 3], // result
+    );
+
+    /// The gate operates on an advice column `scalar_bit`, a triplet (representing projective
+    /// coordinates of a point on an EC) of `table` advice columns and a triplet of `result` columns.
+    ///
+    /// Constraints:
+    ///
+    /// result[i + 1] = table[i] + result[i] if bit == 1
+    ///               = result[i]            if bit == 0
+    /// bit \in {0,1}
+    fn create_gate_custom(
+        cs: &mut ConstraintSystem<Fr>,
+        (scalar_bits, table, result): Self::Advice,
+    ) -> Self {
+        ensure_unique_columns(&[vec![scalar_bits], table.to_vec(), result.to_vec()].concat());
+        let selector = cs.selector();
+
+        cs.create_gate(GATE_NAME, |vc| {
+            let bit = vc.query_advice(scalar_bits, Rotation(ADVICE_OFFSET));
+
+            let table_x = vc.query_advice(table[0], Rotation(ADVICE_OFFSET));
+            let table_y = vc.query_advice(table[1], Rotation(ADVICE_OFFSET));
+            let table_z = vc.query_advice(table[2], Rotation(ADVICE_OFFSET));
+
+            let result_x = vc.query_advice(result[0], Rotation(ADVICE_OFFSET));
+            let result_y = vc.query_advice(result[1], Rotation(ADVICE_OFFSET));
+            let result_z = vc.query_advice(result[2], Rotation(ADVICE_OFFSET));
+
+            let next_result_x = vc.query_advice(result[0], Rotation(ADVICE_OFFSET + 1));
+            let next_result_y = vc.query_advice(result[1], Rotation(ADVICE_OFFSET + 1));
+            let next_result_z = vc.query_advice(result[2], Rotation(ADVICE_OFFSET + 1));
+
+            let GrumpkinPoint {
+                x: added_x,
+                y: added_y,
+                z: added_z,
+            } = curve_arithmetic::points_add(
+                GrumpkinPoint::new(result_x.clone(), result_y.clone(), result_z.clone()),
+                GrumpkinPoint::new(table_x, table_y, table_z),
+            );
+
+            Constraints::with_selector(
+                vc.query_selector(selector),
+                vec![
+                    (
+                        "bit is a binary value",
+                        bit.clone() * (Expression::Constant(Fr::one()) - bit.clone()),
+                    ),
+                    (
+                        "x: next_result = table + result if bit == 1 else result",
+                        next_result_x - bit.clone() * (added_x - result_x.clone()) - result_x,
+                    ),
+                    (
+                        "y: next_result = table + result if bit == 1 else result",
+                        next_result_y - bit.clone() * (added_y - result_y.clone()) - result_y,
+                    ),
+                    (
+                        "z: next_result = table + result if bit == 1 else result",
+                        next_result_z - bit * (added_z - result_z.clone()) - result_z,
+                    ),
+                ],
+            )
+        });
+
+        Self {
+            selector,
+            scalar_bits,
+            table,
+            result,
+        }
+    }
+
+    fn apply_in_new_region(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        FixedBaseMultiplyGateInput {
+            scalar_bits,
+            table,
+            final_result,
+        }: Self::Input,
+    ) -> Result<(), Error> {
+        synthesizer.record_enabled_gate(GATE_NAME);
+        synthesizer.assign_region(
+            || GATE_NAME,
+            |mut region| {
+                let mut result = assign_grumpkin_point_at_infinity(
+                    "initial result",
+                    &mut region,
+                    self.result,
+                    ADVICE_OFFSET as usize,
+                )?;
+
+                for (i, (bit, table_point)) in scalar_bits.iter().zip(table.iter()).enumerate() {
+                    self.selector
+                        .enable(&mut region, SELECTOR_OFFSET as usize + i)?;
+
+                    bit.copy_advice(
+                        || alloc::format!("bit[{i}]"),
+                        &mut region,
+                        self.scalar_bits,
+                        ADVICE_OFFSET as usize + i,
+                    )?;
+                    let table_point = copy_grumpkin_advices(
+                        table_point,
+                        &alloc::format!("table[{i}]"),
+                        &mut region,
+                        self.table,
+                        ADVICE_OFFSET as usize + i,
+                    )?;
+
+                    if i == FIELD_BITS - 1 {
+                        copy_grumpkin_advices(
+                            &final_result,
+                            "final result",
+                            &mut region,
+                            self.result,
+                            ADVICE_OFFSET as usize + i + 1,
+                        )?;
+                        break;
+                    }
+
+                    let mut is_one = false;
+                    bit.value().map(|f| {
+                        is_one = Fr::ONE == *f;
+                    });
+
+                    let next_result = if is_one {
+                        curve_arithmetic::points_add(result.into(), table_point.into())
+                    } else {
+                        result.into()
+                    };
+                    result = assign_grumpkin_advices(
+                        &next_result,
+                        "result",
+                        &mut region,
+                        self.result,
+                        ADVICE_OFFSET as usize + i + 1,
+                    )?;
+                }
+
+                Ok(())
+            },
+        )
+    }
+
+    fn organize_advice_columns(
+        pool: &mut ColumnPool<Advice, ConfigPhase>,
+        cs: &mut ConstraintSystem<Fr>,
+    ) -> Self::Advice {
+        pool.ensure_capacity(cs, 7);
+        (
+            pool.get_column(0),                                           // scalar_bits
+            [pool.get_column(1), pool.get_column(2), pool.get_column(3)], // table
+            [pool.get_column(4), pool.get_column(5), pool.get_column(6)], // result
+        )
+    }
+
+    fn advice_columns(&self) -> Vec<Column<Advice>> {
+        let mut columns = vec![self.scalar_bits];
+        columns.extend(self.table);
+        columns.extend(self.result);
+        columns
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use halo2_proofs::{
+        dev::{MockProver, VerifyFailure},
+        halo2curves::{bn256::Fr, ff::PrimeField},
+    };
+    use rand::RngCore;
+
+    use super::*;
+    use crate::{
+        curve_arithmetic::precompute_generator_table, field_element_to_le_bits,
+        gates::test_utils::OneGateCircuit, rng,
+    };
+
+    fn verify(input: FixedBaseMultiplyGateInput<Fr>) -> Result<(), Vec<VerifyFailure>> {
+        let circuit = OneGateCircuit::<FixedBaseMultiplyGate, _>::new(input);
+        MockProver::run(10, &circuit, vec![])
+            .expect("Mock prover should run")
+            .verify()
+    }
+
+    fn input(n: Fr) -> FixedBaseMultiplyGateInput<Fr> {
+        let table = precompute_generator_table();
+        let scalar_bits = field_element_to_le_bits(n);
+        let final_result = table
+            .iter()
+            .zip(scalar_bits)
+            .filter(|(_, bit)| *bit == Fr::ONE)
+            .fold(GrumpkinPoint::zero(), |acc, (point, _)| {
+                curve_arithmetic::points_add(acc, *point)
+            });
+
+        FixedBaseMultiplyGateInput {
+            scalar_bits,
+            table,
+            final_result,
+        }
+    }
+
+    #[test]
+    fn multiply_by_random_scalar() {
+        let n = Fr::from_u128(rng().next_u64() as u128);
+        assert!(verify(input(n)).is_ok());
+    }
+
+    #[test]
+    fn invalid_result() {
+        let mut input = input(Fr::from_u128(3));
+        input.final_result = input.table[2];
+
+        assert!(verify(input).is_err());
+    }
+
+    #[test]
+    fn bit_is_invalid() {
+        let mut input = input(Fr::from_u128(3));
+        input.scalar_bits[0] = Fr::from_u128(2);
+
+        assert!(verify(input).is_err());
+    }
+}
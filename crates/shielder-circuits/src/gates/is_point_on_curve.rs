@@ -1,4 +1,4 @@
-use alloc::vec;
+use alloc::{vec, vec::Vec};
 
 use halo2_proofs::{
     arithmetic::CurveExt,
@@ -62,6 +62,7 @@ impl Gate for IsPointOnCurveGate {
         synthesizer: &mut impl Synthesizer,
         point: Self::Input,
     ) -> Result<(), Error> {
+        synthesizer.record_enabled_gate(GATE_NAME);
         synthesizer.assign_region(
             || GATE_NAME,
             |mut region| {
@@ -88,6 +89,10 @@ impl Gate for IsPointOnCurveGate {
         pool.ensure_capacity(cs, 3);
         [pool.get_column(0), pool.get_column(1), pool.get_column(2)]
     }
+
+    fn advice_columns(&self) -> Vec<Column<Advice>> {
+        self.point.to_vec()
+    }
 }
 
 #[cfg(test)]
@@ -117,6 +122,29 @@ mod tests {
         assert!(verify(point).is_ok());
     }
 
+    #[test]
+    fn random_projective_representations_pass() {
+        let mut rng = rng();
+
+        for _ in 0..8 {
+            let GrumpkinPoint { x, y, z } = GrumpkinPoint::random(&mut rng);
+            // Every non-zero multiple of the coordinates represents the same point.
+            let scale = Fr::random(&mut rng);
+            assert!(verify(GrumpkinPoint::new(x * scale, y * scale, z * scale)).is_ok());
+        }
+    }
+
+    #[test]
+    fn perturbed_z_fails() {
+        let mut rng = rng();
+
+        for _ in 0..8 {
+            let point: GrumpkinPoint<Fr> = GrumpkinPoint::random(&mut rng);
+            let perturbed = GrumpkinPoint::new(point.x, point.y, point.z + Fr::ONE);
+            assert!(verify(perturbed).is_err());
+        }
+    }
+
     #[test]
     fn incorrect_inputs() {
         let point: GrumpkinPoint<Fr> =
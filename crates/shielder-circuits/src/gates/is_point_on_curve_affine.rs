@@ -1,4 +1,4 @@
-use alloc::vec;
+use alloc::{vec, vec::Vec};
 
 use halo2_proofs::{
     arithmetic::CurveExt,
@@ -59,6 +59,7 @@ impl Gate for IsPointOnCurveAffineGate {
         synthesizer: &mut impl Synthesizer,
         point: Self::Input,
     ) -> Result<(), Error> {
+        synthesizer.record_enabled_gate(GATE_NAME);
         synthesizer.assign_region(
             || GATE_NAME,
             |mut region| {
@@ -85,6 +86,10 @@ impl Gate for IsPointOnCurveAffineGate {
         pool.ensure_capacity(cs, 2);
         [pool.get_column(0), pool.get_column(1)]
     }
+
+    fn advice_columns(&self) -> Vec<Column<Advice>> {
+        self.point.to_vec()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,190 @@
+use alloc::{vec, vec::Vec};
+
+use halo2_proofs::{
+    plonk::{Advice, Column, ConstraintSystem, Error, Expression, Selector},
+    poly::Rotation,
+};
+use macros::embeddable;
+
+use crate::{
+    column_pool::{AccessColumn, ColumnPool, ConfigPhase},
+    embed::Embed,
+    gates::{ensure_unique_columns, Gate},
+    synthesizer::Synthesizer,
+    AssignedCell, Fr,
+};
+
+/// Number of terms (besides the accumulator) that a single application of the gate takes.
+pub const LINEAR_EQUATION_TERMS: usize = 7;
+
+/// Represents the relation: `2^N · accumulator + Σ 2^i · term_i = result` (for `i < N`), where `N`
+/// is `LINEAR_EQUATION_TERMS`.
+///
+/// Chaining applications (with `result` becoming the next `accumulator`) recomposes an arbitrary
+/// number of bits, `N` at a time, starting with the most significant chunk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LinearEquationGate {
+    accumulator: Column<Advice>,
+    terms: [Column<Advice>; LINEAR_EQUATION_TERMS],
+    result: Column<Advice>,
+    selector: Selector,
+}
+
+#[derive(Clone, Debug, Default)]
+#[embeddable(
+    receiver = "LinearEquationGateInput<Fr>",
+    embedded = "LinearEquationGateInput<AssignedCell>"
+)]
+pub struct LinearEquationGateInput<T> {
+    pub accumulator: T,
+    /// Terms in LE order, i.e., `terms[i]` has the coefficient `2^i`.
+    pub terms: [T; LINEAR_EQUATION_TERMS],
+    pub result: T,
+}
+
+const SELECTOR_OFFSET: usize = 0;
+const ADVICE_OFFSET: usize = 0;
+const GATE_NAME: &str = "Linear equation gate";
+
+impl Gate for LinearEquationGate {
+    type Input = LinearEquationGateInput<AssignedCell>;
+    type Advice = (
+        Column<Advice>,                          // accumulator
+        [Column<Advice>; LINEAR_EQUATION_TERMS], // terms
+        Column<Advice>,                          // result
+    );
+
+    /// The gate operates on `N + 2` advice columns: `acc`, `T_0`, …, `T_{N-1}` and `res`. It
+    /// enforces that:
+    ///
+    /// `2^N · acc[x] + Σ 2^i · T_i[x] = res[x]`, where `x` is the row where the gate is enabled.
+    fn create_gate_custom(
+        cs: &mut ConstraintSystem<Fr>,
+        (accumulator, terms, result): Self::Advice,
+    ) -> Self {
+        ensure_unique_columns(&[vec![accumulator, result], terms.to_vec()].concat());
+        let selector = cs.selector();
+
+        cs.create_gate(GATE_NAME, |vc| {
+            let selector = vc.query_selector(selector);
+            let accumulator = vc.query_advice(accumulator, Rotation(ADVICE_OFFSET as i32));
+            let result = vc.query_advice(result, Rotation(ADVICE_OFFSET as i32));
+
+            let combination = terms.iter().enumerate().fold(
+                Expression::Constant(Fr::from(1u64 << LINEAR_EQUATION_TERMS)) * accumulator,
+                |sum, (i, &term)| {
+                    let term = vc.query_advice(term, Rotation(ADVICE_OFFSET as i32));
+                    sum + Expression::Constant(Fr::from(1u64 << i)) * term
+                },
+            );
+
+            vec![selector * (combination - result)]
+        });
+
+        Self {
+            accumulator,
+            terms,
+            result,
+            selector,
+        }
+    }
+
+    fn apply_in_new_region(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        input: Self::Input,
+    ) -> Result<(), Error> {
+        synthesizer.record_enabled_gate(GATE_NAME);
+        synthesizer.assign_region(
+            || GATE_NAME,
+            |mut region| {
+                self.selector.enable(&mut region, SELECTOR_OFFSET)?;
+
+                input.accumulator.copy_advice(
+                    || "accumulator",
+                    &mut region,
+                    self.accumulator,
+                    ADVICE_OFFSET,
+                )?;
+                for (i, term) in input.terms.iter().enumerate() {
+                    term.copy_advice(
+                        || alloc::format!("term_{i}"),
+                        &mut region,
+                        self.terms[i],
+                        ADVICE_OFFSET,
+                    )?;
+                }
+                input
+                    .result
+                    .copy_advice(|| "result", &mut region, self.result, ADVICE_OFFSET)?;
+
+                Ok(())
+            },
+        )
+    }
+
+    fn organize_advice_columns(
+        pool: &mut ColumnPool<Advice, ConfigPhase>,
+        cs: &mut ConstraintSystem<Fr>,
+    ) -> Self::Advice {
+        pool.ensure_capacity(cs, LINEAR_EQUATION_TERMS + 2);
+        let accumulator = pool.get_column(0);
+        let terms = core::array::from_fn(|i| pool.get_column(i + 1));
+        let result = pool.get_column(LINEAR_EQUATION_TERMS + 1);
+        (accumulator, terms, result)
+    }
+
+    fn advice_columns(&self) -> Vec<Column<Advice>> {
+        [vec![self.accumulator, self.result], self.terms.to_vec()].concat()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::{halo2curves::bn256::Fr, plonk::ConstraintSystem};
+
+    use crate::gates::{
+        linear_equation::{LinearEquationGate, LinearEquationGateInput, LINEAR_EQUATION_TERMS},
+        test_utils::verify,
+        Gate as _,
+    };
+
+    fn input(
+        accumulator: u64,
+        terms: [u64; LINEAR_EQUATION_TERMS],
+        result: u64,
+    ) -> LinearEquationGateInput<Fr> {
+        LinearEquationGateInput {
+            accumulator: Fr::from(accumulator),
+            terms: terms.map(Fr::from),
+            result: Fr::from(result),
+        }
+    }
+
+    #[test]
+    #[should_panic = "Advice columns must be unique"]
+    fn gate_creation_with_repeated_columns_fails() {
+        let mut cs = ConstraintSystem::<Fr>::default();
+        let column = cs.advice_column();
+        let terms = core::array::from_fn(|_| cs.advice_column());
+
+        LinearEquationGate::create_gate_custom(&mut cs, (column, terms, column));
+    }
+
+    #[test]
+    fn correct_combination_passes() {
+        // 3 · 2^7 + 0b0100101
+        let input = input(3, [1, 0, 1, 0, 0, 1, 0], 3 * 128 + 37);
+        assert!(verify::<LinearEquationGate, _>(input).is_ok());
+    }
+
+    #[test]
+    fn incorrect_combination_fails() {
+        let errors = verify::<LinearEquationGate, _>(input(3, [1, 0, 1, 0, 0, 1, 0], 3 * 128 + 38))
+            .expect_err("Verification should fail");
+        assert_eq!(errors.len(), 1);
+        assert!(
+            errors[0].contains("Constraint 0 in gate 0 ('Linear equation gate') is not satisfied")
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use alloc::vec;
+use alloc::{vec, vec::Vec};
 
 use halo2_proofs::{
     plonk::{Advice, Column, ConstraintSystem, Error, Selector},
@@ -75,6 +75,7 @@ impl<const N: usize> Gate for MembershipGate<N> {
         synthesizer: &mut impl Synthesizer,
         input: Self::Input,
     ) -> Result<(), Error> {
+        synthesizer.record_enabled_gate(GATE_NAME);
         synthesizer.assign_region(
             || GATE_NAME,
             |mut region| {
@@ -110,6 +111,12 @@ impl<const N: usize> Gate for MembershipGate<N> {
         let needle_advice = pool.get_column(N);
         (needle_advice, haystack_advice)
     }
+
+    fn advice_columns(&self) -> Vec<Column<Advice>> {
+        let mut columns = self.haystack_advice.to_vec();
+        columns.push(self.needle_advice);
+        columns
+    }
 }
 
 #[cfg(test)]
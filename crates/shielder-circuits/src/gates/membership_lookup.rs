@@ -0,0 +1,215 @@
+use alloc::{vec, vec::Vec};
+
+use halo2_proofs::{
+    plonk::{Advice, Column, ConstraintSystem, Error, Selector},
+    poly::Rotation,
+};
+use macros::embeddable;
+
+use crate::{
+    column_pool::{AccessColumn, ColumnPool, ConfigPhase},
+    embed::Embed,
+    gates::{ensure_unique_columns, Gate},
+    synthesizer::Synthesizer,
+    AssignedCell, Fr,
+};
+
+/// Represents the relation: `needle ∈ haystack`, for a haystack of arbitrary length. Unlike
+/// `MembershipGate`, the cost does not grow with the degree of the constraint system: the haystack
+/// is laid out in a single column, which (together with its selector) forms a lookup table.
+///
+/// All the applications of the gate within a circuit share the same table. To keep them apart, every
+/// row of an application carries its `tag`, which is part of the looked-up tuple: the needle is
+/// proven to belong to the union of the haystacks passed with the same tag. The tag is expected to
+/// be constrained to a constant, so that the prover cannot move a needle to another haystack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MembershipLookupGate {
+    tag_advice: Column<Advice>,
+    needle_advice: Column<Advice>,
+    haystack_advice: Column<Advice>,
+    needle_selector: Selector,
+    haystack_selector: Selector,
+}
+
+#[derive(Clone, Debug, Default)]
+#[embeddable(
+    receiver = "MembershipLookupGateInput<Fr>",
+    embedded = "MembershipLookupGateInput<AssignedCell>"
+)]
+pub struct MembershipLookupGateInput<T> {
+    pub tag: T,
+    pub needle: T,
+    pub haystack: Vec<T>,
+}
+
+const NEEDLE_OFFSET: usize = 0;
+const GATE_NAME: &str = "Membership lookup";
+
+impl Gate for MembershipLookupGate {
+    type Input = MembershipLookupGateInput<AssignedCell>;
+    type Advice = (Column<Advice>, Column<Advice>, Column<Advice>);
+
+    /// The gate operates on three advice columns `tag`, `needle` and `haystack` and two selectors
+    /// `S_n` and `S_h`. It enforces that:
+    ///
+    /// `(S_n[x], S_n[x] · tag[x], S_n[x] · needle[x])` belongs to
+    /// `{(S_h[y], S_h[y] · tag[y], S_h[y] · haystack[y])}`
+    ///
+    /// for every row `x`. Rows without `S_n` are trivially satisfied by rows without `S_h`, while
+    /// rows with `S_n` must match a row where `S_h` is enabled (in particular, a zero needle cannot
+    /// be matched by a disabled row).
+    fn create_gate_custom(
+        cs: &mut ConstraintSystem<Fr>,
+        (tag_advice, needle_advice, haystack_advice): Self::Advice,
+    ) -> Self {
+        ensure_unique_columns(&[tag_advice, needle_advice, haystack_advice]);
+        let needle_selector = cs.complex_selector();
+        let haystack_selector = cs.complex_selector();
+
+        cs.lookup_any(GATE_NAME, |vc| {
+            let needle_selector = vc.query_selector(needle_selector);
+            let haystack_selector = vc.query_selector(haystack_selector);
+            let tag = vc.query_advice(tag_advice, Rotation::cur());
+            let needle = vc.query_advice(needle_advice, Rotation::cur());
+            let hay = vc.query_advice(haystack_advice, Rotation::cur());
+
+            vec![
+                (needle_selector.clone(), haystack_selector.clone()),
+                (
+                    needle_selector.clone() * tag.clone(),
+                    haystack_selector.clone() * tag,
+                ),
+                (needle_selector * needle, haystack_selector * hay),
+            ]
+        });
+
+        Self {
+            tag_advice,
+            needle_advice,
+            haystack_advice,
+            needle_selector,
+            haystack_selector,
+        }
+    }
+
+    fn apply_in_new_region(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        input: Self::Input,
+    ) -> Result<(), Error> {
+        synthesizer.record_enabled_gate(GATE_NAME);
+        synthesizer.assign_region(
+            || GATE_NAME,
+            |mut region| {
+                self.needle_selector.enable(&mut region, NEEDLE_OFFSET)?;
+                input.needle.copy_advice(
+                    || "needle",
+                    &mut region,
+                    self.needle_advice,
+                    NEEDLE_OFFSET,
+                )?;
+
+                // The needle and the haystack share the rows, so all of them need the tag.
+                for offset in 0..input.haystack.len().max(NEEDLE_OFFSET + 1) {
+                    input.tag.copy_advice(
+                        || alloc::format!("tag_{offset}"),
+                        &mut region,
+                        self.tag_advice,
+                        offset,
+                    )?;
+                }
+
+                for (offset, hay) in input.haystack.iter().enumerate() {
+                    self.haystack_selector.enable(&mut region, offset)?;
+                    hay.copy_advice(
+                        || alloc::format!("haystack_{offset}"),
+                        &mut region,
+                        self.haystack_advice,
+                        offset,
+                    )?;
+                }
+
+                Ok(())
+            },
+        )
+    }
+
+    fn organize_advice_columns(
+        pool: &mut ColumnPool<Advice, ConfigPhase>,
+        cs: &mut ConstraintSystem<Fr>,
+    ) -> Self::Advice {
+        pool.ensure_capacity(cs, 3);
+        (pool.get_column(0), pool.get_column(1), pool.get_column(2))
+    }
+
+    fn advice_columns(&self) -> Vec<Column<Advice>> {
+        vec![self.tag_advice, self.needle_advice, self.haystack_advice]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use halo2_proofs::{halo2curves::bn256::Fr, plonk::ConstraintSystem};
+
+    use super::{MembershipLookupGate, MembershipLookupGateInput};
+    use crate::gates::{
+        membership::{MembershipGate, MembershipGateInput},
+        test_utils::verify,
+        Gate,
+    };
+
+    fn input(needle: u64, haystack: &[u64]) -> MembershipLookupGateInput<Fr> {
+        MembershipLookupGateInput {
+            tag: Fr::from(1),
+            needle: Fr::from(needle),
+            haystack: haystack.iter().copied().map(Fr::from).collect(),
+        }
+    }
+
+    #[test]
+    #[should_panic = "Advice columns must be unique"]
+    fn gate_creation_with_repeated_column_fails() {
+        let mut cs = ConstraintSystem::<Fr>::default();
+        let tag = cs.advice_column();
+        let column = cs.advice_column();
+        MembershipLookupGate::create_gate_custom(&mut cs, (tag, column, column));
+    }
+
+    #[test]
+    fn needle_in_large_haystack_passes() {
+        let haystack = (1..=100).collect::<Vec<_>>();
+        assert!(verify::<MembershipLookupGate, _>(input(37, &haystack)).is_ok());
+        assert!(verify::<MembershipLookupGate, _>(input(100, &haystack)).is_ok());
+    }
+
+    #[test]
+    fn needle_not_in_large_haystack_fails() {
+        let haystack = (1..=100).collect::<Vec<_>>();
+        assert!(verify::<MembershipLookupGate, _>(input(101, &haystack)).is_err());
+    }
+
+    #[test]
+    fn agrees_with_product_based_gate() {
+        for (needle, haystack) in [
+            (1, [2, 1]),
+            (2, [2, 2]),
+            (3, [1, 2]),
+            (0, [1, 2]),
+            (0, [0, 2]),
+        ] {
+            let product_based = verify::<MembershipGate<2>, _>(MembershipGateInput {
+                needle: Fr::from(needle),
+                haystack: haystack.map(Fr::from),
+            });
+            let lookup_based = verify::<MembershipLookupGate, _>(input(needle, &haystack));
+
+            assert_eq!(
+                product_based.is_ok(),
+                lookup_based.is_ok(),
+                "needle: {needle}, haystack: {haystack:?}"
+            );
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use alloc::collections::BTreeSet;
+use alloc::{collections::BTreeSet, vec::Vec};
 
 use halo2_proofs::{
     circuit::Region,
@@ -12,12 +12,32 @@ use crate::{
     AssignedCell, Fr, Value,
 };
 
+#[cfg(feature = "chip-balances")]
+pub mod balance_update;
+pub mod canonical_bit;
+#[cfg(feature = "chip-merkle")]
+pub mod digit_increment;
+#[cfg(feature = "chip-elgamal")]
+pub mod fixed_base_multiply;
 pub mod is_point_on_curve;
+#[cfg(feature = "chip-elgamal")]
 pub mod is_point_on_curve_affine;
+pub mod linear_equation;
 pub mod membership;
+pub mod membership_lookup;
+pub mod non_zero;
+#[cfg(feature = "chip-elgamal")]
 pub mod points_add;
+#[cfg(feature = "chip-elgamal")]
+pub mod points_sub;
+#[cfg(feature = "chip-merkle")]
+pub mod positioned_membership;
+#[cfg(feature = "chip-elgamal")]
 pub mod scalar_multiply;
+#[cfg(feature = "chip-elgamal")]
+pub mod scalar_multiply_w2;
 pub mod sum;
+#[cfg(feature = "chip-elgamal")]
 pub mod to_affine;
 
 #[cfg(test)]
@@ -46,9 +66,9 @@ pub trait Gate: Sized {
     /// Register the gate in the `ConstraintSystem` with already prepared advice columns.
     fn create_gate_custom(cs: &mut ConstraintSystem<Fr>, advice: Self::Advice) -> Self;
 
-    /// Apply the gate in a new region. The gate MUST enable its selector, copy (constrained if
-    /// applicable) the inputs to the region and return new `Gate::Values` struct with the newly
-    /// created assigned cells.
+    /// Apply the gate in a new region. The gate MUST enable its selector (and report it with
+    /// `Synthesizer::record_enabled_gate`), copy (constrained if applicable) the inputs to the
+    /// region and return new `Gate::Values` struct with the newly created assigned cells.
     fn apply_in_new_region(
         &self,
         synthesizer: &mut impl Synthesizer,
@@ -63,6 +83,9 @@ pub trait Gate: Sized {
         pool: &mut ColumnPool<Advice, ConfigPhase>,
         cs: &mut ConstraintSystem<Fr>,
     ) -> Self::Advice;
+
+    /// All advice columns that the gate queries, in no particular order.
+    fn advice_columns(&self) -> Vec<Column<Advice>>;
 }
 
 pub fn ensure_unique_columns(advice: &[Column<Advice>]) {
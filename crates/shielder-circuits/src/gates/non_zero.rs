@@ -0,0 +1,131 @@
+use alloc::{vec, vec::Vec};
+
+use halo2_proofs::{
+    plonk::{Advice, Column, ConstraintSystem, Error, Expression, Selector},
+    poly::Rotation,
+};
+use macros::embeddable;
+
+use crate::{
+    column_pool::{AccessColumn, ColumnPool, ConfigPhase},
+    embed::Embed,
+    gates::{ensure_unique_columns, Gate},
+    synthesizer::Synthesizer,
+    AssignedCell, Fr,
+};
+
+/// Represents the relation: `value * inverse = 1`, which proves that `value` is non-zero.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NonZeroGate {
+    advice: [Column<Advice>; 2],
+    selector: Selector,
+}
+
+#[derive(Clone, Debug, Default)]
+#[embeddable(
+    receiver = "NonZeroGateInput<Fr>",
+    embedded = "NonZeroGateInput<AssignedCell>"
+)]
+pub struct NonZeroGateInput<T> {
+    pub value: T,
+    pub inverse: T,
+}
+
+const SELECTOR_OFFSET: usize = 0;
+const ADVICE_OFFSET: usize = 0;
+const GATE_NAME: &str = "Non-zero gate";
+
+impl Gate for NonZeroGate {
+    type Input = NonZeroGateInput<AssignedCell>;
+    type Advice = [Column<Advice>; 2];
+
+    /// The gate operates on two advice columns `A` and `B`. It enforces that:
+    /// `A[x] * B[x] = 1`, where `x` is the row where the gate is enabled.
+    fn create_gate_custom(cs: &mut ConstraintSystem<Fr>, advice: Self::Advice) -> Self {
+        ensure_unique_columns(&advice);
+        let selector = cs.selector();
+
+        cs.create_gate(GATE_NAME, |vc| {
+            let selector = vc.query_selector(selector);
+            let value = vc.query_advice(advice[0], Rotation(ADVICE_OFFSET as i32));
+            let inverse = vc.query_advice(advice[1], Rotation(ADVICE_OFFSET as i32));
+            vec![selector * (value * inverse - Expression::Constant(Fr::ONE))]
+        });
+        Self { advice, selector }
+    }
+
+    fn apply_in_new_region(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        input: Self::Input,
+    ) -> Result<(), Error> {
+        synthesizer.record_enabled_gate(GATE_NAME);
+        synthesizer.assign_region(
+            || GATE_NAME,
+            |mut region| {
+                self.selector.enable(&mut region, SELECTOR_OFFSET)?;
+
+                input
+                    .value
+                    .copy_advice(|| "value", &mut region, self.advice[0], ADVICE_OFFSET)?;
+                input.inverse.copy_advice(
+                    || "inverse",
+                    &mut region,
+                    self.advice[1],
+                    ADVICE_OFFSET,
+                )?;
+
+                Ok(())
+            },
+        )
+    }
+
+    fn organize_advice_columns(
+        pool: &mut ColumnPool<Advice, ConfigPhase>,
+        cs: &mut ConstraintSystem<Fr>,
+    ) -> Self::Advice {
+        pool.ensure_capacity(cs, 2);
+        pool.get_column_array()
+    }
+
+    fn advice_columns(&self) -> Vec<Column<Advice>> {
+        self.advice.to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::halo2curves::bn256::Fr;
+
+    use crate::{
+        gates::{
+            non_zero::{NonZeroGate, NonZeroGateInput},
+            test_utils::verify,
+        },
+        Field,
+    };
+
+    fn input(value: Fr, inverse: Fr) -> NonZeroGateInput<Fr> {
+        NonZeroGateInput { value, inverse }
+    }
+
+    #[test]
+    fn non_zero_value_with_its_inverse_passes() {
+        let value = Fr::from(3);
+        let inverse = value.invert().unwrap();
+        assert!(verify::<NonZeroGate, _>(input(value, inverse)).is_ok());
+    }
+
+    #[test]
+    fn wrong_inverse_fails() {
+        let errors = verify::<NonZeroGate, _>(input(Fr::from(3), Fr::from(3)))
+            .expect_err("Verification should fail");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("Constraint 0 in gate 0 ('Non-zero gate') is not satisfied"));
+    }
+
+    #[test]
+    fn zero_fails() {
+        assert!(verify::<NonZeroGate, _>(input(Fr::ZERO, Fr::ONE)).is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use alloc::vec;
+use alloc::{vec, vec::Vec};
 
 use halo2_proofs::{
     halo2curves::bn256::Fr,
@@ -91,6 +91,7 @@ impl Gate for PointsAddGate {
         synthesizer: &mut impl Synthesizer,
         input: Self::Input,
     ) -> Result<(), Error> {
+        synthesizer.record_enabled_gate(GATE_NAME);
         synthesizer.assign_region(
             || GATE_NAME,
             |mut region| {
@@ -117,6 +118,10 @@ impl Gate for PointsAddGate {
             [pool.get_column(6), pool.get_column(7), pool.get_column(8)],
         )
     }
+
+    fn advice_columns(&self) -> Vec<Column<Advice>> {
+        [self.p, self.q, self.s].concat()
+    }
 }
 
 #[cfg(test)]
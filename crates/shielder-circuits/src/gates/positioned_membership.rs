@@ -0,0 +1,200 @@
+use alloc::{vec, vec::Vec};
+
+use halo2_proofs::{
+    plonk::{Advice, Column, ConstraintSystem, Constraints, Error, Expression, Selector},
+    poly::Rotation,
+};
+use macros::embeddable;
+
+use crate::{
+    column_pool::{AccessColumn, ColumnPool, ConfigPhase},
+    embed::Embed,
+    gates::{ensure_unique_columns, Gate},
+    synthesizer::Synthesizer,
+    AssignedCell, Fr,
+};
+
+/// Represents the relation: `position ∈ {0, …, N - 1}` and `haystack_position = needle`.
+///
+/// Unlike `MembershipGate`, it pins down where the needle is, which lets a Merkle path be bound to
+/// the index of its leaf.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PositionedMembershipGate<const N: usize> {
+    needle: Column<Advice>,
+    position: Column<Advice>,
+    haystack: [Column<Advice>; N],
+    selector: Selector,
+}
+
+#[derive(Clone, Debug)]
+#[embeddable(
+    receiver = "PositionedMembershipGateInput<Fr, N>",
+    impl_generics = "<const N: usize>",
+    embedded = "PositionedMembershipGateInput<AssignedCell, N>"
+)]
+pub struct PositionedMembershipGateInput<T, const N: usize> {
+    pub needle: T,
+    pub position: T,
+    pub haystack: [T; N],
+}
+
+const SELECTOR_OFFSET: usize = 0;
+const ADVICE_OFFSET: usize = 0;
+const GATE_NAME: &str = "Positioned membership gate";
+
+impl<const N: usize> Gate for PositionedMembershipGate<N> {
+    type Input = PositionedMembershipGateInput<AssignedCell, N>;
+    type Advice = (Column<Advice>, Column<Advice>, [Column<Advice>; N]);
+
+    /// The gate operates on advice columns `needle`, `pos` and `N` advice columns `haystack`. It
+    /// enforces that:
+    ///
+    /// `(pos[x] - 0) · … · (pos[x] - (N - 1)) = 0` and, for every `j`,
+    /// `Π_{k ≠ j} (pos[x] - k) · (haystack_j[x] - needle[x]) = 0`, where `x` is the row where the
+    /// gate is enabled. The product in the latter vanishes exactly when `pos[x] ≠ j`.
+    fn create_gate_custom(
+        cs: &mut ConstraintSystem<Fr>,
+        (needle, position, haystack): Self::Advice,
+    ) -> Self {
+        ensure_unique_columns(&[haystack.to_vec(), vec![needle, position]].concat());
+        let selector = cs.selector();
+
+        cs.create_gate(GATE_NAME, |vc| {
+            let needle = vc.query_advice(needle, Rotation(ADVICE_OFFSET as i32));
+            let position = vc.query_advice(position, Rotation(ADVICE_OFFSET as i32));
+            let vanishing_except = |excluded: Option<usize>| {
+                (0..N).filter(|&k| Some(k) != excluded).fold(
+                    Expression::Constant(Fr::from(1)),
+                    |product, k| {
+                        product * (position.clone() - Expression::Constant(Fr::from(k as u64)))
+                    },
+                )
+            };
+
+            let mut constraints = vec![("position is in range", vanishing_except(None))];
+            for (j, &hay) in haystack.iter().enumerate() {
+                let element = vc.query_advice(hay, Rotation(ADVICE_OFFSET as i32));
+                constraints.push((
+                    "needle is at the position",
+                    vanishing_except(Some(j)) * (element - needle.clone()),
+                ));
+            }
+
+            Constraints::with_selector(vc.query_selector(selector), constraints)
+        });
+
+        Self {
+            needle,
+            position,
+            haystack,
+            selector,
+        }
+    }
+
+    fn apply_in_new_region(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        input: Self::Input,
+    ) -> Result<(), Error> {
+        synthesizer.record_enabled_gate(GATE_NAME);
+        synthesizer.assign_region(
+            || GATE_NAME,
+            |mut region| {
+                self.selector.enable(&mut region, SELECTOR_OFFSET)?;
+
+                input
+                    .needle
+                    .copy_advice(|| "needle", &mut region, self.needle, ADVICE_OFFSET)?;
+                input.position.copy_advice(
+                    || "position",
+                    &mut region,
+                    self.position,
+                    ADVICE_OFFSET,
+                )?;
+
+                for (i, hay) in input.haystack.iter().enumerate() {
+                    hay.copy_advice(
+                        || alloc::format!("haystack_{i}"),
+                        &mut region,
+                        self.haystack[i],
+                        ADVICE_OFFSET,
+                    )?;
+                }
+
+                Ok(())
+            },
+        )
+    }
+
+    fn organize_advice_columns(
+        pool: &mut ColumnPool<Advice, ConfigPhase>,
+        cs: &mut ConstraintSystem<Fr>,
+    ) -> Self::Advice {
+        pool.ensure_capacity(cs, N + 2);
+        let haystack = pool.get_column_array();
+        (pool.get_column(N), pool.get_column(N + 1), haystack)
+    }
+
+    fn advice_columns(&self) -> Vec<Column<Advice>> {
+        let mut columns = self.haystack.to_vec();
+        columns.extend([self.needle, self.position]);
+        columns
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::halo2curves::bn256::Fr;
+
+    use super::{PositionedMembershipGate, PositionedMembershipGateInput};
+    use crate::gates::test_utils::verify;
+
+    impl Default for PositionedMembershipGateInput<Fr, 3> {
+        fn default() -> Self {
+            Self {
+                needle: Fr::default(),
+                position: Fr::default(),
+                haystack: [Fr::default(); 3],
+            }
+        }
+    }
+
+    fn input(
+        needle: u64,
+        position: u64,
+        haystack: [u64; 3],
+    ) -> PositionedMembershipGateInput<Fr, 3> {
+        PositionedMembershipGateInput {
+            needle: Fr::from(needle),
+            position: Fr::from(position),
+            haystack: haystack.map(Fr::from),
+        }
+    }
+
+    #[test]
+    fn needle_at_the_position_passes() {
+        assert!(verify::<PositionedMembershipGate<3>, _>(input(5, 0, [5, 6, 7])).is_ok());
+        assert!(verify::<PositionedMembershipGate<3>, _>(input(7, 2, [5, 6, 7])).is_ok());
+    }
+
+    #[test]
+    fn repeated_needle_passes_at_each_position() {
+        assert!(verify::<PositionedMembershipGate<3>, _>(input(5, 0, [5, 6, 5])).is_ok());
+        assert!(verify::<PositionedMembershipGate<3>, _>(input(5, 2, [5, 6, 5])).is_ok());
+    }
+
+    #[test]
+    fn needle_at_another_position_fails() {
+        let errors = verify::<PositionedMembershipGate<3>, _>(input(5, 1, [5, 6, 7]))
+            .expect_err("Verification should fail");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("needle is at the position"));
+    }
+
+    #[test]
+    fn position_out_of_range_fails() {
+        let errors = verify::<PositionedMembershipGate<3>, _>(input(5, 3, [5, 6, 7]))
+            .expect_err("Verification should fail");
+        assert!(errors.iter().any(|e| e.contains("position is in range")));
+    }
+}
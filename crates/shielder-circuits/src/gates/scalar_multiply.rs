@@ -1,4 +1,4 @@
-use alloc::vec;
+use alloc::{vec, vec::Vec};
 
 use halo2_proofs::{
     arithmetic::Field,
@@ -158,6 +158,7 @@ impl Gate for ScalarMultiplyGate {
             final_result,
         }: Self::Input,
     ) -> Result<(), Error> {
+        synthesizer.record_enabled_gate(GATE_NAME);
         synthesizer.assign_region(
             || GATE_NAME,
             |mut region| {
@@ -253,6 +254,13 @@ impl Gate for ScalarMultiplyGate {
             [pool.get_column(4), pool.get_column(5), pool.get_column(6)], // result
         )
     }
+
+    fn advice_columns(&self) -> Vec<Column<Advice>> {
+        let mut columns = vec![self.scalar_bits];
+        columns.extend(self.input);
+        columns.extend(self.result);
+        columns
+    }
 }
 
 #[cfg(test)]
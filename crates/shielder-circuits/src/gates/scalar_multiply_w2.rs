@@ -0,0 +1,379 @@
+use alloc::{format, vec::Vec};
+
+use halo2_proofs::{
+    arithmetic::Field,
+    halo2curves::bn256::Fr,
+    plonk::{
+        Advice, Column, ConstraintSystem, Constraints, Error, Expression, Selector, VirtualCells,
+    },
+    poly::Rotation,
+};
+
+use super::{assign_grumpkin_advices, assign_grumpkin_point_at_infinity, copy_grumpkin_advices};
+use crate::{
+    column_pool::{AccessColumn, ColumnPool, ConfigPhase},
+    consts::FIELD_BITS,
+    curve_arithmetic::{self, GrumpkinPoint},
+    gates::{ensure_unique_columns, scalar_multiply::ScalarMultiplyGateInput, Gate},
+    synthesizer::Synthesizer,
+    AssignedCell, Value,
+};
+
+/// Number of scalar bits consumed by a single row of the gate.
+const WINDOW_SIZE: usize = 2;
+/// Number of rows on which the gate is enabled.
+const WINDOWS: usize = FIELD_BITS / WINDOW_SIZE;
+
+/// Windowed version of `ScalarMultiplyGate`: represents the same relation, but consumes two bits of
+/// the scalar per row, which roughly halves the number of rows at the cost of more advice columns.
+/// The multiples `2P` and `3P` of the current base point `P` are computed in every row and the one
+/// selected by the window is added to the result.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ScalarMultiplyGateW2 {
+    selector: Selector,
+    scalar_bits: [Column<Advice>; WINDOW_SIZE],
+    base: [Column<Advice>; 3],
+    doubled: [Column<Advice>; 3],
+    tripled: [Column<Advice>; 3],
+    addend: [Column<Advice>; 3],
+    result: [Column<Advice>; 3],
+}
+
+const SELECTOR_OFFSET: usize = 0;
+const ADVICE_OFFSET: usize = 0;
+const GATE_NAME: &str = "Scalar multiply gate (2-bit windows)";
+
+fn query_point(
+    vc: &mut VirtualCells<Fr>,
+    columns: [Column<Advice>; 3],
+    rotation: usize,
+) -> GrumpkinPoint<Expression<Fr>> {
+    let [x, y, z] = columns.map(|column| vc.query_advice(column, Rotation(rotation as i32)));
+    GrumpkinPoint::new(x, y, z)
+}
+
+impl Gate for ScalarMultiplyGateW2 {
+    type Input = ScalarMultiplyGateInput<AssignedCell>;
+
+    type Advice = (
+        [Column<Advice>; WINDOW_SIZE], // scalar_bits
+        [Column<Advice>; 3],           // base
+        [Column<Advice>; 3],           // doubled
+        [Column<Advice>; 3],           // tripled
+        [Column<Advice>; 3],           // addend
+        [Column<Advice>; 3],           // result
+    );
+
+    /// The gate operates on two advice columns `bit0` and `bit1` (a window of the scalar, in LE
+    /// order) and five triplets (projective coordinates of points on an EC): `base`, `doubled`,
+    /// `tripled`, `addend` and `result`. Constraints:
+    ///
+    /// doubled[i] = 2 * base[i]
+    /// tripled[i] = doubled[i] + base[i]
+    /// addend[i] = (bit0 + 2 * bit1) * base[i]   (selected from 0, base, doubled and tripled)
+    /// result[i + 1] = result[i] + addend[i]
+    /// base[i + 1] = 2 * doubled[i]
+    /// bit0, bit1 \in {0,1}
+    fn create_gate_custom(
+        cs: &mut ConstraintSystem<Fr>,
+        (scalar_bits, base, doubled, tripled, addend, result): Self::Advice,
+    ) -> Self {
+        ensure_unique_columns(
+            &[
+                scalar_bits.to_vec(),
+                base.to_vec(),
+                doubled.to_vec(),
+                tripled.to_vec(),
+                addend.to_vec(),
+                result.to_vec(),
+            ]
+            .concat(),
+        );
+        let selector = cs.selector();
+
+        cs.create_gate(GATE_NAME, |vc| {
+            let bits =
+                scalar_bits.map(|column| vc.query_advice(column, Rotation(ADVICE_OFFSET as i32)));
+
+            let base_point = query_point(vc, base, ADVICE_OFFSET);
+            let doubled_point = query_point(vc, doubled, ADVICE_OFFSET);
+            let tripled_point = query_point(vc, tripled, ADVICE_OFFSET);
+            let addend_point = query_point(vc, addend, ADVICE_OFFSET);
+            let result_point = query_point(vc, result, ADVICE_OFFSET);
+            let next_base = query_point(vc, base, ADVICE_OFFSET + 1);
+            let next_result = query_point(vc, result, ADVICE_OFFSET + 1);
+
+            let one = Expression::Constant(Fr::ONE);
+            let mut constraints = bits
+                .iter()
+                .enumerate()
+                .map(|(i, bit)| {
+                    (
+                        format!("bit{i} is a binary value"),
+                        bit.clone() * (one.clone() - bit.clone()),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            let relations = [
+                (
+                    "doubled = 2 * base",
+                    doubled_point.clone(),
+                    curve_arithmetic::point_double(base_point.clone()),
+                ),
+                (
+                    "tripled = doubled + base",
+                    tripled_point.clone(),
+                    curve_arithmetic::points_add(doubled_point.clone(), base_point.clone()),
+                ),
+                (
+                    "addend = window * base",
+                    addend_point.clone(),
+                    curve_arithmetic::select_window(
+                        bits,
+                        base_point,
+                        doubled_point.clone(),
+                        tripled_point,
+                    ),
+                ),
+                (
+                    "next_result = result + addend",
+                    next_result,
+                    curve_arithmetic::points_add(result_point, addend_point),
+                ),
+                (
+                    "next_base = 2 * doubled",
+                    next_base,
+                    curve_arithmetic::point_double(doubled_point),
+                ),
+            ];
+            for (name, actual, expected) in relations {
+                constraints.extend([
+                    (format!("x: {name}"), actual.x - expected.x),
+                    (format!("y: {name}"), actual.y - expected.y),
+                    (format!("z: {name}"), actual.z - expected.z),
+                ]);
+            }
+
+            Constraints::with_selector(vc.query_selector(selector), constraints)
+        });
+
+        Self {
+            selector,
+            scalar_bits,
+            base,
+            doubled,
+            tripled,
+            addend,
+            result,
+        }
+    }
+
+    fn apply_in_new_region(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        ScalarMultiplyGateInput {
+            scalar_bits,
+            input,
+            final_result,
+        }: Self::Input,
+    ) -> Result<(), Error> {
+        synthesizer.record_enabled_gate(GATE_NAME);
+        synthesizer.assign_region(
+            || GATE_NAME,
+            |mut region| {
+                let mut base = copy_grumpkin_advices(
+                    &input,
+                    "initial base",
+                    &mut region,
+                    self.base,
+                    ADVICE_OFFSET,
+                )?;
+
+                let mut result = assign_grumpkin_point_at_infinity(
+                    "initial result",
+                    &mut region,
+                    self.result,
+                    ADVICE_OFFSET,
+                )?;
+
+                for (window, bits) in scalar_bits.chunks_exact(WINDOW_SIZE).enumerate() {
+                    let offset = ADVICE_OFFSET + window;
+                    self.selector
+                        .enable(&mut region, SELECTOR_OFFSET + window)?;
+
+                    for (i, (bit, column)) in bits.iter().zip(self.scalar_bits).enumerate() {
+                        bit.copy_advice(
+                            || format!("bit[{}]", WINDOW_SIZE * window + i),
+                            &mut region,
+                            column,
+                            offset,
+                        )?;
+                    }
+
+                    let base_value: GrumpkinPoint<Value> = base.clone().into();
+                    let doubled = curve_arithmetic::point_double(base_value);
+                    let tripled = curve_arithmetic::points_add(doubled, base_value);
+                    let addend = curve_arithmetic::select_window(
+                        [bits[0].value().cloned(), bits[1].value().cloned()],
+                        base_value,
+                        doubled,
+                        tripled,
+                    );
+
+                    assign_grumpkin_advices(
+                        &doubled,
+                        "doubled",
+                        &mut region,
+                        self.doubled,
+                        offset,
+                    )?;
+                    assign_grumpkin_advices(
+                        &tripled,
+                        "tripled",
+                        &mut region,
+                        self.tripled,
+                        offset,
+                    )?;
+                    assign_grumpkin_advices(&addend, "addend", &mut region, self.addend, offset)?;
+
+                    result = if window == WINDOWS - 1 {
+                        copy_grumpkin_advices(
+                            &final_result,
+                            "final result",
+                            &mut region,
+                            self.result,
+                            offset + 1,
+                        )?
+                    } else {
+                        assign_grumpkin_advices(
+                            &curve_arithmetic::points_add(result.into(), addend),
+                            "result",
+                            &mut region,
+                            self.result,
+                            offset + 1,
+                        )?
+                    };
+
+                    base = assign_grumpkin_advices(
+                        &curve_arithmetic::point_double(doubled),
+                        "base",
+                        &mut region,
+                        self.base,
+                        offset + 1,
+                    )?;
+                }
+
+                Ok(())
+            },
+        )
+    }
+
+    fn organize_advice_columns(
+        pool: &mut ColumnPool<Advice, ConfigPhase>,
+        cs: &mut ConstraintSystem<Fr>,
+    ) -> Self::Advice {
+        pool.ensure_capacity(cs, WINDOW_SIZE + 15);
+        let point = |first: usize| core::array::from_fn(|i| pool.get_column(first + i));
+        (
+            core::array::from_fn(|i| pool.get_column(i)), // scalar_bits
+            point(WINDOW_SIZE),                           // base
+            point(WINDOW_SIZE + 3),                       // doubled
+            point(WINDOW_SIZE + 6),                       // tripled
+            point(WINDOW_SIZE + 9),                       // addend
+            point(WINDOW_SIZE + 12),                      // result
+        )
+    }
+
+    fn advice_columns(&self) -> Vec<Column<Advice>> {
+        [
+            self.scalar_bits.as_slice(),
+            &self.base,
+            &self.doubled,
+            &self.tripled,
+            &self.addend,
+            &self.result,
+        ]
+        .concat()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use halo2_proofs::{
+        dev::{MockProver, VerifyFailure},
+        halo2curves::{bn256::Fr, ff::PrimeField, group::Group, grumpkin::G1},
+    };
+
+    use super::ScalarMultiplyGateW2;
+    use crate::{
+        curve_arithmetic::{self, normalize_point, GrumpkinPoint},
+        field_element_to_le_bits,
+        gates::{scalar_multiply::ScalarMultiplyGateInput, test_utils::OneGateCircuit},
+        rng, Field,
+    };
+
+    fn verify(input: ScalarMultiplyGateInput<Fr>) -> Result<(), Vec<VerifyFailure>> {
+        let circuit = OneGateCircuit::<ScalarMultiplyGateW2, _>::new(input);
+        MockProver::run(10, &circuit, vec![])
+            .expect("Mock prover should run")
+            .verify()
+    }
+
+    #[test]
+    fn multiply_random_points() {
+        let mut rng = rng();
+
+        for _ in 0..4 {
+            let p: GrumpkinPoint<Fr> = G1::random(&mut rng).into();
+            let bits = field_element_to_le_bits(Fr::random(&mut rng));
+
+            let final_result = curve_arithmetic::scalar_multiply_windowed(p, bits);
+            assert_eq!(
+                normalize_point(final_result),
+                normalize_point(curve_arithmetic::scalar_multiply(p, bits))
+            );
+
+            assert!(verify(ScalarMultiplyGateInput {
+                scalar_bits: bits,
+                input: p,
+                final_result
+            })
+            .is_ok());
+        }
+    }
+
+    #[test]
+    fn invalid_result_fails() {
+        let p: GrumpkinPoint<Fr> = G1::random(rng()).into();
+        let bits = field_element_to_le_bits(Fr::from_u128(3));
+        let incorrect_result = curve_arithmetic::scalar_multiply_windowed(
+            p,
+            field_element_to_le_bits(Fr::from_u128(4)),
+        );
+
+        assert!(verify(ScalarMultiplyGateInput {
+            scalar_bits: bits,
+            input: p,
+            final_result: incorrect_result
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn bit_is_invalid() {
+        let p: GrumpkinPoint<Fr> = G1::random(rng()).into();
+        let mut bits = field_element_to_le_bits(Fr::from_u128(5));
+        let final_result = curve_arithmetic::scalar_multiply_windowed(p, bits);
+
+        bits[1] = Fr::from_u128(2);
+        assert!(verify(ScalarMultiplyGateInput {
+            scalar_bits: bits,
+            input: p,
+            final_result
+        })
+        .is_err());
+    }
+}
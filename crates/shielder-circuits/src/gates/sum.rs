@@ -1,4 +1,4 @@
-use alloc::vec;
+use alloc::{vec, vec::Vec};
 
 use halo2_proofs::{
     plonk::{Advice, Column, ConstraintSystem, Error, Selector},
@@ -58,6 +58,7 @@ impl Gate for SumGate {
         synthesizer: &mut impl Synthesizer,
         input: Self::Input,
     ) -> Result<(), Error> {
+        synthesizer.record_enabled_gate(GATE_NAME);
         synthesizer.assign_region(
             || GATE_NAME,
             |mut region| {
@@ -86,6 +87,10 @@ impl Gate for SumGate {
         pool.ensure_capacity(cs, 3);
         pool.get_column_array()
     }
+
+    fn advice_columns(&self) -> Vec<Column<Advice>> {
+        self.advice.to_vec()
+    }
 }
 
 #[cfg(test)]
@@ -93,6 +98,7 @@ mod tests {
     use halo2_proofs::{halo2curves::bn256::Fr, plonk::ConstraintSystem};
 
     use crate::gates::{
+        ensure_unique_columns,
         sum::{SumGate, SumGateInput},
         test_utils::verify,
         Gate as _,
@@ -125,6 +131,17 @@ mod tests {
         SumGate::create_gate_custom(&mut cs, [advice_column; 3]);
     }
 
+    #[test]
+    fn reported_advice_columns_are_unique() {
+        let mut cs = ConstraintSystem::<Fr>::default();
+        let advice = [cs.advice_column(), cs.advice_column(), cs.advice_column()];
+        let gate = SumGate::create_gate_custom(&mut cs, advice);
+
+        let columns = gate.advice_columns();
+        assert_eq!(columns, advice.to_vec());
+        ensure_unique_columns(&columns);
+    }
+
     #[test]
     fn zeros_passes() {
         assert!(verify::<SumGate, _>(input(0, 0, 0)).is_ok());
@@ -1,4 +1,4 @@
-use alloc::vec;
+use alloc::{vec, vec::Vec};
 
 use halo2_proofs::{
     arithmetic::Field,
@@ -106,6 +106,7 @@ impl Gate for ToAffineGate {
             point_projective_z_inverse: z_inverse,
         }: Self::Input,
     ) -> Result<(), Error> {
+        synthesizer.record_enabled_gate(GATE_NAME);
         synthesizer.assign_region(
             || GATE_NAME,
             |mut region| {
@@ -151,6 +152,13 @@ impl Gate for ToAffineGate {
             pool.get_column(5),                                           // z_{p}^{-1}
         )
     }
+
+    fn advice_columns(&self) -> Vec<Column<Advice>> {
+        let mut columns = self.point_projective.to_vec();
+        columns.extend(self.point_affine);
+        columns.push(self.z_inverse);
+        columns
+    }
 }
 
 #[cfg(test)]
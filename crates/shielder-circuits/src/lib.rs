@@ -1,7 +1,7 @@
 #![no_std]
 
 extern crate alloc;
-#[cfg(test)]
+#[cfg(any(test, feature = "std"))]
 extern crate std;
 
 mod chips;
@@ -13,19 +13,44 @@ pub mod curve_arithmetic;
 mod embed;
 mod gates;
 mod instance_wrapper;
+pub mod nullifier;
 pub mod poseidon;
+#[cfg(feature = "poseidon2")]
+pub mod poseidon2;
 mod range_table;
 mod synthesizer;
 mod version;
 
-use alloc::{fmt::Debug, vec::Vec};
+use alloc::{fmt::Debug, format, string::String, vec::Vec};
 
+#[cfg(feature = "chip-balances")]
 pub use chips::{
-    el_gamal::off_circuit::{decrypt, encrypt, generate_keys},
-    note::{off_circuit::note_hash, Note},
+    balance_update::BalanceUpdateChip,
+    note::off_circuit::multi_balance_hash,
+    shortlist_hash::{
+        off_circuit::{shortlist_hash, skip_hash},
+        Shortlist, ShortlistHashChip,
+    },
+    threshold::ThresholdChip,
+};
+pub use chips::{
+    bits_recompose::BitsRecomposeChip,
+    membership_lookup::MembershipLookupChip,
+    note::{
+        off_circuit::{new_note_hash, note_hash},
+        Note,
+    },
+    range_check::{min_k_for_table, RangeCheckChip},
     viewing_key::off_circuit::derive_viewing_key,
 };
+#[cfg(feature = "chip-elgamal")]
+pub use chips::{
+    el_gamal::off_circuit::{decrypt, encrypt, generate_keys, shared_secret},
+    points_sub::PointsSubChip,
+    scalar_multiply::{FixedBaseMultiplyChip, ScalarMultiplyChipW2},
+};
 pub use circuits::*;
+pub use config_builder::ChipKind;
 pub use consts::MAX_K;
 pub use curve_arithmetic::{grumpkin_point::*, *};
 pub use halo2_proofs::{
@@ -40,13 +65,22 @@ pub use halo2_proofs::{
     poly::{commitment::Params, kzg::commitment::ParamsKZG},
     SerdeFormat,
 };
+use rand::{rngs::StdRng, SeedableRng};
 use rand_core::RngCore;
 pub use strum::{EnumCount, IntoEnumIterator};
-pub use version::NoteVersion;
+pub use version::{
+    NoteLayout, NoteVersion, UnsupportedNoteVersion, COMMITMENT_VERSION, MARSHALL_VERSION,
+};
 
 /// Format for serializing SRS and proving/verifying keys.
 pub const SERDE_FORMAT: SerdeFormat = SerdeFormat::Processed;
 
+/// Placeholder printed instead of secret values by `ProverKnowledge::redacted_debug`.
+pub const REDACTED: &str = "<redacted>";
+
+/// Seed of the fixed example returned by `ProverKnowledge::example`.
+pub const EXAMPLE_SEED: [u8; 32] = [0; 32];
+
 pub type AssignedCell = halo2_proofs::circuit::AssignedCell<Fr, Fr>;
 pub type Value = halo2_proofs::circuit::Value<Fr>;
 
@@ -57,13 +91,36 @@ pub trait ProverKnowledge: Clone + PublicInputProvider<Self::PublicInput> {
     /// Associated type for the public inputs. Expected to be iterable enumeration.
     type PublicInput: IntoEnumIterator + EnumCount;
 
+    /// Unique name of the circuit, e.g., for dispatching requests in a proving service (see
+    /// `circuits::by_name`).
+    fn circuit_name() -> &'static str;
+
     /// Creates a new instance of the circuit values with correct, randomized values. The circuit
     /// MUST be satisfied. Implementation might require more effort to generate such values. Useful
     /// for testing validity of the circuit constraints.
     fn random_correct_example(rng: &mut impl RngCore) -> Self;
 
+    /// Same as `random_correct_example`, but draws the randomness from a `StdRng` seeded with
+    /// `seed`. A failing example (e.g., from a fuzz test) can then be replayed from its seed alone.
+    fn seeded_example(seed: [u8; 32]) -> Self {
+        Self::random_correct_example(&mut StdRng::from_seed(seed))
+    }
+
+    /// Deterministic correct example (generated from `EXAMPLE_SEED`). Every call returns the same
+    /// values, so quick manual tests and doctests can prove against fixed public inputs.
+    fn example() -> Self {
+        Self::seeded_example(EXAMPLE_SEED)
+    }
+
     /// Creates a new instance of the circuit based on the prover's knowledge.
     fn create_circuit(&self) -> Self::Circuit;
+
+    /// Returns a debug representation that is safe to log, i.e., one that does not reveal any
+    /// secret witness. The default implementation redacts everything but the type name;
+    /// implementations are encouraged to print the fields that can be derived from public inputs.
+    fn redacted_debug(&self) -> String {
+        format!("{} {{ {REDACTED} }}", core::any::type_name::<Self>())
+    }
 }
 
 pub trait PublicInputProvider<Id: IntoEnumIterator + EnumCount> {
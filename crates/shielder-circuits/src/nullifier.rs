@@ -0,0 +1,65 @@
+//! Helpers for the published nullifier hashes, i.e., `nullifier_hash(nullifier)` values that the
+//! circuits expose as public inputs (e.g., `HashedOldNullifier`).
+
+pub mod off_circuit {
+    use alloc::vec::Vec;
+
+    #[cfg(feature = "multithreading")]
+    use rayon::prelude::*;
+
+    use crate::{chips::nullifier::off_circuit::nullifier_hash, Fr};
+
+    /// Computes the published hash of every nullifier in `nullifiers`. Meant for indexers
+    /// building the set of spent nullifiers.
+    pub fn hashes(nullifiers: &[Fr]) -> Vec<Fr> {
+        nullifiers
+            .iter()
+            .map(|nullifier| nullifier_hash(*nullifier))
+            .collect()
+    }
+
+    /// Parallel version of [`hashes`].
+    #[cfg(feature = "multithreading")]
+    pub fn hashes_parallel(nullifiers: &[Fr]) -> Vec<Fr> {
+        nullifiers
+            .par_iter()
+            .map(|nullifier| nullifier_hash(*nullifier))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use super::off_circuit::hashes;
+    use crate::{circuits::rng, poseidon::off_circuit::hash, Field, Fr};
+
+    fn random_nullifiers() -> Vec<Fr> {
+        let mut rng = rng();
+        (0..10).map(|_| Fr::random(&mut rng)).collect()
+    }
+
+    #[test]
+    fn batch_matches_single_hashes() {
+        let nullifiers = random_nullifiers();
+
+        let expected = nullifiers
+            .iter()
+            .map(|nullifier| hash(&[*nullifier]))
+            .collect::<Vec<_>>();
+
+        assert_eq!(hashes(&nullifiers), expected);
+    }
+
+    #[cfg(feature = "multithreading")]
+    #[test]
+    fn parallel_batch_matches_sequential_one() {
+        let nullifiers = random_nullifiers();
+
+        assert_eq!(
+            super::off_circuit::hashes_parallel(&nullifiers),
+            hashes(&nullifiers)
+        );
+    }
+}
@@ -8,6 +8,7 @@ use crate::{
 };
 
 pub mod spec;
+pub mod sponge;
 
 pub type PoseidonCircuitHash<const LENGTH: usize> = halo2_poseidon::poseidon::Hash<
     Fr,
@@ -27,20 +28,51 @@ pub type PoseidonOffCircuitHash<const LENGTH: usize> = halo2_poseidon::poseidon:
 >;
 
 pub mod off_circuit {
-    use crate::{poseidon::PoseidonOffCircuitHash, Fr};
+    use crate::{consts::POSEIDON_RATE, poseidon::PoseidonOffCircuitHash, Fr};
 
     /// Compute Poseidon hash of `input` (off-circuit).
     pub fn hash<const LENGTH: usize>(input: &[Fr; LENGTH]) -> Fr {
         PoseidonOffCircuitHash::<LENGTH>::init().hash(*input)
     }
+
+    /// Compute Poseidon hash of `input` prefixed with `domain` (off-circuit), i.e.,
+    /// `hash([domain, input...])`. Hashes of the same input under different domains (e.g.,
+    /// `consts::NOTE_HASH_DOMAIN` and `consts::MAC_HASH_DOMAIN`) are unrelated. `input` must leave
+    /// room for the domain in a single permutation.
+    ///
+    /// `PREFIXED` is the length of the hashed array and must be `LENGTH + 1` (checked at compile
+    /// time), e.g., `hash_with_domain::<2, 3>(domain, &[a, b])`.
+    pub fn hash_with_domain<const LENGTH: usize, const PREFIXED: usize>(
+        domain: Fr,
+        input: &[Fr; LENGTH],
+    ) -> Fr {
+        const { assert!(PREFIXED == LENGTH + 1, "PREFIXED must be LENGTH + 1") };
+        const { assert!(PREFIXED <= POSEIDON_RATE, "no room for the domain element") };
+
+        hash(&prefixed(domain, input))
+    }
+
+    /// `[domain, input...]`, assuming that `PREFIXED = LENGTH + 1`.
+    pub(super) fn prefixed<T: Clone, const LENGTH: usize, const PREFIXED: usize>(
+        domain: T,
+        input: &[T; LENGTH],
+    ) -> [T; PREFIXED] {
+        core::array::from_fn(|i| match i {
+            0 => domain.clone(),
+            _ => input[i - 1].clone(),
+        })
+    }
 }
 
 pub mod circuit {
     use halo2_proofs::plonk::Error;
 
     use crate::{
-        consts::merkle_constants::{ARITY, WIDTH},
-        poseidon::PoseidonCircuitHash,
+        consts::{
+            merkle_constants::{ARITY, WIDTH},
+            POSEIDON_RATE,
+        },
+        poseidon::{off_circuit::prefixed, PoseidonCircuitHash},
         synthesizer::Synthesizer,
         AssignedCell, Fr,
     };
@@ -57,4 +89,135 @@ pub mod circuit {
         PoseidonCircuitHash::<LENGTH>::init(poseidon_chip, synthesizer.namespace(|| "Hash init"))?
             .hash(synthesizer.namespace(|| "Poseidon hash"), input)
     }
+
+    /// In-circuit counterpart of [`super::off_circuit::hash_with_domain`]. `domain` is assigned as
+    /// a constant, so it is fixed by the circuit rather than chosen by the prover.
+    pub fn hash_with_domain<const LENGTH: usize, const PREFIXED: usize>(
+        synthesizer: &mut impl Synthesizer,
+        poseidon_chip: PoseidonChip,
+        domain: Fr,
+        input: [AssignedCell; LENGTH],
+    ) -> Result<AssignedCell, Error> {
+        const { assert!(PREFIXED == LENGTH + 1, "PREFIXED must be LENGTH + 1") };
+        const { assert!(PREFIXED <= POSEIDON_RATE, "no room for the domain element") };
+
+        let domain = synthesizer.assign_constant("hash domain", domain)?;
+        hash(
+            synthesizer,
+            poseidon_chip,
+            prefixed::<_, LENGTH, PREFIXED>(domain, &input),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{vec, vec::Vec};
+
+    use halo2_proofs::{
+        circuit::{floor_planner::V1, Layouter},
+        dev::{MockProver, VerifyFailure},
+        plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+    };
+
+    use crate::{
+        column_pool::{ColumnPool, PreSynthesisPhase},
+        config_builder::ConfigsBuilder,
+        consts::{MAC_HASH_DOMAIN, NOTE_HASH_DOMAIN, NULLIFIER_HASH_DOMAIN},
+        embed::Embed,
+        poseidon::{
+            circuit::{self, PoseidonChip},
+            off_circuit::{hash, hash_with_domain},
+        },
+        synthesizer::create_synthesizer,
+        Fr,
+    };
+
+    const INPUT: [u64; 2] = [41, 42];
+
+    #[test]
+    fn same_input_is_separated_by_domains() {
+        let input = INPUT.map(Fr::from);
+        assert_eq!(hash(&input), hash(&INPUT.map(Fr::from)));
+
+        let note = hash_with_domain::<2, 3>(*NOTE_HASH_DOMAIN, &input);
+        let nullifier = hash_with_domain::<2, 3>(*NULLIFIER_HASH_DOMAIN, &input);
+        let mac = hash_with_domain::<2, 3>(*MAC_HASH_DOMAIN, &input);
+
+        assert_ne!(note, nullifier);
+        assert_ne!(note, mac);
+        assert_ne!(nullifier, mac);
+        assert_ne!(note, hash(&input));
+    }
+
+    #[test]
+    fn domain_is_prepended_to_input() {
+        let [a, b] = INPUT.map(Fr::from);
+        assert_eq!(
+            hash_with_domain::<2, 3>(*NOTE_HASH_DOMAIN, &[a, b]),
+            hash(&[*NOTE_HASH_DOMAIN, a, b])
+        );
+    }
+
+    #[derive(Clone, Debug, Default)]
+    struct DomainHashCircuit([Fr; 2]);
+
+    impl Circuit<Fr> for DomainHashCircuit {
+        type Config = (
+            ColumnPool<Advice, PreSynthesisPhase>,
+            PoseidonChip,
+            Column<Instance>,
+        );
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+            let configs_builder = ConfigsBuilder::new(meta).with_poseidon();
+            let poseidon = configs_builder.poseidon_chip();
+
+            (configs_builder.finish(), poseidon, instance)
+        }
+
+        fn synthesize(
+            &self,
+            (pool, poseidon, instance): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let pool = pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+
+            let input = self.0.embed(&mut synthesizer, "input")?;
+            let digest = circuit::hash_with_domain::<2, 3>(
+                &mut synthesizer,
+                poseidon,
+                *MAC_HASH_DOMAIN,
+                input,
+            )?;
+
+            synthesizer.constrain_instance(digest.cell(), instance, 0)
+        }
+    }
+
+    fn verify(digest: Fr) -> Result<(), Vec<VerifyFailure>> {
+        MockProver::run(
+            7,
+            &DomainHashCircuit(INPUT.map(Fr::from)),
+            vec![vec![digest]],
+        )
+        .expect("Mock prover should run successfully")
+        .verify()
+    }
+
+    #[test]
+    fn circuit_hash_matches_off_circuit_one() {
+        let input = INPUT.map(Fr::from);
+
+        assert!(verify(hash_with_domain::<2, 3>(*MAC_HASH_DOMAIN, &input)).is_ok());
+        assert!(verify(hash_with_domain::<2, 3>(*NOTE_HASH_DOMAIN, &input)).is_err());
+    }
 }
@@ -0,0 +1,284 @@
+//! Absorb/squeeze interface for hashing inputs of arbitrary length.
+//!
+//! The squeezed value depends on the number `n` of absorbed elements:
+//!  - for `n <= POSEIDON_RATE` it is `hash::<n>(input)`, so in particular absorbing `POSEIDON_RATE`
+//!    elements gives the same result as the fixed-length `hash`,
+//!  - otherwise, the input is split into `POSEIDON_RATE`-long chunks (the last one padded with
+//!    zeros) and chained as `h_{i+1} = hash([h_i, chunk_i])`, starting from `h_0 = n`.
+//!
+//! Hashes of different lengths are domain-separated, so the two cases never collide, and binding
+//! `n` makes the zero padding unambiguous.
+
+use crate::consts::POSEIDON_RATE;
+
+static_assertions::const_assert_eq!(POSEIDON_RATE, 7);
+
+/// Length of the hash used for chaining: the previous digest and a single chunk.
+const CHAINED_LENGTH: usize = POSEIDON_RATE + 1;
+
+/// Evaluates `$body` with `$input` bound to `$items` converted to an array of the matching length.
+macro_rules! with_constant_length {
+    (@arm $items:expr, $input:ident, $body:expr, $len:literal) => {{
+        let $input: [_; $len] = core::array::from_fn(|i| $items[i].clone());
+        $body
+    }};
+    ($items:expr, |$input:ident| $body:expr) => {
+        match $items.len() {
+            0 => with_constant_length!(@arm $items, $input, $body, 0),
+            1 => with_constant_length!(@arm $items, $input, $body, 1),
+            2 => with_constant_length!(@arm $items, $input, $body, 2),
+            3 => with_constant_length!(@arm $items, $input, $body, 3),
+            4 => with_constant_length!(@arm $items, $input, $body, 4),
+            5 => with_constant_length!(@arm $items, $input, $body, 5),
+            6 => with_constant_length!(@arm $items, $input, $body, 6),
+            7 => with_constant_length!(@arm $items, $input, $body, 7),
+            _ => unreachable!("at most POSEIDON_RATE items are hashed directly"),
+        }
+    };
+}
+
+pub mod off_circuit {
+    use alloc::{vec, vec::Vec};
+
+    use super::CHAINED_LENGTH;
+    use crate::{consts::POSEIDON_RATE, poseidon::off_circuit::hash, Field, Fr};
+
+    /// Off-circuit sponge (see the module documentation for the exact construction).
+    #[derive(Clone, Debug, Default)]
+    pub struct Sponge {
+        absorbed: Vec<Fr>,
+    }
+
+    impl Sponge {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn absorb(&mut self, elems: &[Fr]) {
+            self.absorbed.extend_from_slice(elems);
+        }
+
+        /// Returns the hash of everything absorbed so far. The sponge then continues from the
+        /// returned value, i.e., it behaves as if only the returned value had been absorbed.
+        pub fn squeeze(&mut self) -> Fr {
+            let digest = if self.absorbed.len() <= POSEIDON_RATE {
+                with_constant_length!(self.absorbed, |input| hash(&input))
+            } else {
+                self.absorbed.chunks(POSEIDON_RATE).fold(
+                    Fr::from(self.absorbed.len() as u64),
+                    |last, chunk| {
+                        let mut input = [Fr::ZERO; CHAINED_LENGTH];
+                        input[0] = last;
+                        input[1..=chunk.len()].copy_from_slice(chunk);
+                        hash(&input)
+                    },
+                )
+            };
+
+            self.absorbed = vec![digest];
+            digest
+        }
+    }
+}
+
+pub mod circuit {
+    use alloc::{vec, vec::Vec};
+    use core::array;
+
+    use halo2_proofs::plonk::Error;
+
+    use super::CHAINED_LENGTH;
+    use crate::{
+        consts::POSEIDON_RATE,
+        poseidon::circuit::{hash, PoseidonChip},
+        synthesizer::Synthesizer,
+        AssignedCell, Fr,
+    };
+
+    /// In-circuit counterpart of [`super::off_circuit::Sponge`].
+    #[derive(Clone, Debug)]
+    pub struct Sponge {
+        poseidon: PoseidonChip,
+        absorbed: Vec<AssignedCell>,
+    }
+
+    impl Sponge {
+        pub fn new(poseidon: PoseidonChip) -> Self {
+            Self {
+                poseidon,
+                absorbed: vec![],
+            }
+        }
+
+        pub fn absorb(&mut self, elems: &[AssignedCell]) {
+            self.absorbed.extend_from_slice(elems);
+        }
+
+        /// Returns the hash of everything absorbed so far. The sponge then continues from the
+        /// returned value, i.e., it behaves as if only the returned value had been absorbed.
+        pub fn squeeze(
+            &mut self,
+            synthesizer: &mut impl Synthesizer,
+        ) -> Result<AssignedCell, Error> {
+            let digest = if self.absorbed.len() <= POSEIDON_RATE {
+                with_constant_length!(self.absorbed, |input| hash(
+                    synthesizer,
+                    self.poseidon.clone(),
+                    input
+                ))?
+            } else {
+                let zero_cell = synthesizer.zero_cell()?;
+                let mut last = synthesizer
+                    .assign_constant("sponge_length", Fr::from(self.absorbed.len() as u64))?;
+
+                for chunk in self.absorbed.chunks(POSEIDON_RATE) {
+                    let mut input: [_; CHAINED_LENGTH] = array::from_fn(|_| zero_cell.clone());
+                    input[0] = last;
+                    input[1..=chunk.len()].clone_from_slice(chunk);
+                    last = hash(synthesizer, self.poseidon.clone(), input)?;
+                }
+                last
+            };
+
+            self.absorbed = vec![digest.clone()];
+            Ok(digest)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+
+    use halo2_proofs::{
+        circuit::{floor_planner::V1, Layouter},
+        dev::MockProver,
+        plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+    };
+
+    use super::{circuit, off_circuit::Sponge};
+    use crate::{
+        column_pool::{ColumnPool, PreSynthesisPhase},
+        config_builder::ConfigsBuilder,
+        consts::POSEIDON_RATE,
+        embed::Embed,
+        poseidon::{circuit::PoseidonChip, off_circuit::hash},
+        synthesizer::create_synthesizer,
+        Fr,
+    };
+
+    fn input(len: usize) -> Vec<Fr> {
+        (0..len as u64).map(|i| Fr::from(i + 1)).collect()
+    }
+
+    fn squeeze(input: &[Fr]) -> Fr {
+        let mut sponge = Sponge::new();
+        sponge.absorb(input);
+        sponge.squeeze()
+    }
+
+    #[test]
+    fn rate_long_input_matches_fixed_length_hash() {
+        let input: [Fr; POSEIDON_RATE] = input(POSEIDON_RATE).try_into().unwrap();
+        assert_eq!(squeeze(&input), hash(&input));
+    }
+
+    #[test]
+    fn split_absorption_matches_single_absorption() {
+        let input = input(20);
+
+        let mut sponge = Sponge::new();
+        sponge.absorb(&input[..5]);
+        sponge.absorb(&input[5..]);
+
+        assert_eq!(sponge.squeeze(), squeeze(&input));
+    }
+
+    #[test]
+    fn trailing_zeros_change_the_digest() {
+        let mut padded = input(10);
+        padded.extend([Fr::from(0); 4]);
+
+        assert_ne!(squeeze(&input(10)), squeeze(&padded));
+        assert_ne!(
+            squeeze(&input(3)),
+            squeeze(&[input(3), vec![Fr::from(0)]].concat())
+        );
+    }
+
+    #[derive(Clone, Debug, Default)]
+    struct SpongeCircuit(Vec<Fr>);
+
+    impl Circuit<Fr> for SpongeCircuit {
+        type Config = (
+            ColumnPool<Advice, PreSynthesisPhase>,
+            PoseidonChip,
+            Column<Instance>,
+        );
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self(vec![Fr::from(0); self.0.len()])
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+            let configs_builder = ConfigsBuilder::new(meta).with_poseidon();
+            let poseidon = configs_builder.poseidon_chip();
+
+            (configs_builder.finish(), poseidon, instance)
+        }
+
+        fn synthesize(
+            &self,
+            (pool, poseidon, instance): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let pool = pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+
+            let input = self
+                .0
+                .iter()
+                .map(|item| item.embed(&mut synthesizer, "input"))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let mut sponge = circuit::Sponge::new(poseidon);
+            sponge.absorb(&input);
+            let digest = sponge.squeeze(&mut synthesizer)?;
+
+            synthesizer.constrain_instance(digest.cell(), instance, 0)
+        }
+    }
+
+    fn verify(input: Vec<Fr>, digest: Fr) -> Result<(), Vec<String>> {
+        MockProver::run(10, &SpongeCircuit(input), vec![vec![digest]])
+            .expect("Mock prover should run successfully")
+            .verify()
+            .map_err(|errors| {
+                errors
+                    .into_iter()
+                    .map(|failure| failure.to_string())
+                    .collect()
+            })
+    }
+
+    #[test]
+    fn circuit_sponge_matches_off_circuit_one() {
+        for len in [3, POSEIDON_RATE, 16] {
+            let input = input(len);
+            assert!(verify(input.clone(), squeeze(&input)).is_ok());
+        }
+    }
+
+    #[test]
+    fn circuit_sponge_output_is_constrained() {
+        let input = input(16);
+        assert!(verify(input.clone(), squeeze(&input) + Fr::from(1)).is_err());
+    }
+}
@@ -0,0 +1,300 @@
+use alloc::{format, vec::Vec};
+use core::array;
+
+use halo2_proofs::{
+    circuit::{Region, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error, Expression, Fixed, Selector},
+    poly::Rotation,
+};
+
+use crate::{
+    chips::sum::SumChip,
+    gates::ensure_unique_columns,
+    poseidon2::{
+        apply_round, external_linear_layer, internal_linear_layer, is_full_round, sbox, RATE,
+        ROUNDS, ROUND_CONSTANTS, WIDTH,
+    },
+    synthesizer::Synthesizer,
+    AssignedCell, Field, Fr,
+};
+
+/// Chip computing the Poseidon2 permutation. The whole permutation is laid out in a single region,
+/// one row per round, with the state kept in `WIDTH` advice columns:
+///  - row `0` holds the input state,
+///  - row `1` holds the state after the initial linear layer,
+///  - row `r + 2` holds the state after round `r`.
+#[derive(Clone, Debug)]
+pub struct Poseidon2Chip {
+    state: [Column<Advice>; WIDTH],
+    round_constants: [Column<Fixed>; WIDTH],
+    initial_linear_layer: Selector,
+    full_round: Selector,
+    partial_round: Selector,
+    sum: SumChip,
+}
+
+impl Poseidon2Chip {
+    /// Registers the gates of the permutation. `sum` is used for absorbing the input in `hash`.
+    pub fn configure(
+        cs: &mut ConstraintSystem<Fr>,
+        state: [Column<Advice>; WIDTH],
+        round_constants: [Column<Fixed>; WIDTH],
+        sum: SumChip,
+    ) -> Self {
+        ensure_unique_columns(&state);
+        let initial_linear_layer = cs.selector();
+        let full_round = cs.selector();
+        let partial_round = cs.selector();
+
+        cs.create_gate("Poseidon2 initial linear layer", |vc| {
+            let selector = vc.query_selector(initial_linear_layer);
+            let current = state.map(|column| vc.query_advice(column, Rotation::cur()));
+            let next = state.map(|column| vc.query_advice(column, Rotation::next()));
+
+            constrain_transition(selector, next, external_linear_layer(current))
+        });
+
+        cs.create_gate("Poseidon2 full round", |vc| {
+            let selector = vc.query_selector(full_round);
+            let current = state.map(|column| vc.query_advice(column, Rotation::cur()));
+            let constants = round_constants.map(|column| vc.query_fixed(column, Rotation::cur()));
+            let next = state.map(|column| vc.query_advice(column, Rotation::next()));
+
+            let after_sbox = array::from_fn(|i| sbox(current[i].clone() + constants[i].clone()));
+            constrain_transition(selector, next, external_linear_layer(after_sbox))
+        });
+
+        cs.create_gate("Poseidon2 partial round", |vc| {
+            let selector = vc.query_selector(partial_round);
+            let mut current = state.map(|column| vc.query_advice(column, Rotation::cur()));
+            let constant = vc.query_fixed(round_constants[0], Rotation::cur());
+            let next = state.map(|column| vc.query_advice(column, Rotation::next()));
+
+            current[0] = sbox(current[0].clone() + constant);
+            constrain_transition(selector, next, internal_linear_layer(current))
+        });
+
+        Self {
+            state,
+            round_constants,
+            initial_linear_layer,
+            full_round,
+            partial_round,
+            sum,
+        }
+    }
+
+    /// Applies the permutation to `input` and returns the resulting state.
+    pub fn permute(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        input: [AssignedCell; WIDTH],
+    ) -> Result<[AssignedCell; WIDTH], Error> {
+        synthesizer.assign_region(
+            || "Poseidon2 permutation",
+            |mut region| {
+                let mut state_value = Value::known([Fr::ZERO; WIDTH]);
+                for (i, cell) in input.iter().enumerate() {
+                    cell.copy_advice(|| format!("input[{i}]"), &mut region, self.state[i], 0)?;
+                    state_value = state_value.zip(cell.value()).map(|(mut state, value)| {
+                        state[i] = *value;
+                        state
+                    });
+                }
+
+                self.initial_linear_layer.enable(&mut region, 0)?;
+                state_value = state_value.map(external_linear_layer);
+                let mut state = self.assign_state(&mut region, 1, state_value)?;
+
+                for round in 0..ROUNDS {
+                    let row = round + 1;
+                    if is_full_round(round) {
+                        self.full_round.enable(&mut region, row)?;
+                    } else {
+                        self.partial_round.enable(&mut region, row)?;
+                    }
+                    for (i, column) in self.round_constants.iter().enumerate() {
+                        region.assign_fixed(
+                            || format!("round constant[{i}]"),
+                            *column,
+                            row,
+                            || Value::known(ROUND_CONSTANTS[round][i]),
+                        )?;
+                    }
+
+                    state_value = state_value.map(|state| apply_round(round, state));
+                    state = self.assign_state(&mut region, row + 1, state_value)?;
+                }
+
+                Ok(state)
+            },
+        )
+    }
+
+    fn assign_state(
+        &self,
+        region: &mut Region<'_, Fr>,
+        row: usize,
+        state: Value<[Fr; WIDTH]>,
+    ) -> Result<[AssignedCell; WIDTH], Error> {
+        let cells = (0..WIDTH)
+            .map(|i| {
+                region.assign_advice(
+                    || format!("state[{i}]"),
+                    self.state[i],
+                    row,
+                    || state.map(|state| state[i]),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(cells
+            .try_into()
+            .expect("exactly `WIDTH` cells are assigned"))
+    }
+
+    fn add(
+        &self,
+        synthesizer: &mut impl Synthesizer,
+        a: AssignedCell,
+        b: AssignedCell,
+    ) -> Result<AssignedCell, Error> {
+        let sum = synthesizer.assign_value("absorbed", a.value() + b.value())?;
+        self.sum.constrain_sum(synthesizer, a, b, sum.clone())?;
+        Ok(sum)
+    }
+}
+
+fn constrain_transition(
+    selector: Expression<Fr>,
+    next: [Expression<Fr>; WIDTH],
+    expected: [Expression<Fr>; WIDTH],
+) -> Vec<Expression<Fr>> {
+    next.into_iter()
+        .zip(expected)
+        .map(|(next, expected)| selector.clone() * (next - expected))
+        .collect()
+}
+
+/// Compute Poseidon2 hash of `input` (in-circuit). Mirrors `poseidon2::off_circuit::hash`.
+pub fn hash<const LENGTH: usize>(
+    synthesizer: &mut impl Synthesizer,
+    poseidon2_chip: Poseidon2Chip,
+    input: [AssignedCell; LENGTH],
+) -> Result<AssignedCell, Error> {
+    const { assert!(LENGTH > 0, "cannot hash empty input") };
+
+    let zero = synthesizer.zero_cell()?;
+    let mut state: [AssignedCell; WIDTH] = array::from_fn(|_| zero.clone());
+    state[RATE] = synthesizer.assign_constant("length", Fr::from(LENGTH as u64))?;
+
+    for chunk in input.chunks(RATE) {
+        for (element, absorbed) in state.iter_mut().zip(chunk) {
+            *element = poseidon2_chip.add(synthesizer, element.clone(), absorbed.clone())?;
+        }
+        state = poseidon2_chip.permute(synthesizer, state)?;
+    }
+
+    Ok(state[0].clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec;
+
+    use halo2_proofs::{
+        circuit::{floor_planner::V1, Layouter},
+        dev::MockProver,
+        plonk::{Advice, Circuit, ConstraintSystem, Error, Fixed},
+    };
+    use strum_macros::{EnumCount, EnumIter};
+
+    use crate::{
+        chips::sum::SumChip,
+        column_pool::{AccessColumn, ColumnPool, PreSynthesisPhase},
+        embed::Embed,
+        gates::{sum::SumGate, Gate},
+        instance_wrapper::InstanceWrapper,
+        poseidon2::{
+            circuit::{hash, Poseidon2Chip},
+            off_circuit, WIDTH,
+        },
+        synthesizer::create_synthesizer,
+        Fr,
+    };
+
+    const LENGTH: usize = 5;
+
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, EnumIter, EnumCount)]
+    enum TestInstance {
+        Digest,
+    }
+
+    #[derive(Clone, Debug, Default)]
+    struct HashCircuit([Fr; LENGTH]);
+
+    impl Circuit<Fr> for HashCircuit {
+        type Config = (
+            ColumnPool<Advice, PreSynthesisPhase>,
+            Poseidon2Chip,
+            InstanceWrapper<TestInstance>,
+        );
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let instance = InstanceWrapper::new(meta);
+
+            let mut advice_pool = ColumnPool::<Advice, _>::new();
+            let sum = SumChip::new(SumGate::create_gate(meta, &mut advice_pool));
+            advice_pool.ensure_capacity(meta, WIDTH);
+
+            let mut fixed_pool = ColumnPool::<Fixed, _>::new();
+            fixed_pool.ensure_capacity(meta, WIDTH);
+
+            let chip = Poseidon2Chip::configure(
+                meta,
+                advice_pool.get_column_array(),
+                fixed_pool.get_column_array(),
+                sum,
+            );
+
+            (advice_pool.conclude_configuration(), chip, instance)
+        }
+
+        fn synthesize(
+            &self,
+            (pool, chip, instance): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let pool = pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+            let input = self.0.embed(&mut synthesizer, "input")?;
+            let digest = hash(&mut synthesizer, chip, input)?;
+            instance.constrain_cells(&mut synthesizer, [(digest, TestInstance::Digest)])
+        }
+    }
+
+    fn verify(input: [Fr; LENGTH], expected_digest: Fr) -> bool {
+        MockProver::run(8, &HashCircuit(input), vec![vec![expected_digest]])
+            .expect("Mock prover should run successfully")
+            .verify()
+            .is_ok()
+    }
+
+    #[test]
+    fn matches_off_circuit_hash() {
+        let input = [1u64, 2, 3, 4, 5].map(Fr::from);
+
+        assert!(verify(input, off_circuit::hash(&input)));
+    }
+
+    #[test]
+    fn incorrect_digest_fails() {
+        let input = [1u64, 2, 3, 4, 5].map(Fr::from);
+
+        assert!(!verify(input, off_circuit::hash(&input) + Fr::from(1)));
+    }
+}
@@ -1,13 +1,17 @@
-use alloc::string::String;
+#[cfg(feature = "diagnostics")]
+use alloc::collections::BTreeSet;
+use alloc::{rc::Rc, string::String};
+use core::cell::RefCell;
 
 use halo2_proofs::{
     circuit::{Cell, Layouter, NamespacedLayouter, Region, Table},
-    plonk::{Advice, Challenge, Column, Error, Instance},
+    plonk::{Advice, Any, Challenge, Column, Error, Instance},
 };
 
 use crate::{
     column_pool::{AccessColumn, ColumnPool, SynthesisPhase},
-    AssignedCell, Fr, Value,
+    consts::MAX_K,
+    AssignedCell, Field, Fr, Value,
 };
 
 /// A `Synthesizer` is a layouter that can also access advice columns with some inner load balancing.
@@ -34,6 +38,37 @@ pub trait Synthesizer: Layouter<Fr> + AccessColumn<Advice> {
         name: impl Into<String>,
         constant: Fr,
     ) -> Result<AssignedCell, Error>;
+
+    /// Returns a cell constrained to zero. The constant is assigned only once per synthesis
+    /// (shared with all namespaced synthesizers) and the same cell is returned afterwards.
+    fn zero_cell(&mut self) -> Result<AssignedCell, Error>;
+
+    /// Notes that the selector of the gate named `gate` is enabled. Called by every `Gate`
+    /// application; a no-op unless the `diagnostics` feature is on.
+    fn record_enabled_gate(&mut self, gate: &'static str);
+
+    /// Names of the gates whose selectors have been enabled at least once so far (by this
+    /// synthesizer or any synthesizer namespaced from it). Lets tests check that they actually
+    /// exercise the gates they are meant to.
+    #[cfg(feature = "diagnostics")]
+    fn enabled_gates(&self) -> BTreeSet<&'static str>;
+
+    /// Constrains `cell` to be equal to the public input at `row` of `column`. Unlike
+    /// `InstanceWrapper::constrain_cells`, works with raw instance columns. Fails with
+    /// `Error::Synthesis` if `column` is not an instance column and with `Error::BoundsFailure` if
+    /// `row` cannot be a row of any circuit with `k <= MAX_K`.
+    fn constrain_instance_value(
+        &mut self,
+        cell: &AssignedCell,
+        column: Column<Any>,
+        row: usize,
+    ) -> Result<(), Error> {
+        let column = Column::<Instance>::try_from(column).map_err(|_| Error::Synthesis)?;
+        if row >= 1 << MAX_K {
+            return Err(Error::BoundsFailure);
+        }
+        self.constrain_instance(cell.cell(), column, row)
+    }
 }
 
 /// Creates a new synthesizer from a layouter and an advice pool.
@@ -44,12 +79,18 @@ pub fn create_synthesizer<'a, L: Layouter<Fr>>(
     SynthesizerImpl {
         layouter: layouter.namespace(|| "synthesizer"),
         advice_pool,
+        zero_cell: Default::default(),
+        #[cfg(feature = "diagnostics")]
+        enabled_gates: Default::default(),
     }
 }
 
 struct SynthesizerImpl<'a, L: Layouter<Fr>> {
     layouter: NamespacedLayouter<'a, Fr, L>,
     advice_pool: &'a ColumnPool<Advice, SynthesisPhase>,
+    zero_cell: Rc<RefCell<Option<AssignedCell>>>,
+    #[cfg(feature = "diagnostics")]
+    enabled_gates: Rc<RefCell<BTreeSet<&'static str>>>,
 }
 
 impl<L: Layouter<Fr>> Synthesizer for SynthesizerImpl<'_, L> {
@@ -57,6 +98,9 @@ impl<L: Layouter<Fr>> Synthesizer for SynthesizerImpl<'_, L> {
         SynthesizerImpl {
             layouter: self.layouter.namespace(|| name),
             advice_pool: self.advice_pool,
+            zero_cell: self.zero_cell.clone(),
+            #[cfg(feature = "diagnostics")]
+            enabled_gates: self.enabled_gates.clone(),
         }
     }
 
@@ -85,6 +129,28 @@ impl<L: Layouter<Fr>> Synthesizer for SynthesizerImpl<'_, L> {
             |mut region| region.assign_advice_from_constant(|| name.clone(), advice, 0, constant),
         )
     }
+
+    fn zero_cell(&mut self) -> Result<AssignedCell, Error> {
+        if let Some(zero) = self.zero_cell.borrow().clone() {
+            return Ok(zero);
+        }
+        let zero = self.assign_constant("Zero", Fr::ZERO)?;
+        *self.zero_cell.borrow_mut() = Some(zero.clone());
+        Ok(zero)
+    }
+
+    #[cfg(feature = "diagnostics")]
+    fn record_enabled_gate(&mut self, gate: &'static str) {
+        self.enabled_gates.borrow_mut().insert(gate);
+    }
+
+    #[cfg(not(feature = "diagnostics"))]
+    fn record_enabled_gate(&mut self, _gate: &'static str) {}
+
+    #[cfg(feature = "diagnostics")]
+    fn enabled_gates(&self) -> BTreeSet<&'static str> {
+        self.enabled_gates.borrow().clone()
+    }
 }
 
 /// Delegate `Layouter` implementation to the inner layouter.
@@ -149,3 +215,165 @@ impl<L: Layouter<Fr>> AccessColumn<Advice> for SynthesizerImpl<'_, L> {
         self.advice_pool.get_column_array()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::vec;
+
+    use halo2_proofs::{
+        circuit::{floor_planner::V1, Layouter},
+        dev::MockProver,
+        plonk::{Advice, Any, Circuit, Column, ConstraintSystem, Error, Fixed},
+    };
+    use strum_macros::{EnumCount, EnumIter};
+
+    use crate::{
+        column_pool::{AccessColumn, ColumnPool, PreSynthesisPhase},
+        instance_wrapper::InstanceWrapper,
+        synthesizer::{create_synthesizer, Synthesizer},
+        Field, Fr, Value,
+    };
+
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, EnumIter, EnumCount)]
+    enum TestInstance {
+        First,
+        Second,
+        Namespaced,
+    }
+
+    #[derive(Clone, Debug, Default)]
+    struct ZeroCellCircuit;
+
+    impl Circuit<Fr> for ZeroCellCircuit {
+        type Config = (
+            ColumnPool<Advice, PreSynthesisPhase>,
+            InstanceWrapper<TestInstance>,
+        );
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let instance = InstanceWrapper::new(meta);
+
+            let mut advice_pool = ColumnPool::<Advice, _>::new();
+            advice_pool.ensure_capacity(meta, 1);
+            let mut fixed_pool = ColumnPool::<Fixed, _>::new();
+            fixed_pool.ensure_capacity(meta, 1);
+
+            (advice_pool.conclude_configuration(), instance)
+        }
+
+        fn synthesize(
+            &self,
+            (pool, instance): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let pool = pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+
+            let first = synthesizer.zero_cell()?;
+            let second = synthesizer.zero_cell()?;
+            let namespaced = synthesizer.namespaced("namespace").zero_cell()?;
+
+            instance.constrain_cells(
+                &mut synthesizer,
+                [
+                    (first, TestInstance::First),
+                    (second, TestInstance::Second),
+                    (namespaced, TestInstance::Namespaced),
+                ],
+            )
+        }
+    }
+
+    fn verify(public_input: [Fr; 3]) -> bool {
+        MockProver::run(4, &ZeroCellCircuit, vec![public_input.to_vec()])
+            .expect("Mock prover should run successfully")
+            .verify()
+            .is_ok()
+    }
+
+    #[test]
+    fn repeated_zero_cells_are_zero() {
+        assert!(verify([Fr::ZERO; 3]));
+    }
+
+    #[test]
+    fn repeated_zero_cells_are_not_one() {
+        assert!(!verify([Fr::ZERO, Fr::ONE, Fr::ZERO]));
+        assert!(!verify([Fr::ZERO, Fr::ZERO, Fr::ONE]));
+    }
+
+    /// Constrains a cell with `self.0` to row 0 of either the instance column (`self.1 == false`)
+    /// or a fixed column (`self.1 == true`).
+    #[derive(Clone, Debug, Default)]
+    struct RawInstanceCircuit(Fr, bool);
+
+    impl Circuit<Fr> for RawInstanceCircuit {
+        type Config = (
+            ColumnPool<Advice, PreSynthesisPhase>,
+            Column<Any>,
+            Column<Any>,
+        );
+        type FloorPlanner = V1;
+
+        fn without_witnesses(&self) -> Self {
+            Self(Fr::ZERO, self.1)
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+
+            let mut advice_pool = ColumnPool::<Advice, _>::new();
+            advice_pool.ensure_capacity(meta, 1);
+            let mut fixed_pool = ColumnPool::<Fixed, _>::new();
+            fixed_pool.ensure_capacity(meta, 1);
+            let fixed = fixed_pool.get_any_column();
+
+            (
+                advice_pool.conclude_configuration(),
+                instance.into(),
+                fixed.into(),
+            )
+        }
+
+        fn synthesize(
+            &self,
+            (pool, instance, fixed): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let pool = pool.start_synthesis();
+            let mut synthesizer = create_synthesizer(&mut layouter, &pool);
+
+            let cell = synthesizer.assign_value("value", Value::known(self.0))?;
+            let column = if self.1 { fixed } else { instance };
+            synthesizer.constrain_instance_value(&cell, column, 0)
+        }
+    }
+
+    #[test]
+    fn cell_is_constrained_to_raw_instance_row() {
+        let run = |public_input| {
+            MockProver::run(
+                4,
+                &RawInstanceCircuit(Fr::from(5), false),
+                vec![vec![public_input]],
+            )
+            .expect("Mock prover should run successfully")
+            .verify()
+        };
+
+        assert!(run(Fr::from(5)).is_ok());
+        assert!(run(Fr::from(6)).is_err());
+    }
+
+    #[test]
+    fn non_instance_column_is_rejected() {
+        let result = MockProver::run(4, &RawInstanceCircuit(Fr::from(5), true), vec![vec![]]);
+        assert!(matches!(result, Err(Error::Synthesis)));
+    }
+}
@@ -1,15 +1,65 @@
+use core::fmt::{self, Display, Formatter};
+
 use crate::Fr;
 
-pub const NOTE_VERSION: NoteVersion = NoteVersion(0);
+pub const NOTE_VERSION: NoteVersion = NoteVersion::V0;
+
+/// Version of the layout produced by `marshall::write_versioned`. Must be bumped whenever the
+/// serialized representation of the wrapped artifacts changes (e.g. a new `SERDE_FORMAT`).
+pub const MARSHALL_VERSION: u16 = 1;
 
-#[derive(Copy, Clone, Debug)]
-pub struct NoteVersion(u8);
+/// Version of the meaning of the `Commitment` public input of the deposit and withdraw circuits.
+/// Must be bumped whenever the way it is derived changes:
+///  - `0`: an opaque value, chosen by the caller and only copied to the public input,
+///  - `1`: `hash(COMMITMENT_VERSION, recipient, amount, nonce)`, computed in-circuit.
+pub const COMMITMENT_VERSION: u8 = 1;
+
+/// Version of a note, which determines the layout of its hash (see [`NoteVersion::hash_layout`]).
+/// Only the supported versions are representable.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NoteVersion {
+    V0 = 0,
+    V1 = 1,
+}
 
 impl NoteVersion {
-    pub fn new(note_version: u8) -> Self {
-        Self(note_version)
+    pub fn new(note_version: u8) -> Result<Self, UnsupportedNoteVersion> {
+        match note_version {
+            0 => Ok(NoteVersion::V0),
+            1 => Ok(NoteVersion::V1),
+            version => Err(UnsupportedNoteVersion(version)),
+        }
     }
+
     pub fn as_field(&self) -> Fr {
-        Fr::from(self.0 as u64)
+        Fr::from(*self as u64)
+    }
+
+    /// The shape of the hash of notes of this version.
+    pub fn hash_layout(&self) -> NoteLayout {
+        match self {
+            NoteVersion::V0 => NoteLayout::Nested,
+            NoteVersion::V1 => NoteLayout::Flat,
+        }
     }
 }
+
+/// Returned by [`NoteVersion::new`] for a version without a known note layout.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct UnsupportedNoteVersion(pub u8);
+
+impl Display for UnsupportedNoteVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Unsupported note version: {}", self.0)
+    }
+}
+
+/// How the fields of a note are fed to Poseidon to compute the note hash.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NoteLayout {
+    /// `hash(version, id, nullifier, hash(account_balance, token_address, 0, 0, 0, 0, 0))`, the
+    /// shape of notes created by the 1st version of Shielder.
+    Nested,
+    /// `hash(version, id, nullifier, account_balance, token_address)`.
+    Flat,
+}